@@ -0,0 +1,230 @@
+//! Proof authentication through transaction introspection.
+//!
+//! Every transaction that mines must declare upfront the proof account it mines with, by including
+//! an auth payload as the data of an instruction to the noop program. Mine and chop parse the
+//! instructions sysvar to find the payload and reject any proof that does not match it. This stops
+//! miners from packing hashes from many proofs into a single transaction to save on fees.
+
+use bytemuck::{Pod, Zeroable};
+use solana_program::{
+    instruction::Instruction, program_error::ProgramError, pubkey::Pubkey,
+};
+
+use crate::{consts::NOOP_PROGRAM_ID, error::CoalError};
+
+/// The magic prefix of an auth payload.
+pub const AUTH_MAGIC: [u8; 4] = *b"COAL";
+
+/// The current version of the auth payload layout.
+pub const AUTH_VERSION: u8 = 1;
+
+/// The data of an auth instruction.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct AuthPayload {
+    /// The magic prefix identifying the payload.
+    pub magic: [u8; 4],
+
+    /// The version of the payload layout.
+    pub version: u8,
+
+    /// The proof account authorized to mine in the transaction.
+    pub proof: Pubkey,
+
+    /// The mint of the resource being mined.
+    pub mint: Pubkey,
+}
+
+impl AuthPayload {
+    pub fn new(proof: Pubkey, mint: Pubkey) -> Self {
+        Self {
+            magic: AUTH_MAGIC,
+            version: AUTH_VERSION,
+            proof,
+            mint,
+        }
+    }
+
+    /// Returns the payload if the data is a valid auth payload of the current version.
+    pub fn try_from_bytes(data: &[u8]) -> Option<&Self> {
+        let payload = bytemuck::try_from_bytes::<Self>(data).ok()?;
+        if payload.magic.ne(&AUTH_MAGIC) || payload.version.ne(&AUTH_VERSION) {
+            return None;
+        }
+        Some(payload)
+    }
+
+    pub fn to_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+}
+
+/// Builds an auth instruction.
+pub fn auth(proof: Pubkey, mint: Pubkey) -> Instruction {
+    Instruction {
+        program_id: NOOP_PROGRAM_ID,
+        accounts: vec![],
+        data: AuthPayload::new(proof, mint).to_bytes().to_vec(),
+    }
+}
+
+/// Errors if the transaction does not declare the given proof account for the given mint.
+pub fn authenticate(
+    instructions_data: &[u8],
+    proof: &Pubkey,
+    mint: &Pubkey,
+) -> Result<(), ProgramError> {
+    match parse_auth_payload(instructions_data, mint) {
+        Some(payload) if payload.proof.eq(proof) => Ok(()),
+        _ => Err(CoalError::AuthFailed.into()),
+    }
+}
+
+/// Returns the first auth payload for the given mint in the instructions sysvar data.
+///
+/// Instructions to other programs, and noop instructions which do not carry a valid payload for
+/// the mint, are skipped, so compute budget and other instructions may appear in any order.
+pub fn parse_auth_payload(instructions_data: &[u8], mint: &Pubkey) -> Option<AuthPayload> {
+    instructions(instructions_data)
+        .filter(|(program_id, _)| program_id.eq(&NOOP_PROGRAM_ID.as_ref()))
+        .filter_map(|(_, data)| AuthPayload::try_from_bytes(data))
+        .find(|payload| payload.mint.eq(mint))
+        .copied()
+}
+
+/// Iterates the program id and data of each instruction in the instructions sysvar data, stopping
+/// at the first malformed instruction.
+pub(crate) fn instructions(instructions_data: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    let num_instructions = read_u16(instructions_data, 0).unwrap_or(0);
+    (0..num_instructions as usize).map_while(move |i| read_instruction(instructions_data, i))
+}
+
+fn read_instruction(instructions_data: &[u8], index: usize) -> Option<(&[u8], &[u8])> {
+    // Find the start of the instruction.
+    let mut curr = read_u16(instructions_data, 2 + index * 2)? as usize;
+
+    // Skip the account metas.
+    let num_accounts = read_u16(instructions_data, curr)? as usize;
+    curr = curr.checked_add(2)?.checked_add(num_accounts.checked_mul(33)?)?;
+
+    // Read the program id.
+    let program_id = instructions_data.get(curr..curr.checked_add(32)?)?;
+    curr += 32;
+
+    // Read the instruction data.
+    let data_len = read_u16(instructions_data, curr)? as usize;
+    curr += 2;
+    let data = instructions_data.get(curr..curr.checked_add(data_len)?)?;
+    Some((program_id, data))
+}
+
+pub(crate) fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_program::{instruction::AccountMeta, pubkey};
+
+    use super::*;
+    use crate::{
+        consts::{COAL_MINT_ADDRESS, WOOD_MINT_ADDRESS},
+        test_utils::sysvar_data,
+    };
+
+    fn compute_budget_instruction(discriminator: u8, value: u32) -> Instruction {
+        Instruction {
+            program_id: pubkey!("ComputeBudget111111111111111111111111111111"),
+            accounts: vec![],
+            data: [vec![discriminator], value.to_le_bytes().to_vec()].concat(),
+        }
+    }
+
+    fn other_instruction() -> Instruction {
+        Instruction {
+            program_id: crate::id(),
+            accounts: vec![
+                AccountMeta::new(Pubkey::new_unique(), true),
+                AccountMeta::new_readonly(Pubkey::new_unique(), false),
+            ],
+            data: vec![2; 24],
+        }
+    }
+
+    #[test]
+    fn test_authenticate_with_instructions_in_any_order() {
+        let proof = Pubkey::new_unique();
+        let data = sysvar_data(&[
+            compute_budget_instruction(2, 500_000),
+            other_instruction(),
+            auth(proof, COAL_MINT_ADDRESS),
+            compute_budget_instruction(3, 1_000),
+            other_instruction(),
+        ]);
+        assert!(authenticate(&data, &proof, &COAL_MINT_ADDRESS).is_ok());
+    }
+
+    #[test]
+    fn test_authenticate_rejects_other_proof() {
+        let data = sysvar_data(&[auth(Pubkey::new_unique(), COAL_MINT_ADDRESS)]);
+        assert!(authenticate(&data, &Pubkey::new_unique(), &COAL_MINT_ADDRESS).is_err());
+    }
+
+    #[test]
+    fn test_authenticate_rejects_other_mint() {
+        let proof = Pubkey::new_unique();
+        let data = sysvar_data(&[auth(proof, COAL_MINT_ADDRESS)]);
+        assert!(authenticate(&data, &proof, &WOOD_MINT_ADDRESS).is_err());
+    }
+
+    #[test]
+    fn test_parse_skips_noops_without_payload() {
+        let proof = Pubkey::new_unique();
+        let legacy = Instruction {
+            program_id: NOOP_PROGRAM_ID,
+            accounts: vec![],
+            data: Pubkey::new_unique().to_bytes().to_vec(),
+        };
+        let mut wrong_version = auth(Pubkey::new_unique(), COAL_MINT_ADDRESS);
+        wrong_version.data[4] = AUTH_VERSION + 1;
+        let data = sysvar_data(&[legacy, wrong_version, auth(proof, COAL_MINT_ADDRESS)]);
+        assert_eq!(
+            parse_auth_payload(&data, &COAL_MINT_ADDRESS),
+            Some(AuthPayload::new(proof, COAL_MINT_ADDRESS))
+        );
+    }
+
+    #[test]
+    fn test_parse_shared_by_resources() {
+        let coal_proof = Pubkey::new_unique();
+        let wood_proof = Pubkey::new_unique();
+        let data = sysvar_data(&[
+            auth(wood_proof, WOOD_MINT_ADDRESS),
+            auth(coal_proof, COAL_MINT_ADDRESS),
+        ]);
+        assert!(authenticate(&data, &coal_proof, &COAL_MINT_ADDRESS).is_ok());
+        assert!(authenticate(&data, &wood_proof, &WOOD_MINT_ADDRESS).is_ok());
+    }
+
+    #[test]
+    fn test_parse_truncated_data() {
+        let proof = Pubkey::new_unique();
+        let data = sysvar_data(&[other_instruction(), auth(proof, COAL_MINT_ADDRESS)]);
+        for len in 0..data.len() - 2 {
+            assert_eq!(parse_auth_payload(&data[..len], &COAL_MINT_ADDRESS), None);
+        }
+        assert_eq!(parse_auth_payload(&[], &COAL_MINT_ADDRESS), None);
+    }
+
+    #[test]
+    fn test_parse_crafted_offsets() {
+        // A single instruction whose offset points past the end of the data.
+        let mut data = vec![1, 0, 0xff, 0xff];
+        assert_eq!(parse_auth_payload(&data, &COAL_MINT_ADDRESS), None);
+
+        // An instruction claiming more accounts than the data holds.
+        data = vec![1, 0, 4, 0, 0xff, 0xff];
+        assert_eq!(parse_auth_payload(&data, &COAL_MINT_ADDRESS), None);
+    }
+}
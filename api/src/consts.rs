@@ -21,6 +21,44 @@ pub const BASE_WOOD_REWARD_RATE_MIN_THRESHOLD: u64 = 2u64.pow(5) * WOOD_EXTRACTI
 /// The maximum allowed base reward rate, at which point the min difficulty should be decreased.
 pub const BASE_WOOD_REWARD_RATE_MAX_THRESHOLD: u64 = 2u64.pow(8) * WOOD_EXTRACTION_MULTIPLIER;
 
+/// The referral bonus to initialize the program with (5%).
+pub const INITIAL_REFERRAL_BONUS_BPS: u64 = 500;
+
+/// The maximum allowed referral bonus (10%).
+pub const MAX_REFERRAL_BONUS_BPS: u64 = 1_000;
+
+/// The number of epochs a referral remains valid to initialize the program with.
+pub const INITIAL_REFERRAL_EPOCHS: u64 = 5_000;
+
+/// The initial time without hashes after which an empty proof can be reaped by anyone (90 days).
+pub const INITIAL_REAP_AFTER: i64 = ONE_MINUTE * 60 * 24 * 90;
+
+/// The share of the rent of a reaped proof paid to the reaper to initialize the program with (10%).
+pub const INITIAL_REAPER_BONUS_BPS: u64 = 1_000;
+
+/// The maximum allowed share of the rent of a reaped proof paid to the reaper (50%).
+pub const MAX_REAPER_BONUS_BPS: u64 = 5_000;
+
+/// The share of each epoch's emissions held for the jackpot to initialize the program with (2%).
+pub const INITIAL_JACKPOT_BPS: u64 = 200;
+
+/// The maximum allowed share of each epoch's emissions held for the jackpot (10%).
+pub const MAX_JACKPOT_BPS: u64 = 1_000;
+
+/// The difficulty above the minimum a hash must reach to win the jackpot to initialize the program with.
+pub const INITIAL_JACKPOT_DIFFICULTY: u64 = 12;
+
+/// The share of the jackpot won by a hash at the jackpot difficulty (25%). The share doubles for every
+/// bit of difficulty beyond it.
+pub const JACKPOT_PAYOUT_BPS: u64 = 2_500;
+
+/// The share of an even split of the remaining rewards below which a bus counts as depleted and the
+/// busses can be rebalanced (10%).
+pub const REBALANCE_THRESHOLD_BPS: u64 = 1_000;
+
+/// The denominator for values expressed in basis points.
+pub const BASIS_POINTS: u64 = 10_000;
+
 /// The spam/liveness tolerance in seconds.
 pub const TOLERANCE: i64 = 5;
 
@@ -40,6 +78,22 @@ pub const ONE_COAL: u64 = 10u64.pow(TOKEN_DECIMALS as u32);
 /// One WOOD token, denominated in indivisible units.
 pub const ONE_WOOD: u64 = 10u64.pow(TOKEN_DECIMALS as u32);
 
+/// The smallest deposit accepted on behalf of another miner, in indivisible units of either resource.
+/// Every deposit restarts the target's stake timer, so this puts a price on delaying its multiplier.
+pub const MIN_STAKE_FOR_AMOUNT: u64 = ONE_COAL;
+
+/// One ORE token, denominated in indivisible units.
+pub const ONE_ORE: u64 = 10u64.pow(11);
+
+/// The maximum bonus paid to miners who stake ORE, in basis points of the mining reward.
+pub const ORE_BOOST_MAX_BPS: u64 = 1_000;
+
+/// The ORE stake at which the ORE boost reaches its maximum.
+pub const ORE_BOOST_MAX_STAKE: u64 = ONE_ORE * 100;
+
+/// One INGOT token, denominated in indivisible units.
+pub const ONE_INGOT: u64 = 10u64.pow(TOKEN_DECIMALS as u32);
+
 /// The duration of one minute, in seconds.
 pub const ONE_MINUTE: i64 = 60;
 
@@ -47,27 +101,60 @@ pub const ONE_MINUTE: i64 = 60;
 pub const COAL_EPOCH_MINUTES: i64 = 2;
 pub const WOOD_EPOCH_MINUTES: i64 = 5;
 
-/// The duration of a program epoch, in seconds.
+/// The duration of a program epoch to initialize the program with, in seconds. Epoch rewards are
+/// defined for an epoch of this duration and scale with the configured duration.
 pub const COAL_EPOCH_DURATION: i64 = ONE_MINUTE * COAL_EPOCH_MINUTES;
 pub const WOOD_EPOCH_DURATION: i64 = ONE_MINUTE * WOOD_EPOCH_MINUTES;
+
+/// The shortest allowed duration of a program epoch, in seconds.
+pub const MIN_EPOCH_DURATION: i64 = ONE_MINUTE;
+
+/// The longest allowed duration of a program epoch, in seconds.
+pub const MAX_EPOCH_DURATION: i64 = ONE_MINUTE * 30;
+
+/// The number of consecutive late resets after which the epoch duration is lengthened by a minute.
+/// A reset is late if it happens more than half an epoch after the epoch ended.
+pub const LATE_RESETS_BEFORE_ADAPT: u64 = 10;
+
+/// The duration of a smelting epoch, in seconds.
+pub const SMELT_EPOCH_DURATION: i64 = ONE_MINUTE * 60;
+
+/// The quantity of COAL burned per INGOT smelted to initialize the program with.
+pub const INITIAL_SMELT_COAL_COST: u64 = ONE_COAL * 100;
+
+/// The quantity of WOOD burned per INGOT smelted to initialize the program with.
+pub const INITIAL_SMELT_WOOD_COST: u64 = ONE_WOOD * 10;
+
+/// The maximum quantity of INGOT that can be smelted per smelting epoch to initialize the program with.
+pub const INITIAL_SMELT_EPOCH_OUTPUT_CAP: u64 = ONE_INGOT * 1000;
+
 /// The maximum token supply (21 million).
 pub const MAX_COAL_SUPPLY: u64 = ONE_COAL * 21_000_000;
 
+/// COAL emissions are halved for every 5% of the max supply in circulation.
+pub const COAL_HALVING_INTERVAL: u64 = MAX_COAL_SUPPLY / 20;
+
 /// The multiplier for the target quantity of COAL to be mined per epoch.
 pub const COAL_EXTRACTION_MULTIPLIER: u64 = 1000;
 pub const WOOD_EXTRACTION_MULTIPLIER: u64 = 10;
 
-/// The target quantity of COAL to be mined per epoch.
+/// The target quantity of COAL to be mined per epoch of `COAL_EPOCH_DURATION`.
 pub const TARGET_COAL_EPOCH_REWARDS: u64 = ONE_COAL * COAL_EXTRACTION_MULTIPLIER * COAL_EPOCH_MINUTES as u64;
 
 /// The initial quantity of WOOD distributed to each bus (1000 WOOD).
 pub const INITIAL_WOOD_EPOCH_REWARDS: u64 = ONE_WOOD * 1000;
 
-/// The minimum rewards a bus can have for each epoch 0.1 WOOD
+/// The minimum rewards a bus can have for each epoch of `WOOD_EPOCH_DURATION` (0.1 WOOD).
 pub const MIN_WOOD_EPOCH_REWARDS: u64 = ONE_WOOD / 10;
-/// The maximum rewards a bus can have for each epoch 4000 WOOD
+/// The maximum rewards a bus can have for each epoch of `WOOD_EPOCH_DURATION` (4000 WOOD).
 pub const MAX_WOOD_EPOCH_REWARDS: u64 = ONE_WOOD * 4000;
 
+/// The maximum WOOD token supply (100 million).
+pub const MAX_WOOD_SUPPLY: u64 = ONE_WOOD * 100_000_000;
+
+/// The WOOD bus reward limits are halved for every 10% of the max supply in circulation.
+pub const WOOD_DECAY_INTERVAL: u64 = MAX_WOOD_SUPPLY / 10;
+
 /// WOOD propogation rate is 5% per epoch
 /// New bus rewards = remaining + (remaining rewards / WOOD_PROPOGATION_RATE)
 pub const WOOD_PROPOGATION_RATE: u64 = 20;
@@ -79,9 +166,13 @@ pub const MAX_COAL_EPOCH_REWARDS: u64 = TARGET_COAL_EPOCH_REWARDS * BUS_COUNT as
 /// The quantity of COAL each bus is allowed to issue per epoch.
 pub const BUS_COAL_EPOCH_REWARDS: u64 = MAX_COAL_EPOCH_REWARDS / BUS_COUNT as u64;
 
-/// The number of bus accounts, for parallelizing mine operations.
+/// The initial number of bus accounts, for parallelizing mine operations.
 pub const BUS_COUNT: usize = 8;
 
+/// The maximum number of bus accounts per resource. Reset takes every bus, and miners submit it in
+/// the same legacy transaction as a mine, so this is bounded by the 1232 byte transaction limit.
+pub const MAX_BUS_COUNT: u64 = 12;
+
 /// The smoothing factor for reward rate changes. The reward rate cannot change by mCOAL or less
 /// than a factor of this constant from one epoch to the next.
 pub const SMOOTHING_FACTOR: u64 = 2;
@@ -93,6 +184,11 @@ static_assertions::const_assert!(
     (MAX_COAL_EPOCH_REWARDS / BUS_COUNT as u64) * BUS_COUNT as u64 == MAX_COAL_EPOCH_REWARDS
 );
 
+// Assert MAX_COAL_SUPPLY is evenly divisible into halving intervals.
+static_assertions::const_assert!(
+    (MAX_COAL_SUPPLY / COAL_HALVING_INTERVAL) * COAL_HALVING_INTERVAL == MAX_COAL_SUPPLY
+);
+
 /// The seed of the bus account PDA.
 pub const COAL_BUS: &[u8] = b"bus";
 pub const WOOD_BUS: &[u8] = b"wood_bus";
@@ -100,6 +196,7 @@ pub const WOOD_BUS: &[u8] = b"wood_bus";
 /// The seed of the config account PDA.
 pub const COAL_CONFIG: &[u8] = b"config";
 pub const WOOD_CONFIG: &[u8] = b"wood_config";
+pub const INGOT_CONFIG: &[u8] = b"ingot_config";
 
 /// The seed of the metadata account PDA.
 pub const METADATA: &[u8] = b"metadata";
@@ -107,11 +204,31 @@ pub const METADATA: &[u8] = b"metadata";
 /// The seed of the mint account PDA.
 pub const COAL_MINT: &[u8] = b"mint";
 pub const WOOD_MINT: &[u8] = b"wood_mint";
+pub const INGOT_MINT: &[u8] = b"ingot_mint";
 
 /// The seed of proof account PDAs.
 pub const COAL_PROOF: &[u8] = b"proof";
 pub const WOOD_PROOF: &[u8] = b"wood_proof";
 
+/// The seed of the miner keys account PDA.
+pub const MINER_KEYS: &[u8] = b"miner_keys";
+
+/// The seed of the vesting account PDA.
+pub const VESTING: &[u8] = b"vesting";
+
+/// The longest duration a proof can vest its claimed rewards over (1 year).
+pub const MAX_VESTING_DURATION: i64 = ONE_MINUTE * 60 * 24 * 365;
+
+/// The seed of the leaderboard account PDA.
+pub const COAL_LEADERBOARD: &[u8] = b"leaderboard";
+pub const WOOD_LEADERBOARD: &[u8] = b"wood_leaderboard";
+
+/// The number of proofs ranked on each leaderboard.
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// The maximum number of additional miner keys a proof can authorize.
+pub const MAX_MINER_KEYS: usize = 8;
+
 /// The seed of the tool account PDA.
 pub const COAL_MAIN_HAND_TOOL: &[u8] = b"coal_main_hand_tool";
 
@@ -120,6 +237,9 @@ pub const TREASURY: &[u8] = b"treasury";
 
 pub const PLUGIN_UPDATE_AUTHORITY: &[u8] = b"update_authority";
 
+/// The seed of the forge collection PDA.
+pub const FORGE_COLLECTION: &[u8] = b"forge_collection";
+
 /// Noise for deriving the mint pda
 pub const MINT_NOISE: [u8; 16] = [
     89, 157, 88, 232, 243, 249, 197, 132, 199, 49, 19, 234, 91, 94, 150, 41,
@@ -128,14 +248,23 @@ pub const MINT_NOISE: [u8; 16] = [
 /// The name for token metadata.
 pub const COAL_METADATA_NAME: &str = "coal";
 pub const WOOD_METADATA_NAME: &str = "wood";
+pub const INGOT_METADATA_NAME: &str = "ingot";
 
 /// The ticker symbol for token metadata.
 pub const COAL_METADATA_SYMBOL: &str = "COAL";
 pub const WOOD_METADATA_SYMBOL: &str = "WOOD";
+pub const INGOT_METADATA_SYMBOL: &str = "INGOT";
 
 /// The uri for token metdata.
 pub const COAL_METADATA_URI: &str = "https://coal.digital/metadata.json";
 pub const WOOD_METADATA_URI: &str = "https://coal.digital/metadata.wood.json";
+pub const INGOT_METADATA_URI: &str = "https://coal.digital/metadata.ingot.json";
+
+/// The name and uri for the forge collection and forged tools.
+pub const FORGE_COLLECTION_NAME: &str = "Coal Forge";
+pub const FORGE_COLLECTION_URI: &str = "https://coal.digital/metadata.forge.json";
+pub const FORGE_TOOL_NAME: &str = "Forged Pickaxe";
+pub const FORGE_TOOL_URI: &str = "https://coal.digital/metadata.pickaxe.json";
 
 /// Program id for const pda derivations
 const PROGRAM_ID: [u8; 32] = unsafe { *(&crate::id() as *const Pubkey as *const [u8; 32]) };
@@ -147,9 +276,11 @@ pub const ORE_PROGRAM_ID_BYTES: [u8; 32] = unsafe { *(&ORE_PROGRAM_ID as *const
 /// Forge collection ids
 pub const FORGE_PICKAXE_COLLECTION: Pubkey = pubkey!("CuaLHUJA1dyQ6AYcTcMZrCoBqssSJbqkY7VfEEFdxzCk");
 
-/// The addresses of the bus accounts.
-pub const COAL_BUS_ADDRESSES: [Pubkey; BUS_COUNT] = array_const_fn_init![const_coal_bus_address; 8];
-pub const WOOD_BUS_ADDRESSES: [Pubkey; BUS_COUNT] = array_const_fn_init![const_wood_bus_address; 8];
+/// The addresses of the bus accounts, up to the maximum bus count.
+pub const COAL_BUS_ADDRESSES: [Pubkey; MAX_BUS_COUNT as usize] =
+    array_const_fn_init![const_coal_bus_address; 12];
+pub const WOOD_BUS_ADDRESSES: [Pubkey; MAX_BUS_COUNT as usize] =
+    array_const_fn_init![const_wood_bus_address; 12];
 
 /// Function to derive const bus addresses.
 const fn const_coal_bus_address(i: usize) -> Pubkey {
@@ -160,11 +291,35 @@ const fn const_wood_bus_address(i: usize) -> Pubkey {
     Pubkey::new_from_array(ed25519::derive_program_address(&[WOOD_BUS, &[i as u8]], &PROGRAM_ID).0)
 }
 
+/// Returns the address of the coal bus with the given id.
+pub fn coal_bus_address(id: u64) -> Pubkey {
+    match COAL_BUS_ADDRESSES.get(id as usize) {
+        Some(address) => *address,
+        None => Pubkey::find_program_address(&[COAL_BUS, &[id as u8]], &crate::id()).0,
+    }
+}
+
+/// Returns the address of the wood bus with the given id.
+pub fn wood_bus_address(id: u64) -> Pubkey {
+    match WOOD_BUS_ADDRESSES.get(id as usize) {
+        Some(address) => *address,
+        None => Pubkey::find_program_address(&[WOOD_BUS, &[id as u8]], &crate::id()).0,
+    }
+}
+
 /// The address of the config account.
 pub const COAL_CONFIG_ADDRESS: Pubkey =
     Pubkey::new_from_array(ed25519::derive_program_address(&[COAL_CONFIG], &PROGRAM_ID).0);
 pub const WOOD_CONFIG_ADDRESS: Pubkey =
     Pubkey::new_from_array(ed25519::derive_program_address(&[WOOD_CONFIG], &PROGRAM_ID).0);
+pub const INGOT_CONFIG_ADDRESS: Pubkey =
+    Pubkey::new_from_array(ed25519::derive_program_address(&[INGOT_CONFIG], &PROGRAM_ID).0);
+
+/// The address of the leaderboard account.
+pub const COAL_LEADERBOARD_ADDRESS: Pubkey =
+    Pubkey::new_from_array(ed25519::derive_program_address(&[COAL_LEADERBOARD], &PROGRAM_ID).0);
+pub const WOOD_LEADERBOARD_ADDRESS: Pubkey =
+    Pubkey::new_from_array(ed25519::derive_program_address(&[WOOD_LEADERBOARD], &PROGRAM_ID).0);
 
 /// The address of the mint metadata account.
 pub const COAL_METADATA_ADDRESS: Pubkey = Pubkey::new_from_array(
@@ -189,6 +344,17 @@ pub const WOOD_METADATA_ADDRESS: Pubkey = Pubkey::new_from_array(
     )
     .0,
 );
+pub const INGOT_METADATA_ADDRESS: Pubkey = Pubkey::new_from_array(
+    ed25519::derive_program_address(
+        &[
+            METADATA,
+            unsafe { &*(&mpl_token_metadata::ID as *const Pubkey as *const [u8; 32]) },
+            unsafe { &*(&INGOT_MINT_ADDRESS as *const Pubkey as *const [u8; 32]) },
+        ],
+        unsafe { &*(&mpl_token_metadata::ID as *const Pubkey as *const [u8; 32]) },
+    )
+    .0,
+);
 
 /// The address of the mint account.
 pub const COAL_MINT_ADDRESS: Pubkey =
@@ -198,6 +364,10 @@ pub const COAL_MINT_ADDRESS: Pubkey =
 pub const WOOD_MINT_ADDRESS: Pubkey =
     Pubkey::new_from_array(ed25519::derive_program_address(&[WOOD_MINT, &MINT_NOISE], &PROGRAM_ID).0);
 
+/// The address of the mint account.
+pub const INGOT_MINT_ADDRESS: Pubkey =
+    Pubkey::new_from_array(ed25519::derive_program_address(&[INGOT_MINT, &MINT_NOISE], &PROGRAM_ID).0);
+
 /// The address of the treasury account.
 pub const TREASURY_ADDRESS: Pubkey =
     Pubkey::new_from_array(ed25519::derive_program_address(&[TREASURY], &PROGRAM_ID).0);
@@ -231,6 +401,9 @@ pub const WOOD_TREASURY_TOKENS_ADDRESS: Pubkey = Pubkey::new_from_array(
     .0,
 );
 
+pub const FORGE_COLLECTION_ADDRESS: Pubkey = Pubkey::new_from_array(ed25519::derive_program_address(&[FORGE_COLLECTION], &PROGRAM_ID).0);
+pub const PLUGIN_UPDATE_AUTHORITY_ADDRESS: Pubkey = Pubkey::new_from_array(ed25519::derive_program_address(&[PLUGIN_UPDATE_AUTHORITY], &PROGRAM_ID).0);
+pub const PLUGIN_UPDATE_AUTHORITY_BUMP: u8 = ed25519::derive_program_address(&[PLUGIN_UPDATE_AUTHORITY], &PROGRAM_ID).1;
 pub const COAL_MAIN_HAND_TOOL_ADDRESS: Pubkey = Pubkey::new_from_array(ed25519::derive_program_address(&[COAL_MAIN_HAND_TOOL], &PROGRAM_ID).0);
 
 
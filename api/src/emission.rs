@@ -0,0 +1,196 @@
+//! Integer COAL emission schedule.
+//!
+//! For each 5% of the max supply in circulation, epoch rewards are halved. Epoch rewards are defined
+//! for an epoch of `COAL_EPOCH_DURATION` and scale linearly with the configured epoch duration. All
+//! functions are pure integer math, so clients can reproduce exactly what the program will emit at any
+//! supply.
+
+use crate::consts::*;
+
+/// Returns the number of halvings that apply at the given supply.
+pub fn halving_epoch(supply: u64) -> u32 {
+    (supply / COAL_HALVING_INTERVAL) as u32
+}
+
+/// Returns the supply at which the next halving takes effect.
+pub fn next_halving_supply(supply: u64) -> u64 {
+    (halving_epoch(supply) as u64)
+        .saturating_add(1)
+        .saturating_mul(COAL_HALVING_INTERVAL)
+}
+
+/// Returns the target quantity of COAL to be mined per epoch at the given supply and epoch duration.
+pub fn target_epoch_rewards(supply: u64, epoch_duration: i64) -> u64 {
+    halve(
+        scale_to_epoch(TARGET_COAL_EPOCH_REWARDS, epoch_duration, COAL_EPOCH_DURATION),
+        supply,
+    )
+}
+
+/// Returns the quantity of COAL each bus is allowed to issue per epoch at the given supply and epoch
+/// duration, when the max epoch rewards are split across the given number of busses.
+pub fn bus_epoch_rewards(supply: u64, bus_count: u64, epoch_duration: i64) -> u64 {
+    max_epoch_rewards(supply, epoch_duration)
+        .checked_div(bus_count)
+        .unwrap_or(0)
+}
+
+/// Returns the maximum quantity of COAL that can be mined per epoch at the given supply and epoch
+/// duration.
+pub fn max_epoch_rewards(supply: u64, epoch_duration: i64) -> u64 {
+    halve(
+        scale_to_epoch(MAX_COAL_EPOCH_REWARDS, epoch_duration, COAL_EPOCH_DURATION),
+        supply,
+    )
+}
+
+/// Scales a quantity defined per epoch of the base duration to an epoch of the given duration.
+pub fn scale_to_epoch(amount: u64, epoch_duration: i64, base_epoch_duration: i64) -> u64 {
+    (amount as u128)
+        .saturating_mul(epoch_duration.max(0) as u128)
+        .checked_div(base_epoch_duration.max(0) as u128)
+        .unwrap_or(0)
+        .min(u64::MAX as u128) as u64
+}
+
+/// Returns the min and max base reward rate thresholds at the given supply.
+pub fn base_reward_rate_thresholds(supply: u64) -> (u64, u64) {
+    (
+        halve(BASE_COAL_REWARD_RATE_MIN_THRESHOLD, supply),
+        halve(BASE_COAL_REWARD_RATE_MAX_THRESHOLD, supply),
+    )
+}
+
+/// Halves the amount once for every halving epoch, bottoming out at zero.
+fn halve(amount: u64, supply: u64) -> u64 {
+    amount.checked_shr(halving_epoch(supply)).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HALVINGS: u32 = 20;
+
+    #[test]
+    fn test_halving_epoch_boundaries() {
+        for i in 0..HALVINGS as u64 {
+            let boundary = COAL_HALVING_INTERVAL * i;
+            assert_eq!(halving_epoch(boundary), i as u32);
+            assert_eq!(halving_epoch(boundary + COAL_HALVING_INTERVAL - 1), i as u32);
+            if i > 0 {
+                assert_eq!(halving_epoch(boundary - 1), i as u32 - 1);
+            }
+        }
+        assert_eq!(halving_epoch(MAX_COAL_SUPPLY), HALVINGS);
+    }
+
+    #[test]
+    fn test_halving_epoch_matches_supply_percentage() {
+        for pct in 0..100u64 {
+            let supply = MAX_COAL_SUPPLY / 100 * pct;
+            assert_eq!(halving_epoch(supply), (pct / 5) as u32);
+        }
+    }
+
+    #[test]
+    fn test_next_halving_supply() {
+        assert_eq!(next_halving_supply(0), COAL_HALVING_INTERVAL);
+        for i in 0..HALVINGS as u64 {
+            let boundary = COAL_HALVING_INTERVAL * i;
+            let next = COAL_HALVING_INTERVAL * (i + 1);
+            assert_eq!(next_halving_supply(boundary), next);
+            assert_eq!(next_halving_supply(next - 1), next);
+        }
+        assert_eq!(next_halving_supply(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_epoch_rewards_boundaries() {
+        for i in 0..HALVINGS {
+            let boundary = COAL_HALVING_INTERVAL * i as u64;
+            let factor = 2u64.pow(i);
+            for supply in [boundary, boundary + COAL_HALVING_INTERVAL - 1] {
+                assert_eq!(target_epoch_rewards(supply, COAL_EPOCH_DURATION), TARGET_COAL_EPOCH_REWARDS / factor);
+                assert_eq!(bus_epoch_rewards(supply, BUS_COUNT as u64, COAL_EPOCH_DURATION), BUS_COAL_EPOCH_REWARDS / factor);
+                assert_eq!(max_epoch_rewards(supply, COAL_EPOCH_DURATION), MAX_COAL_EPOCH_REWARDS / factor);
+                assert_eq!(
+                    base_reward_rate_thresholds(supply),
+                    (
+                        BASE_COAL_REWARD_RATE_MIN_THRESHOLD / factor,
+                        BASE_COAL_REWARD_RATE_MAX_THRESHOLD / factor
+                    )
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_epoch_rewards_monotonic() {
+        for i in 1..=HALVINGS {
+            let boundary = COAL_HALVING_INTERVAL * i as u64;
+            assert!(
+                bus_epoch_rewards(boundary, BUS_COUNT as u64, COAL_EPOCH_DURATION)
+                    < bus_epoch_rewards(boundary - 1, BUS_COUNT as u64, COAL_EPOCH_DURATION)
+            );
+            assert!(
+                target_epoch_rewards(boundary, COAL_EPOCH_DURATION)
+                    <= target_epoch_rewards(boundary - 1, COAL_EPOCH_DURATION)
+            );
+        }
+    }
+
+    #[test]
+    fn test_bus_epoch_rewards_split() {
+        for bus_count in 1..=MAX_BUS_COUNT {
+            let rewards = bus_epoch_rewards(0, bus_count, COAL_EPOCH_DURATION);
+            assert_eq!(rewards, MAX_COAL_EPOCH_REWARDS / bus_count);
+            assert!(rewards.saturating_mul(bus_count) <= MAX_COAL_EPOCH_REWARDS);
+        }
+        assert_eq!(bus_epoch_rewards(0, 0, COAL_EPOCH_DURATION), 0);
+    }
+
+    #[test]
+    fn test_epoch_rewards_overflow() {
+        assert_eq!(bus_epoch_rewards(u64::MAX, BUS_COUNT as u64, COAL_EPOCH_DURATION), 0);
+        assert_eq!(target_epoch_rewards(u64::MAX, COAL_EPOCH_DURATION), 0);
+        assert_eq!(max_epoch_rewards(u64::MAX, COAL_EPOCH_DURATION), 0);
+        assert_eq!(max_epoch_rewards(0, i64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_epoch_rewards_scale_with_duration() {
+        for minutes in 1..=60 {
+            let epoch_duration = ONE_MINUTE * minutes;
+            let target_per_minute = TARGET_COAL_EPOCH_REWARDS / COAL_EPOCH_MINUTES as u64;
+            let max_per_minute = MAX_COAL_EPOCH_REWARDS / COAL_EPOCH_MINUTES as u64;
+            assert_eq!(target_epoch_rewards(0, epoch_duration), target_per_minute * minutes as u64);
+            assert_eq!(max_epoch_rewards(0, epoch_duration), max_per_minute * minutes as u64);
+            assert_eq!(
+                bus_epoch_rewards(0, BUS_COUNT as u64, epoch_duration),
+                max_per_minute * minutes as u64 / BUS_COUNT as u64
+            );
+        }
+    }
+
+    #[test]
+    fn test_epoch_rewards_scale_with_halvings() {
+        let epoch_duration = COAL_EPOCH_DURATION * 3;
+        for i in 0..HALVINGS {
+            let supply = COAL_HALVING_INTERVAL * i as u64;
+            assert_eq!(
+                target_epoch_rewards(supply, epoch_duration),
+                (TARGET_COAL_EPOCH_REWARDS * 3) >> i
+            );
+        }
+    }
+
+    #[test]
+    fn test_scale_to_epoch() {
+        assert_eq!(scale_to_epoch(1_000, 600, 300), 2_000);
+        assert_eq!(scale_to_epoch(1_000, 100, 300), 333);
+        assert_eq!(scale_to_epoch(1_000, 300, 0), 0);
+        assert_eq!(scale_to_epoch(1_000, -300, 300), 0);
+        assert_eq!(scale_to_epoch(u64::MAX, i64::MAX, 1), u64::MAX);
+    }
+}
@@ -21,6 +21,32 @@ pub enum CoalError {
     MaxSupply = 6,
     #[error("The proof does not match the expected account")]
     AuthFailed = 7,
+    #[error("The miner key has expired")]
+    MinerKeyExpired = 8,
+    #[error("The miner key has reached its hash quota for this epoch")]
+    HashQuotaExceeded = 9,
+    #[error("The maximum number of miner keys has been reached")]
+    TooManyMinerKeys = 10,
+    #[error("The smelting output cap has been reached for this epoch")]
+    SmeltCapExceeded = 11,
+    #[error("The quantity burned does not meet the lowest forging tier")]
+    ForgeTierNotMet = 12,
+    #[error("The proof vests its claims and cannot be closed and claimed at once")]
+    VestingEnabled = 13,
+    #[error("The transaction does not verify the miner's signature over the relayed solution")]
+    RelaySignatureInvalid = 14,
+    #[error("The proof holds a balance or has hashed too recently to be reaped")]
+    ProofNotReapable = 15,
+    #[error("The busses have already been rebalanced this epoch")]
+    RebalanceTooSoon = 16,
+    #[error("The deposit is below the minimum accepted on behalf of another miner")]
+    StakeTooSmall = 17,
+    #[error("The proof has vesting tokens which must be released before its authority can change")]
+    VestingNotReleased = 18,
+    #[error("No bus is depleted enough to rebalance")]
+    RebalanceNotNeeded = 19,
+    #[error("The proof has a tool equipped which must be unequipped before it can be reaped")]
+    ToolEquipped = 20,
 }
 
 impl From<CoalError> for ProgramError {
@@ -1,4 +1,5 @@
 use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
 
 use crate::utils::impl_to_bytes;
 
@@ -8,6 +9,35 @@ pub struct MineEvent {
     pub difficulty: u64,
     pub reward: u64,
     pub timing: i64,
+    pub jackpot: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct SolvencyEvent {
+    pub outstanding_liabilities: u64,
+    pub treasury_balance: u64,
+    pub surplus: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct StakeEvent {
+    pub staker: Pubkey,
+    pub proof: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ReapEvent {
+    pub proof: Pubkey,
+    pub authority_lamports: u64,
+    pub reaper_lamports: u64,
 }
 
 impl_to_bytes!(MineEvent);
+impl_to_bytes!(SolvencyEvent);
+impl_to_bytes!(StakeEvent);
+impl_to_bytes!(ReapEvent);
@@ -0,0 +1,88 @@
+//! Tool forging tiers.
+//!
+//! Forged tools are graded by the quantity of COAL and WOOD burned to create them. The highest
+//! tier the burn qualifies for sets the tool's multiplier, and its durability scales with the
+//! COAL burned.
+
+use crate::consts::*;
+
+/// A tier of forged tool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ForgeTier {
+    /// The minimum quantity of COAL that must be burned to forge this tier.
+    pub coal_cost: u64,
+
+    /// The minimum quantity of WOOD that must be burned to forge this tier.
+    pub wood_cost: u64,
+
+    /// The reward multiplier of the tool, in percent.
+    pub multiplier: u64,
+
+    /// The durability granted per COAL burned, in basis points.
+    pub durability_bps: u64,
+}
+
+/// The forging tiers, in ascending order.
+pub const FORGE_TIERS: [ForgeTier; 3] = [
+    ForgeTier {
+        coal_cost: ONE_COAL * 100,
+        wood_cost: ONE_WOOD * 10,
+        multiplier: 10,
+        durability_bps: 10_000,
+    },
+    ForgeTier {
+        coal_cost: ONE_COAL * 1_000,
+        wood_cost: ONE_WOOD * 100,
+        multiplier: 25,
+        durability_bps: 12_500,
+    },
+    ForgeTier {
+        coal_cost: ONE_COAL * 10_000,
+        wood_cost: ONE_WOOD * 1_000,
+        multiplier: 50,
+        durability_bps: 15_000,
+    },
+];
+
+/// Returns the highest tier the given burn qualifies for.
+pub fn forge_tier(coal_amount: u64, wood_amount: u64) -> Option<ForgeTier> {
+    FORGE_TIERS
+        .iter()
+        .rev()
+        .find(|tier| coal_amount.ge(&tier.coal_cost) && wood_amount.ge(&tier.wood_cost))
+        .copied()
+}
+
+/// Returns the durability and multiplier of a tool forged from the given burn.
+pub fn forge_attributes(coal_amount: u64, wood_amount: u64) -> Option<(u64, u64)> {
+    let tier = forge_tier(coal_amount, wood_amount)?;
+    let durability = (coal_amount as u128)
+        .saturating_mul(tier.durability_bps as u128)
+        .saturating_div(BASIS_POINTS as u128)
+        .min(u64::MAX as u128) as u64;
+    Some((durability, tier.multiplier))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forge_tier_below_minimum() {
+        assert_eq!(forge_tier(ONE_COAL * 99, ONE_WOOD * 10), None);
+        assert_eq!(forge_tier(ONE_COAL * 100, ONE_WOOD * 9), None);
+    }
+
+    #[test]
+    fn test_forge_tier_requires_both_resources() {
+        let tier = forge_tier(ONE_COAL * 10_000, ONE_WOOD * 100).unwrap();
+        assert_eq!(tier, FORGE_TIERS[1]);
+    }
+
+    #[test]
+    fn test_forge_attributes_scale_with_burn() {
+        let (durability, multiplier) = forge_attributes(ONE_COAL * 2_000, ONE_WOOD * 100).unwrap();
+        assert_eq!(multiplier, 25);
+        assert_eq!(durability, ONE_COAL * 2_500);
+    }
+}
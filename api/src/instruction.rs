@@ -27,9 +27,33 @@ pub enum CoalInstruction {
     OpenWood = 7,
     Equip = 8,
     Unequip = 9,
+    UpgradeAccount = 10,
+    ProposeProofAuthority = 11,
+    AcceptProofAuthority = 12,
+    AddMiner = 13,
+    RemoveMiner = 14,
+    CloseAndClaim = 15,
+    Smelt = 16,
+    ForgeTool = 17,
+    AuditTreasury = 18,
+    StakeFor = 19,
+    ConfigureVesting = 20,
+    ReleaseVested = 21,
+    MineRelayed = 22,
+    ReapProof = 23,
+    Rebalance = 24,
     // Admin
     InitCoal = 100,
     InitWood = 101,
+    ConfigureReferrals = 102,
+    InitIngot = 103,
+    ConfigureSmelting = 104,
+    InitForge = 105,
+    AddBusses = 106,
+    ConfigureReaping = 107,
+    InitLeaderboard = 108,
+    ConfigureJackpot = 109,
+    ConfigureEpoch = 110,
 }
 
 impl CoalInstruction {
@@ -55,6 +79,15 @@ pub struct InitializeArgs {
     pub treasury_bump: u8,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct InitIngotArgs {
+    pub config_bump: u8,
+    pub metadata_bump: u8,
+    pub mint_bump: u8,
+    pub treasury_bump: u8,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct OpenArgs {
@@ -74,6 +107,29 @@ pub struct UnequipArgs {
     pub plugin_authority_bump: u8,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct AcceptProofAuthorityArgs {
+    pub proof_bump: u8,
+    pub tool_bump: u8,
+    pub old_tool_bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct AddMinerArgs {
+    pub expires_at: [u8; 8],
+    pub hash_quota: [u8; 8],
+    pub bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct CloseAndClaimArgs {
+    pub tool_bump: u8,
+    pub plugin_authority_bump: u8,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct MineArgs {
@@ -88,18 +144,89 @@ pub struct MineArgsV2 {
     pub nonce: [u8; 8],
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct MineRelayedArgs {
+    pub digest: [u8; 16],
+    pub nonce: [u8; 8],
+    pub fee_bps: [u8; 8],
+    pub miner: Pubkey,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct ClaimArgs {
     pub amount: [u8; 8],
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ConfigureVestingArgs {
+    pub duration: [u8; 8],
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct StakeArgs {
     pub amount: [u8; 8],
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ConfigureReferralsArgs {
+    pub bonus_bps: [u8; 8],
+    pub epochs: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ConfigureEpochArgs {
+    pub epoch_duration: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ConfigureJackpotArgs {
+    pub jackpot_bps: [u8; 8],
+    pub jackpot_difficulty: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ConfigureReapingArgs {
+    pub reap_after: [u8; 8],
+    pub reaper_bonus_bps: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ConfigureSmeltingArgs {
+    pub coal_cost: [u8; 8],
+    pub wood_cost: [u8; 8],
+    pub epoch_output_cap: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SmeltArgs {
+    pub amount: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct InitForgeArgs {
+    pub collection_bump: u8,
+    pub plugin_authority_bump: u8,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct ForgeToolArgs {
+    pub coal_amount: [u8; 8],
+    pub wood_amount: [u8; 8],
+    pub plugin_authority_bump: u8,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct UpgradeArgs {
@@ -109,46 +236,105 @@ pub struct UpgradeArgs {
 impl_to_bytes!(InitializeArgs);
 impl_to_bytes!(OpenArgs);
 impl_to_bytes!(MineArgs);
+impl_to_bytes!(MineRelayedArgs);
 impl_to_bytes!(ClaimArgs);
 impl_to_bytes!(StakeArgs);
 impl_to_bytes!(UpgradeArgs);
 impl_to_bytes!(EquipArgs);
 impl_to_bytes!(UnequipArgs);
+impl_to_bytes!(ConfigureReferralsArgs);
+impl_to_bytes!(ConfigureReapingArgs);
+impl_to_bytes!(ConfigureJackpotArgs);
+impl_to_bytes!(ConfigureEpochArgs);
+impl_to_bytes!(AcceptProofAuthorityArgs);
+impl_to_bytes!(AddMinerArgs);
+impl_to_bytes!(CloseAndClaimArgs);
+impl_to_bytes!(InitIngotArgs);
+impl_to_bytes!(ConfigureSmeltingArgs);
+impl_to_bytes!(SmeltArgs);
+impl_to_bytes!(InitForgeArgs);
+impl_to_bytes!(ForgeToolArgs);
+impl_to_bytes!(ConfigureVestingArgs);
 
 impl_instruction_from_bytes!(InitializeArgs);
 impl_instruction_from_bytes!(OpenArgs);
 impl_instruction_from_bytes!(MineArgs);
+impl_instruction_from_bytes!(MineRelayedArgs);
 impl_instruction_from_bytes!(ClaimArgs);
 impl_instruction_from_bytes!(StakeArgs);
 impl_instruction_from_bytes!(UpgradeArgs);
 impl_instruction_from_bytes!(EquipArgs);
 impl_instruction_from_bytes!(UnequipArgs);
+impl_instruction_from_bytes!(ConfigureReferralsArgs);
+impl_instruction_from_bytes!(ConfigureReapingArgs);
+impl_instruction_from_bytes!(ConfigureJackpotArgs);
+impl_instruction_from_bytes!(ConfigureEpochArgs);
+impl_instruction_from_bytes!(AcceptProofAuthorityArgs);
+impl_instruction_from_bytes!(AddMinerArgs);
+impl_instruction_from_bytes!(CloseAndClaimArgs);
+impl_instruction_from_bytes!(InitIngotArgs);
+impl_instruction_from_bytes!(ConfigureSmeltingArgs);
+impl_instruction_from_bytes!(SmeltArgs);
+impl_instruction_from_bytes!(InitForgeArgs);
+impl_instruction_from_bytes!(ForgeToolArgs);
+impl_instruction_from_bytes!(ConfigureVestingArgs);
 
-/// Builds an auth instruction.
-pub fn auth(proof: Pubkey) -> Instruction {
-    Instruction {
-        program_id: NOOP_PROGRAM_ID,
-        accounts: vec![],
-        data: proof.to_bytes().to_vec(),
-    }
+/// The bumps stored in accounts the caller has already loaded.
+///
+/// Builders create the addresses of these accounts from the stored bumps with
+/// `create_program_address`, rather than searching for the bump with `find_program_address`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Bumps {
+    /// The bump stored in the proof.
+    pub proof: Option<u64>,
+
+    /// The bump stored in the relayer's proof.
+    pub relayer_proof: Option<u64>,
+
+    /// The bump stored in the tool.
+    pub tool: Option<u64>,
+
+    /// The bump stored in the vesting account.
+    pub vesting: Option<u64>,
+}
+
+/// Returns the PDA of the seeds and its bump, created from the stored bump if it is known.
+///
+/// Accounts from before the bump was stored read it as zero, so a zero bump is searched for.
+fn pda(seeds: &[&[u8]], bump: Option<u64>) -> (Pubkey, u8) {
+    bump.filter(|bump| bump.gt(&0))
+        .and_then(|bump| u8::try_from(bump).ok())
+        .and_then(|bump| {
+            Pubkey::create_program_address(&[seeds, &[&[bump]]].concat(), &crate::id())
+                .ok()
+                .map(|address| (address, bump))
+        })
+        .unwrap_or_else(|| Pubkey::find_program_address(seeds, &crate::id()))
 }
 
 /// Builds a claim instruction.
-pub fn claim_coal(signer: Pubkey, beneficiary: Pubkey, amount: u64) -> Instruction {
-    let proof = Pubkey::find_program_address(&[COAL_PROOF, signer.as_ref()], &crate::id()).0;
-    let treasury_tokens = spl_associated_token_account::get_associated_token_address(
-        &TREASURY_ADDRESS,
-        &COAL_MINT_ADDRESS,
-    );
+pub fn claim_coal(
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    amount: u64,
+    token_program: Pubkey,
+    bumps: Bumps,
+) -> Instruction {
+    let proof = pda(&[COAL_PROOF, signer.as_ref()], bumps.proof).0;
+    let vesting = pda(&[VESTING, proof.as_ref()], bumps.vesting).0;
     Instruction {
         program_id: crate::id(),
         accounts: vec![
             AccountMeta::new(signer, true),
             AccountMeta::new(beneficiary, false),
             AccountMeta::new(proof, false),
+            AccountMeta::new(COAL_CONFIG_ADDRESS, false),
             AccountMeta::new_readonly(TREASURY_ADDRESS, false),
-            AccountMeta::new(treasury_tokens, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(COAL_TREASURY_TOKENS_ADDRESS, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(COAL_MINT_ADDRESS, false),
+            AccountMeta::new(vesting, false),
+            AccountMeta::new_readonly(system_program::id(), false),
         ],
         data: [
             CoalInstruction::Claim.to_vec(),
@@ -162,21 +348,28 @@ pub fn claim_coal(signer: Pubkey, beneficiary: Pubkey, amount: u64) -> Instructi
     }
 }
 
-pub fn claim_wood(signer: Pubkey, beneficiary: Pubkey, amount: u64) -> Instruction {
-    let proof = Pubkey::find_program_address(&[WOOD_PROOF, signer.as_ref()], &crate::id()).0;
-    let treasury_tokens = spl_associated_token_account::get_associated_token_address(
-        &TREASURY_ADDRESS,
-        &WOOD_MINT_ADDRESS,
-    );
+pub fn claim_wood(
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    amount: u64,
+    token_program: Pubkey,
+    bumps: Bumps,
+) -> Instruction {
+    let proof = pda(&[WOOD_PROOF, signer.as_ref()], bumps.proof).0;
+    let vesting = pda(&[VESTING, proof.as_ref()], bumps.vesting).0;
     Instruction {
         program_id: crate::id(),
         accounts: vec![
             AccountMeta::new(signer, true),
             AccountMeta::new(beneficiary, false),
             AccountMeta::new(proof, false),
+            AccountMeta::new(WOOD_CONFIG_ADDRESS, false),
             AccountMeta::new_readonly(TREASURY_ADDRESS, false),
-            AccountMeta::new(treasury_tokens, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(WOOD_TREASURY_TOKENS_ADDRESS, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(WOOD_MINT_ADDRESS, false),
+            AccountMeta::new(vesting, false),
+            AccountMeta::new_readonly(system_program::id(), false),
         ],
         data: [
             CoalInstruction::Claim.to_vec(),
@@ -191,53 +384,163 @@ pub fn claim_wood(signer: Pubkey, beneficiary: Pubkey, amount: u64) -> Instructi
 }
 
 /// Builds a close instruction.
-pub fn close_coal(signer: Pubkey) -> Instruction {
-    let proof_pda = Pubkey::find_program_address(&[COAL_PROOF, signer.as_ref()], &crate::id());
+pub fn close_coal(signer: Pubkey, bumps: Bumps) -> Instruction {
+    let proof_pda = pda(&[COAL_PROOF, signer.as_ref()], bumps.proof);
+    let miner_keys = Pubkey::find_program_address(&[MINER_KEYS, proof_pda.0.as_ref()], &crate::id()).0;
     Instruction {
         program_id: crate::id(),
         accounts: vec![
             AccountMeta::new(signer, true),
             AccountMeta::new(proof_pda.0, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(miner_keys, false),
         ],
         data: CoalInstruction::Close.to_vec(),
     }
 }
 
-pub fn close_wood(signer: Pubkey) -> Instruction {
-    let proof_pda = Pubkey::find_program_address(&[WOOD_PROOF, signer.as_ref()], &crate::id());
+pub fn close_wood(signer: Pubkey, bumps: Bumps) -> Instruction {
+    let proof_pda = pda(&[WOOD_PROOF, signer.as_ref()], bumps.proof);
+    let miner_keys = Pubkey::find_program_address(&[MINER_KEYS, proof_pda.0.as_ref()], &crate::id()).0;
     Instruction {
         program_id: crate::id(),
         accounts: vec![
             AccountMeta::new(signer, true),
             AccountMeta::new(proof_pda.0, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new(miner_keys, false),
         ],
         data: CoalInstruction::Close.to_vec(),
     }
 }
 
+/// Builds a close and claim instruction.
+///
+/// The tool account is always passed. If a tool is equipped, its asset and collection must be
+/// provided so the tool is unequipped.
+pub fn close_and_claim_coal(
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    token_program: Pubkey,
+    tool: Option<(Pubkey, Pubkey)>,
+    bumps: Bumps,
+) -> Instruction {
+    let proof = pda(&[COAL_PROOF, signer.as_ref()], bumps.proof).0;
+    let miner_keys = Pubkey::find_program_address(&[MINER_KEYS, proof.as_ref()], &crate::id()).0;
+    let tool_pda = pda(&[COAL_MAIN_HAND_TOOL, signer.as_ref()], bumps.tool);
+    let plugin_authority = (PLUGIN_UPDATE_AUTHORITY_ADDRESS, PLUGIN_UPDATE_AUTHORITY_BUMP);
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(beneficiary, false),
+        AccountMeta::new(proof, false),
+        AccountMeta::new(COAL_CONFIG_ADDRESS, false),
+        AccountMeta::new_readonly(TREASURY_ADDRESS, false),
+        AccountMeta::new(COAL_TREASURY_TOKENS_ADDRESS, false),
+        AccountMeta::new_readonly(token_program, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(COAL_MINT_ADDRESS, false),
+        AccountMeta::new(miner_keys, false),
+        AccountMeta::new(tool_pda.0, false),
+    ];
+    if let Some((asset, collection)) = tool {
+        accounts.extend([
+            AccountMeta::new(asset, false),
+            AccountMeta::new(collection, false),
+            AccountMeta::new(plugin_authority.0, false),
+            AccountMeta::new_readonly(mpl_core::ID, false),
+        ]);
+    }
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: [
+            CoalInstruction::CloseAndClaim.to_vec(),
+            CloseAndClaimArgs {
+                tool_bump: tool_pda.1,
+                plugin_authority_bump: plugin_authority.1,
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+pub fn close_and_claim_wood(
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    token_program: Pubkey,
+    bumps: Bumps,
+) -> Instruction {
+    let proof = pda(&[WOOD_PROOF, signer.as_ref()], bumps.proof).0;
+    let miner_keys = Pubkey::find_program_address(&[MINER_KEYS, proof.as_ref()], &crate::id()).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(beneficiary, false),
+            AccountMeta::new(proof, false),
+            AccountMeta::new(WOOD_CONFIG_ADDRESS, false),
+            AccountMeta::new_readonly(TREASURY_ADDRESS, false),
+            AccountMeta::new(WOOD_TREASURY_TOKENS_ADDRESS, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(WOOD_MINT_ADDRESS, false),
+            AccountMeta::new(miner_keys, false),
+        ],
+        data: [
+            CoalInstruction::CloseAndClaim.to_vec(),
+            CloseAndClaimArgs {
+                tool_bump: 0,
+                plugin_authority_bump: 0,
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
 /// Builds a mine instruction.
+///
+/// If an ORE proof is provided without a referrer, the program id is passed in place of the referrer.
+/// The system program is passed last, so the signer can pay to upgrade accounts on older layouts.
+/// Miners which have loaded their proof should pass its stored bump in `bumps`.
 pub fn mine_coal(
     signer: Pubkey,
     proof_authority: Pubkey,
     bus: Pubkey,
     solution: Solution,
+    referrer: Option<Pubkey>,
+    ore_proof: Option<Pubkey>,
+    bumps: Bumps,
 ) -> Instruction {
-    let proof = Pubkey::find_program_address(&[COAL_PROOF, proof_authority.as_ref()], &crate::id()).0;
-    let tool = Pubkey::find_program_address(&[COAL_MAIN_HAND_TOOL, proof_authority.as_ref()], &crate::id()).0;
-    
+    let proof = pda(&[COAL_PROOF, proof_authority.as_ref()], bumps.proof).0;
+    let tool = pda(&[COAL_MAIN_HAND_TOOL, proof_authority.as_ref()], bumps.tool).0;
+    let miner_keys = Pubkey::find_program_address(&[MINER_KEYS, proof.as_ref()], &crate::id()).0;
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(bus, false),
+        AccountMeta::new_readonly(COAL_CONFIG_ADDRESS, false),
+        AccountMeta::new(proof, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+        AccountMeta::new(tool, false),
+        AccountMeta::new(miner_keys, false),
+    ];
+    accounts.push(match referrer {
+        Some(referrer) => AccountMeta::new(referrer, false),
+        None => AccountMeta::new_readonly(crate::id(), false),
+    });
+    accounts.push(match ore_proof {
+        Some(ore_proof) => AccountMeta::new_readonly(ore_proof, false),
+        None => AccountMeta::new_readonly(crate::id(), false),
+    });
+    accounts.push(AccountMeta::new(COAL_LEADERBOARD_ADDRESS, false));
+    accounts.push(AccountMeta::new_readonly(system_program::id(), false));
     Instruction {
         program_id: crate::id(),
-        accounts: vec![
-            AccountMeta::new(signer, true),
-            AccountMeta::new(bus, false),
-            AccountMeta::new_readonly(COAL_CONFIG_ADDRESS, false),
-            AccountMeta::new(proof, false),
-            AccountMeta::new_readonly(sysvar::instructions::id(), false),
-            AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
-            AccountMeta::new(tool, false),
-        ],
+        accounts,
         data: [
             CoalInstruction::Mine.to_vec(),
             MineArgs {
@@ -256,18 +559,29 @@ pub fn chop_wood(
     proof_authority: Pubkey,
     bus: Pubkey,
     solution: Solution,
+    referrer: Option<Pubkey>,
+    bumps: Bumps,
 ) -> Instruction {
-    let proof = Pubkey::find_program_address(&[WOOD_PROOF, proof_authority.as_ref()], &crate::id()).0;
+    let proof = pda(&[WOOD_PROOF, proof_authority.as_ref()], bumps.proof).0;
+    let miner_keys = Pubkey::find_program_address(&[MINER_KEYS, proof.as_ref()], &crate::id()).0;
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(bus, false),
+        AccountMeta::new_readonly(WOOD_CONFIG_ADDRESS, false),
+        AccountMeta::new(proof, false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+        AccountMeta::new(miner_keys, false),
+    ];
+    accounts.push(match referrer {
+        Some(referrer) => AccountMeta::new(referrer, false),
+        None => AccountMeta::new_readonly(crate::id(), false),
+    });
+    accounts.push(AccountMeta::new(WOOD_LEADERBOARD_ADDRESS, false));
+    accounts.push(AccountMeta::new_readonly(system_program::id(), false));
     Instruction {
         program_id: crate::id(),
-        accounts: vec![
-            AccountMeta::new(signer, true),
-            AccountMeta::new(bus, false),
-            AccountMeta::new_readonly(WOOD_CONFIG_ADDRESS, false),
-            AccountMeta::new(proof, false),
-            AccountMeta::new_readonly(sysvar::instructions::id(), false),
-            AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
-        ],
+        accounts,
         data: [
             CoalInstruction::Mine.to_vec(),
             MineArgs {
@@ -281,19 +595,87 @@ pub fn chop_wood(
     }
 }
 
-/// Builds an open instruction.
-pub fn open_coal(signer: Pubkey, miner: Pubkey, payer: Pubkey) -> Instruction {
+/// Builds a mine instruction submitted by a relayer, with a solution signed by the miner.
+///
+/// The transaction must also include the `relay_signature` instruction verifying the miner's signature.
+#[allow(clippy::too_many_arguments)]
+pub fn mine_coal_relayed(
+    relayer: Pubkey,
+    miner: Pubkey,
+    proof_authority: Pubkey,
+    bus: Pubkey,
+    solution: Solution,
+    fee_bps: u64,
+    referrer: Option<Pubkey>,
+    ore_proof: Option<Pubkey>,
+    bumps: Bumps,
+) -> Instruction {
+    let relayer_proof = pda(&[COAL_PROOF, relayer.as_ref()], bumps.relayer_proof).0;
+    let mut ix = mine_coal(relayer, proof_authority, bus, solution, referrer, ore_proof, bumps);
+    ix.accounts.insert(6, AccountMeta::new(relayer_proof, false));
+    ix.data = mine_relayed_data(miner, solution, fee_bps);
+    ix
+}
+
+/// Builds a chop instruction submitted by a relayer, with a solution signed by the miner.
+///
+/// The transaction must also include the `relay_signature` instruction verifying the miner's signature.
+#[allow(clippy::too_many_arguments)]
+pub fn chop_wood_relayed(
+    relayer: Pubkey,
+    miner: Pubkey,
+    proof_authority: Pubkey,
+    bus: Pubkey,
+    solution: Solution,
+    fee_bps: u64,
+    referrer: Option<Pubkey>,
+    bumps: Bumps,
+) -> Instruction {
+    let relayer_proof = pda(&[WOOD_PROOF, relayer.as_ref()], bumps.relayer_proof).0;
+    let mut ix = chop_wood(relayer, proof_authority, bus, solution, referrer, bumps);
+    ix.accounts.insert(6, AccountMeta::new(relayer_proof, false));
+    ix.data = mine_relayed_data(miner, solution, fee_bps);
+    ix
+}
+
+fn mine_relayed_data(miner: Pubkey, solution: Solution, fee_bps: u64) -> Vec<u8> {
+    [
+        CoalInstruction::MineRelayed.to_vec(),
+        MineRelayedArgs {
+            digest: solution.d,
+            nonce: solution.n,
+            fee_bps: fee_bps.to_le_bytes(),
+            miner,
+        }
+        .to_bytes()
+        .to_vec(),
+    ]
+    .concat()
+}
+
+/// Builds an open instruction. A referrer is followed by the config, which sets the referral expiry.
+pub fn open_coal(
+    signer: Pubkey,
+    miner: Pubkey,
+    payer: Pubkey,
+    referrer: Option<Pubkey>,
+) -> Instruction {
     let proof_pda = Pubkey::find_program_address(&[COAL_PROOF, signer.as_ref()], &crate::id());
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(miner, false),
+        AccountMeta::new(payer, true),
+        AccountMeta::new(proof_pda.0, false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+    ];
+    if let Some(referrer) = referrer {
+        accounts.push(AccountMeta::new_readonly(referrer, false));
+        accounts.push(AccountMeta::new_readonly(COAL_CONFIG_ADDRESS, false));
+    }
     Instruction {
         program_id: crate::id(),
-        accounts: vec![
-            AccountMeta::new(signer, true),
-            AccountMeta::new_readonly(miner, false),
-            AccountMeta::new(payer, true),
-            AccountMeta::new(proof_pda.0, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
-        ],
+        accounts,
         data: [
             CoalInstruction::OpenCoal.to_vec(),
             OpenArgs { bump: proof_pda.1 }.to_bytes().to_vec(),
@@ -302,18 +684,29 @@ pub fn open_coal(signer: Pubkey, miner: Pubkey, payer: Pubkey) -> Instruction {
     }
 }
 
-pub fn open_wood(signer: Pubkey, miner: Pubkey, payer: Pubkey) -> Instruction {
+/// Builds an open instruction. A referrer is followed by the config, which sets the referral expiry.
+pub fn open_wood(
+    signer: Pubkey,
+    miner: Pubkey,
+    payer: Pubkey,
+    referrer: Option<Pubkey>,
+) -> Instruction {
     let proof_pda = Pubkey::find_program_address(&[WOOD_PROOF, signer.as_ref()], &crate::id());
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new_readonly(miner, false),
+        AccountMeta::new(payer, true),
+        AccountMeta::new(proof_pda.0, false),
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+    ];
+    if let Some(referrer) = referrer {
+        accounts.push(AccountMeta::new_readonly(referrer, false));
+        accounts.push(AccountMeta::new_readonly(WOOD_CONFIG_ADDRESS, false));
+    }
     Instruction {
         program_id: crate::id(),
-        accounts: vec![
-            AccountMeta::new(signer, true),
-            AccountMeta::new_readonly(miner, false),
-            AccountMeta::new(payer, true),
-            AccountMeta::new(proof_pda.0, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
-        ],
+        accounts,
         data: [
             CoalInstruction::OpenWood.to_vec(),
             OpenArgs { bump: proof_pda.1 }.to_bytes().to_vec(),
@@ -366,7 +759,7 @@ pub fn unequip(
     collection: Pubkey,
 ) -> Instruction {
     let tool_pda = Pubkey::find_program_address(&[COAL_MAIN_HAND_TOOL, signer.as_ref()], &crate::id());
-    let plugin_authority = Pubkey::find_program_address(&[PLUGIN_UPDATE_AUTHORITY], &crate::id());
+    let plugin_authority = (PLUGIN_UPDATE_AUTHORITY_ADDRESS, PLUGIN_UPDATE_AUTHORITY_BUMP);
 
     Instruction {
         program_id: crate::id(),
@@ -394,81 +787,75 @@ pub fn unequip(
     }
 }
 
-/// Builds a reset instruction.
-pub fn reset_coal(signer: Pubkey) -> Instruction {
-    let treasury_tokens = spl_associated_token_account::get_associated_token_address(
-        &TREASURY_ADDRESS,
-        &COAL_MINT_ADDRESS,
-    );
+/// Builds a propose proof authority instruction.
+pub fn propose_proof_authority_coal(signer: Pubkey, new_authority: Pubkey, bumps: Bumps) -> Instruction {
+    let proof = pda(&[COAL_PROOF, signer.as_ref()], bumps.proof).0;
     Instruction {
         program_id: crate::id(),
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(COAL_BUS_ADDRESSES[0], false),
-            AccountMeta::new(COAL_BUS_ADDRESSES[1], false),
-            AccountMeta::new(COAL_BUS_ADDRESSES[2], false),
-            AccountMeta::new(COAL_BUS_ADDRESSES[3], false),
-            AccountMeta::new(COAL_BUS_ADDRESSES[4], false),
-            AccountMeta::new(COAL_BUS_ADDRESSES[5], false),
-            AccountMeta::new(COAL_BUS_ADDRESSES[6], false),
-            AccountMeta::new(COAL_BUS_ADDRESSES[7], false),
-            AccountMeta::new(COAL_CONFIG_ADDRESS, false),
-            AccountMeta::new(COAL_MINT_ADDRESS, false),
-            AccountMeta::new(TREASURY_ADDRESS, false),
-            AccountMeta::new(treasury_tokens, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(proof, false),
+            AccountMeta::new_readonly(new_authority, false),
         ],
-        data: CoalInstruction::Reset.to_vec(),
+        data: CoalInstruction::ProposeProofAuthority.to_vec(),
     }
 }
 
-pub fn reset_wood(signer: Pubkey) -> Instruction {
-    let treasury_tokens = spl_associated_token_account::get_associated_token_address(
-        &TREASURY_ADDRESS,
-        &WOOD_MINT_ADDRESS,
-    );
+pub fn propose_proof_authority_wood(signer: Pubkey, new_authority: Pubkey, bumps: Bumps) -> Instruction {
+    let proof = pda(&[WOOD_PROOF, signer.as_ref()], bumps.proof).0;
     Instruction {
         program_id: crate::id(),
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(WOOD_BUS_ADDRESSES[0], false),
-            AccountMeta::new(WOOD_BUS_ADDRESSES[1], false),
-            AccountMeta::new(WOOD_BUS_ADDRESSES[2], false),
-            AccountMeta::new(WOOD_BUS_ADDRESSES[3], false),
-            AccountMeta::new(WOOD_BUS_ADDRESSES[4], false),
-            AccountMeta::new(WOOD_BUS_ADDRESSES[5], false),
-            AccountMeta::new(WOOD_BUS_ADDRESSES[6], false),
-            AccountMeta::new(WOOD_BUS_ADDRESSES[7], false),
-            AccountMeta::new(WOOD_CONFIG_ADDRESS, false),
-            AccountMeta::new(WOOD_MINT_ADDRESS, false),
-            AccountMeta::new(TREASURY_ADDRESS, false),
-            AccountMeta::new(treasury_tokens, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(proof, false),
+            AccountMeta::new_readonly(new_authority, false),
         ],
-        data: CoalInstruction::Reset.to_vec(),
+        data: CoalInstruction::ProposeProofAuthority.to_vec(),
     }
 }
 
-/// Build a stake instruction.
-pub fn stake_coal(signer: Pubkey, sender: Pubkey, amount: u64) -> Instruction {
-    let proof = Pubkey::find_program_address(&[COAL_PROOF, signer.as_ref()], &crate::id()).0;
-    let treasury_tokens = spl_associated_token_account::get_associated_token_address(
-        &TREASURY_ADDRESS,
-        &COAL_MINT_ADDRESS,
-    );
+/// Builds an accept proof authority instruction.
+///
+/// The old tool account is always passed. If a tool is equipped, its asset and collection must be
+/// provided so the tool follows the proof.
+pub fn accept_proof_authority_coal(
+    signer: Pubkey,
+    old_authority: Pubkey,
+    tool: Option<(Pubkey, Pubkey)>,
+) -> Instruction {
+    let old_proof = Pubkey::find_program_address(&[COAL_PROOF, old_authority.as_ref()], &crate::id()).0;
+    let old_miner_keys = Pubkey::find_program_address(&[MINER_KEYS, old_proof.as_ref()], &crate::id()).0;
+    let old_vesting = Pubkey::find_program_address(&[VESTING, old_proof.as_ref()], &crate::id()).0;
+    let proof_pda = Pubkey::find_program_address(&[COAL_PROOF, signer.as_ref()], &crate::id());
+    let old_tool_pda = Pubkey::find_program_address(&[COAL_MAIN_HAND_TOOL, old_authority.as_ref()], &crate::id());
+    let tool_pda = Pubkey::find_program_address(&[COAL_MAIN_HAND_TOOL, signer.as_ref()], &crate::id());
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(old_authority, false),
+        AccountMeta::new(old_proof, false),
+        AccountMeta::new(proof_pda.0, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(old_miner_keys, false),
+        AccountMeta::new(old_tool_pda.0, false),
+        AccountMeta::new(old_vesting, false),
+    ];
+    if let Some((asset, collection)) = tool {
+        accounts.extend([
+            AccountMeta::new(tool_pda.0, false),
+            AccountMeta::new(asset, false),
+            AccountMeta::new(collection, false),
+            AccountMeta::new_readonly(mpl_core::ID, false),
+        ]);
+    }
     Instruction {
         program_id: crate::id(),
-        accounts: vec![
-            AccountMeta::new(signer, true),
-            AccountMeta::new(proof, false),
-            AccountMeta::new(sender, false),
-            AccountMeta::new(treasury_tokens, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
-        ],
+        accounts,
         data: [
-            CoalInstruction::Stake.to_vec(),
-            StakeArgs {
-                amount: amount.to_le_bytes(),
+            CoalInstruction::AcceptProofAuthority.to_vec(),
+            AcceptProofAuthorityArgs {
+                proof_bump: proof_pda.1,
+                tool_bump: tool_pda.1,
+                old_tool_bump: old_tool_pda.1,
             }
             .to_bytes()
             .to_vec(),
@@ -477,25 +864,28 @@ pub fn stake_coal(signer: Pubkey, sender: Pubkey, amount: u64) -> Instruction {
     }
 }
 
-pub fn stake_wood(signer: Pubkey, sender: Pubkey, amount: u64) -> Instruction {
-    let proof = Pubkey::find_program_address(&[WOOD_PROOF, signer.as_ref()], &crate::id()).0;
-    let treasury_tokens = spl_associated_token_account::get_associated_token_address(
-        &TREASURY_ADDRESS,
-        &WOOD_MINT_ADDRESS,
-    );
+pub fn accept_proof_authority_wood(signer: Pubkey, old_authority: Pubkey) -> Instruction {
+    let old_proof = Pubkey::find_program_address(&[WOOD_PROOF, old_authority.as_ref()], &crate::id()).0;
+    let old_miner_keys = Pubkey::find_program_address(&[MINER_KEYS, old_proof.as_ref()], &crate::id()).0;
+    let old_vesting = Pubkey::find_program_address(&[VESTING, old_proof.as_ref()], &crate::id()).0;
+    let proof_pda = Pubkey::find_program_address(&[WOOD_PROOF, signer.as_ref()], &crate::id());
     Instruction {
         program_id: crate::id(),
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(proof, false),
-            AccountMeta::new(sender, false),
-            AccountMeta::new(treasury_tokens, false),
-            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(old_authority, false),
+            AccountMeta::new(old_proof, false),
+            AccountMeta::new(proof_pda.0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(old_miner_keys, false),
+            AccountMeta::new(old_vesting, false),
         ],
         data: [
-            CoalInstruction::Stake.to_vec(),
-            StakeArgs {
-                amount: amount.to_le_bytes(),
+            CoalInstruction::AcceptProofAuthority.to_vec(),
+            AcceptProofAuthorityArgs {
+                proof_bump: proof_pda.1,
+                tool_bump: 0,
+                old_tool_bump: 0,
             }
             .to_bytes()
             .to_vec(),
@@ -504,48 +894,547 @@ pub fn stake_wood(signer: Pubkey, sender: Pubkey, amount: u64) -> Instruction {
     }
 }
 
-// Build an update instruction.
-pub fn update_coal(signer: Pubkey, miner: Pubkey) -> Instruction {
-    let proof = Pubkey::find_program_address(&[COAL_PROOF, signer.as_ref()], &crate::id()).0;
+/// Builds an add miner instruction.
+///
+/// A hash quota of zero allows the miner to submit any number of hashes per epoch.
+pub fn add_miner(
+    signer: Pubkey,
+    proof: Pubkey,
+    miner: Pubkey,
+    expires_at: i64,
+    hash_quota: u64,
+) -> Instruction {
+    let miner_keys_pda = Pubkey::find_program_address(&[MINER_KEYS, proof.as_ref()], &crate::id());
     Instruction {
         program_id: crate::id(),
         accounts: vec![
             AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(proof, false),
+            AccountMeta::new(miner_keys_pda.0, false),
             AccountMeta::new_readonly(miner, false),
-            AccountMeta::new(proof, false),
+            AccountMeta::new_readonly(system_program::id(), false),
         ],
-        data: CoalInstruction::Update.to_vec(),
+        data: [
+            CoalInstruction::AddMiner.to_vec(),
+            AddMinerArgs {
+                expires_at: expires_at.to_le_bytes(),
+                hash_quota: hash_quota.to_le_bytes(),
+                bump: miner_keys_pda.1,
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
     }
 }
 
-pub fn update_wood(signer: Pubkey, miner: Pubkey) -> Instruction {
-    let proof = Pubkey::find_program_address(&[WOOD_PROOF, signer.as_ref()], &crate::id()).0;
+/// Builds a remove miner instruction.
+pub fn remove_miner(signer: Pubkey, proof: Pubkey, miner: Pubkey) -> Instruction {
+    let miner_keys = Pubkey::find_program_address(&[MINER_KEYS, proof.as_ref()], &crate::id()).0;
     Instruction {
         program_id: crate::id(),
         accounts: vec![
             AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(proof, false),
+            AccountMeta::new(miner_keys, false),
             AccountMeta::new_readonly(miner, false),
-            AccountMeta::new(proof, false),
         ],
-        data: CoalInstruction::Update.to_vec(),
+        data: CoalInstruction::RemoveMiner.to_vec(),
     }
 }
 
-/// Builds an initialize instruction.
-pub fn init_coal(signer: Pubkey) -> Instruction {
-    let bus_pdas = [
-        Pubkey::find_program_address(&[COAL_BUS, &[0]], &crate::id()),
-        Pubkey::find_program_address(&[COAL_BUS, &[1]], &crate::id()),
-        Pubkey::find_program_address(&[COAL_BUS, &[2]], &crate::id()),
-        Pubkey::find_program_address(&[COAL_BUS, &[3]], &crate::id()),
-        Pubkey::find_program_address(&[COAL_BUS, &[4]], &crate::id()),
+/// Builds a smelt instruction.
+pub fn smelt(signer: Pubkey, beneficiary: Pubkey, amount: u64) -> Instruction {
+    let coal_tokens =
+        spl_associated_token_account::get_associated_token_address(&signer, &COAL_MINT_ADDRESS);
+    let wood_tokens =
+        spl_associated_token_account::get_associated_token_address(&signer, &WOOD_MINT_ADDRESS);
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(INGOT_CONFIG_ADDRESS, false),
+            AccountMeta::new(COAL_MINT_ADDRESS, false),
+            AccountMeta::new(WOOD_MINT_ADDRESS, false),
+            AccountMeta::new(INGOT_MINT_ADDRESS, false),
+            AccountMeta::new(coal_tokens, false),
+            AccountMeta::new(wood_tokens, false),
+            AccountMeta::new(beneficiary, false),
+            AccountMeta::new_readonly(TREASURY_ADDRESS, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        data: [
+            CoalInstruction::Smelt.to_vec(),
+            SmeltArgs {
+                amount: amount.to_le_bytes(),
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds a forge tool instruction. The asset must be a new keypair which signs the transaction.
+pub fn forge_tool(signer: Pubkey, asset: Pubkey, coal_amount: u64, wood_amount: u64) -> Instruction {
+    let coal_tokens =
+        spl_associated_token_account::get_associated_token_address(&signer, &COAL_MINT_ADDRESS);
+    let wood_tokens =
+        spl_associated_token_account::get_associated_token_address(&signer, &WOOD_MINT_ADDRESS);
+    let plugin_authority_pda = (PLUGIN_UPDATE_AUTHORITY_ADDRESS, PLUGIN_UPDATE_AUTHORITY_BUMP);
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(asset, true),
+            AccountMeta::new(FORGE_COLLECTION_ADDRESS, false),
+            AccountMeta::new_readonly(plugin_authority_pda.0, false),
+            AccountMeta::new(COAL_MINT_ADDRESS, false),
+            AccountMeta::new(WOOD_MINT_ADDRESS, false),
+            AccountMeta::new(coal_tokens, false),
+            AccountMeta::new(wood_tokens, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(mpl_core::ID, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: [
+            CoalInstruction::ForgeTool.to_vec(),
+            ForgeToolArgs {
+                coal_amount: coal_amount.to_le_bytes(),
+                wood_amount: wood_amount.to_le_bytes(),
+                plugin_authority_bump: plugin_authority_pda.1,
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds an audit treasury instruction.
+pub fn audit_treasury_coal(bus_count: u64) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(COAL_CONFIG_ADDRESS, false),
+        AccountMeta::new_readonly(COAL_TREASURY_TOKENS_ADDRESS, false),
+    ];
+    accounts.extend((0..bus_count).map(|id| AccountMeta::new_readonly(coal_bus_address(id), false)));
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: CoalInstruction::AuditTreasury.to_vec(),
+    }
+}
+
+pub fn audit_treasury_wood(bus_count: u64) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(WOOD_CONFIG_ADDRESS, false),
+        AccountMeta::new_readonly(WOOD_TREASURY_TOKENS_ADDRESS, false),
+    ];
+    accounts.extend((0..bus_count).map(|id| AccountMeta::new_readonly(wood_bus_address(id), false)));
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: CoalInstruction::AuditTreasury.to_vec(),
+    }
+}
+
+/// Builds an upgrade account instruction.
+pub fn upgrade_account(payer: Pubkey, account: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(account, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: CoalInstruction::UpgradeAccount.to_vec(),
+    }
+}
+
+/// Builds an upgrade account instruction for the coal config.
+///
+/// Configs which predate the outstanding liabilities backfill them from the treasury balance.
+pub fn upgrade_config_coal(payer: Pubkey) -> Instruction {
+    let mut ix = upgrade_account(payer, COAL_CONFIG_ADDRESS);
+    ix.accounts
+        .push(AccountMeta::new_readonly(COAL_TREASURY_TOKENS_ADDRESS, false));
+    ix
+}
+
+/// Builds an upgrade account instruction for the wood config.
+///
+/// Configs which predate the outstanding liabilities backfill them from the treasury balance.
+pub fn upgrade_config_wood(payer: Pubkey) -> Instruction {
+    let mut ix = upgrade_account(payer, WOOD_CONFIG_ADDRESS);
+    ix.accounts
+        .push(AccountMeta::new_readonly(WOOD_TREASURY_TOKENS_ADDRESS, false));
+    ix
+}
+
+/// Builds an upgrade account instruction for a coal proof.
+///
+/// Referred proofs which predate the referral expiry backfill it from the config.
+pub fn upgrade_proof_coal(payer: Pubkey, authority: Pubkey) -> Instruction {
+    let proof = Pubkey::find_program_address(&[COAL_PROOF, authority.as_ref()], &crate::id()).0;
+    let mut ix = upgrade_account(payer, proof);
+    ix.accounts
+        .push(AccountMeta::new_readonly(COAL_CONFIG_ADDRESS, false));
+    ix
+}
+
+/// Builds an upgrade account instruction for a wood proof.
+///
+/// Referred proofs which predate the referral expiry backfill it from the config.
+pub fn upgrade_proof_wood(payer: Pubkey, authority: Pubkey) -> Instruction {
+    let proof = Pubkey::find_program_address(&[WOOD_PROOF, authority.as_ref()], &crate::id()).0;
+    let mut ix = upgrade_account(payer, proof);
+    ix.accounts
+        .push(AccountMeta::new_readonly(WOOD_CONFIG_ADDRESS, false));
+    ix
+}
+
+/// Builds a reset instruction.
+pub fn reset_coal(signer: Pubkey, bus_count: u64) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(COAL_CONFIG_ADDRESS, false),
+        AccountMeta::new(COAL_MINT_ADDRESS, false),
+        AccountMeta::new(TREASURY_ADDRESS, false),
+        AccountMeta::new(COAL_TREASURY_TOKENS_ADDRESS, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    accounts.extend((0..bus_count).map(|id| AccountMeta::new(coal_bus_address(id), false)));
+    accounts.push(AccountMeta::new(COAL_LEADERBOARD_ADDRESS, false));
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: CoalInstruction::Reset.to_vec(),
+    }
+}
+
+pub fn reset_wood(signer: Pubkey, bus_count: u64) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(WOOD_CONFIG_ADDRESS, false),
+        AccountMeta::new(WOOD_MINT_ADDRESS, false),
+        AccountMeta::new(TREASURY_ADDRESS, false),
+        AccountMeta::new(WOOD_TREASURY_TOKENS_ADDRESS, false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+    ];
+    accounts.extend((0..bus_count).map(|id| AccountMeta::new(wood_bus_address(id), false)));
+    accounts.push(AccountMeta::new(WOOD_LEADERBOARD_ADDRESS, false));
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: CoalInstruction::Reset.to_vec(),
+    }
+}
+
+/// Builds a rebalance instruction to spread the remaining rewards evenly across the busses.
+pub fn rebalance_coal(signer: Pubkey, bus_count: u64) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(COAL_CONFIG_ADDRESS, false),
+    ];
+    accounts.extend((0..bus_count).map(|id| AccountMeta::new(coal_bus_address(id), false)));
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: CoalInstruction::Rebalance.to_vec(),
+    }
+}
+
+/// Builds a rebalance instruction to spread the remaining rewards evenly across the busses.
+pub fn rebalance_wood(signer: Pubkey, bus_count: u64) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(WOOD_CONFIG_ADDRESS, false),
+    ];
+    accounts.extend((0..bus_count).map(|id| AccountMeta::new(wood_bus_address(id), false)));
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: CoalInstruction::Rebalance.to_vec(),
+    }
+}
+
+/// Build a stake instruction.
+pub fn stake_coal(
+    signer: Pubkey,
+    sender: Pubkey,
+    amount: u64,
+    token_program: Pubkey,
+    bumps: Bumps,
+) -> Instruction {
+    let proof = pda(&[COAL_PROOF, signer.as_ref()], bumps.proof).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(proof, false),
+            AccountMeta::new(COAL_CONFIG_ADDRESS, false),
+            AccountMeta::new(sender, false),
+            AccountMeta::new(COAL_TREASURY_TOKENS_ADDRESS, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(COAL_MINT_ADDRESS, false),
+        ],
+        data: [
+            CoalInstruction::Stake.to_vec(),
+            StakeArgs {
+                amount: amount.to_le_bytes(),
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+pub fn stake_wood(
+    signer: Pubkey,
+    sender: Pubkey,
+    amount: u64,
+    token_program: Pubkey,
+    bumps: Bumps,
+) -> Instruction {
+    let proof = pda(&[WOOD_PROOF, signer.as_ref()], bumps.proof).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(proof, false),
+            AccountMeta::new(WOOD_CONFIG_ADDRESS, false),
+            AccountMeta::new(sender, false),
+            AccountMeta::new(WOOD_TREASURY_TOKENS_ADDRESS, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(WOOD_MINT_ADDRESS, false),
+        ],
+        data: [
+            CoalInstruction::Stake.to_vec(),
+            StakeArgs {
+                amount: amount.to_le_bytes(),
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds a configure vesting instruction to set the duration claims of a proof vest over.
+pub fn configure_vesting(signer: Pubkey, proof: Pubkey, duration: i64) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(proof, false),
+        ],
+        data: [
+            CoalInstruction::ConfigureVesting.to_vec(),
+            ConfigureVestingArgs {
+                duration: duration.to_le_bytes(),
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds a release vested instruction to pay out the unlocked portion of vesting claims.
+pub fn release_vested_coal(
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    token_program: Pubkey,
+    bumps: Bumps,
+) -> Instruction {
+    let proof = pda(&[COAL_PROOF, signer.as_ref()], bumps.proof).0;
+    let vesting = pda(&[VESTING, proof.as_ref()], bumps.vesting).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(beneficiary, false),
+            AccountMeta::new(vesting, false),
+            AccountMeta::new(COAL_CONFIG_ADDRESS, false),
+            AccountMeta::new_readonly(TREASURY_ADDRESS, false),
+            AccountMeta::new(COAL_TREASURY_TOKENS_ADDRESS, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(COAL_MINT_ADDRESS, false),
+        ],
+        data: CoalInstruction::ReleaseVested.to_vec(),
+    }
+}
+
+/// Builds a release vested instruction to pay out the unlocked portion of vesting claims.
+pub fn release_vested_wood(
+    signer: Pubkey,
+    beneficiary: Pubkey,
+    token_program: Pubkey,
+    bumps: Bumps,
+) -> Instruction {
+    let proof = pda(&[WOOD_PROOF, signer.as_ref()], bumps.proof).0;
+    let vesting = pda(&[VESTING, proof.as_ref()], bumps.vesting).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(beneficiary, false),
+            AccountMeta::new(vesting, false),
+            AccountMeta::new(WOOD_CONFIG_ADDRESS, false),
+            AccountMeta::new_readonly(TREASURY_ADDRESS, false),
+            AccountMeta::new(WOOD_TREASURY_TOKENS_ADDRESS, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(WOOD_MINT_ADDRESS, false),
+        ],
+        data: CoalInstruction::ReleaseVested.to_vec(),
+    }
+}
+
+/// Builds a reap proof instruction to close an abandoned proof of the given authority.
+pub fn reap_proof_coal(signer: Pubkey, authority: Pubkey, bumps: Bumps) -> Instruction {
+    let proof = pda(&[COAL_PROOF, authority.as_ref()], bumps.proof).0;
+    let miner_keys = Pubkey::find_program_address(&[MINER_KEYS, proof.as_ref()], &crate::id()).0;
+    let tool = pda(&[COAL_MAIN_HAND_TOOL, authority.as_ref()], bumps.tool).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(authority, false),
+            AccountMeta::new(proof, false),
+            AccountMeta::new_readonly(COAL_CONFIG_ADDRESS, false),
+            AccountMeta::new(miner_keys, false),
+            AccountMeta::new_readonly(tool, false),
+        ],
+        data: CoalInstruction::ReapProof.to_vec(),
+    }
+}
+
+/// Builds a reap proof instruction to close an abandoned proof of the given authority.
+pub fn reap_proof_wood(signer: Pubkey, authority: Pubkey, bumps: Bumps) -> Instruction {
+    let proof = pda(&[WOOD_PROOF, authority.as_ref()], bumps.proof).0;
+    let miner_keys = Pubkey::find_program_address(&[MINER_KEYS, proof.as_ref()], &crate::id()).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(authority, false),
+            AccountMeta::new(proof, false),
+            AccountMeta::new_readonly(WOOD_CONFIG_ADDRESS, false),
+            AccountMeta::new(miner_keys, false),
+        ],
+        data: CoalInstruction::ReapProof.to_vec(),
+    }
+}
+
+/// Builds a stake instruction which credits the proof of another authority.
+pub fn stake_for_coal(
+    signer: Pubkey,
+    proof_authority: Pubkey,
+    sender: Pubkey,
+    amount: u64,
+    token_program: Pubkey,
+    bumps: Bumps,
+) -> Instruction {
+    let proof = pda(&[COAL_PROOF, proof_authority.as_ref()], bumps.proof).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(proof, false),
+            AccountMeta::new(COAL_CONFIG_ADDRESS, false),
+            AccountMeta::new(sender, false),
+            AccountMeta::new(COAL_TREASURY_TOKENS_ADDRESS, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(COAL_MINT_ADDRESS, false),
+        ],
+        data: [
+            CoalInstruction::StakeFor.to_vec(),
+            StakeArgs {
+                amount: amount.to_le_bytes(),
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds a stake instruction which credits the proof of another authority.
+pub fn stake_for_wood(
+    signer: Pubkey,
+    proof_authority: Pubkey,
+    sender: Pubkey,
+    amount: u64,
+    token_program: Pubkey,
+    bumps: Bumps,
+) -> Instruction {
+    let proof = pda(&[WOOD_PROOF, proof_authority.as_ref()], bumps.proof).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(proof, false),
+            AccountMeta::new(WOOD_CONFIG_ADDRESS, false),
+            AccountMeta::new(sender, false),
+            AccountMeta::new(WOOD_TREASURY_TOKENS_ADDRESS, false),
+            AccountMeta::new_readonly(token_program, false),
+            AccountMeta::new_readonly(WOOD_MINT_ADDRESS, false),
+        ],
+        data: [
+            CoalInstruction::StakeFor.to_vec(),
+            StakeArgs {
+                amount: amount.to_le_bytes(),
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+// Build an update instruction.
+pub fn update_coal(signer: Pubkey, miner: Pubkey, bumps: Bumps) -> Instruction {
+    let proof = pda(&[COAL_PROOF, signer.as_ref()], bumps.proof).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(miner, false),
+            AccountMeta::new(proof, false),
+        ],
+        data: CoalInstruction::Update.to_vec(),
+    }
+}
+
+pub fn update_wood(signer: Pubkey, miner: Pubkey, bumps: Bumps) -> Instruction {
+    let proof = pda(&[WOOD_PROOF, signer.as_ref()], bumps.proof).0;
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(miner, false),
+            AccountMeta::new(proof, false),
+        ],
+        data: CoalInstruction::Update.to_vec(),
+    }
+}
+
+/// Builds an initialize instruction.
+pub fn init_coal(signer: Pubkey) -> Instruction {
+    let bus_pdas = [
+        Pubkey::find_program_address(&[COAL_BUS, &[0]], &crate::id()),
+        Pubkey::find_program_address(&[COAL_BUS, &[1]], &crate::id()),
+        Pubkey::find_program_address(&[COAL_BUS, &[2]], &crate::id()),
+        Pubkey::find_program_address(&[COAL_BUS, &[3]], &crate::id()),
+        Pubkey::find_program_address(&[COAL_BUS, &[4]], &crate::id()),
         Pubkey::find_program_address(&[COAL_BUS, &[5]], &crate::id()),
         Pubkey::find_program_address(&[COAL_BUS, &[6]], &crate::id()),
         Pubkey::find_program_address(&[COAL_BUS, &[7]], &crate::id()),
     ];
     let config_pda = Pubkey::find_program_address(&[COAL_CONFIG], &crate::id());
     let mint_pda = Pubkey::find_program_address(&[COAL_MINT, MINT_NOISE.as_slice()], &crate::id());
-    let treasury_pda = Pubkey::find_program_address(&[TREASURY], &crate::id());
+    let treasury_pda = (TREASURY_ADDRESS, TREASURY_BUMP);
     let treasury_tokens =
         spl_associated_token_account::get_associated_token_address(&treasury_pda.0, &mint_pda.0);
     let metadata_pda = Pubkey::find_program_address(
@@ -578,6 +1467,7 @@ pub fn init_coal(signer: Pubkey) -> Instruction {
             AccountMeta::new_readonly(spl_associated_token_account::id(), false),
             AccountMeta::new_readonly(mpl_token_metadata::ID, false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new(COAL_LEADERBOARD_ADDRESS, false),
         ],
         data: [
             CoalInstruction::InitCoal.to_vec(),
@@ -615,7 +1505,7 @@ pub fn init_wood(signer: Pubkey) -> Instruction {
     ];
     let config_pda = Pubkey::find_program_address(&[WOOD_CONFIG], &crate::id());
     let mint_pda = Pubkey::find_program_address(&[WOOD_MINT, MINT_NOISE.as_slice()], &crate::id());
-    let treasury_pda = Pubkey::find_program_address(&[TREASURY], &crate::id());
+    let treasury_pda = (TREASURY_ADDRESS, TREASURY_BUMP);
     let treasury_tokens =
         spl_associated_token_account::get_associated_token_address(&treasury_pda.0, &mint_pda.0);
     let metadata_pda = Pubkey::find_program_address(
@@ -648,6 +1538,7 @@ pub fn init_wood(signer: Pubkey) -> Instruction {
             AccountMeta::new_readonly(spl_associated_token_account::id(), false),
             AccountMeta::new_readonly(mpl_token_metadata::ID, false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new(WOOD_LEADERBOARD_ADDRESS, false),
         ],
         data: [
             CoalInstruction::InitWood.to_vec(),
@@ -671,3 +1562,281 @@ pub fn init_wood(signer: Pubkey) -> Instruction {
         .concat(),
     }
 }
+
+/// Builds a configure referrals instruction.
+pub fn configure_referrals(signer: Pubkey, config: Pubkey, bonus_bps: u64, epochs: u64) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config, false),
+        ],
+        data: [
+            CoalInstruction::ConfigureReferrals.to_vec(),
+            ConfigureReferralsArgs {
+                bonus_bps: bonus_bps.to_le_bytes(),
+                epochs: epochs.to_le_bytes(),
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds an add busses instruction to grow a resource from `bus_count` to `new_bus_count` busses.
+pub fn add_busses_coal(signer: Pubkey, bus_count: u64, new_bus_count: u64) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(COAL_CONFIG_ADDRESS, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend((bus_count..new_bus_count).map(|id| AccountMeta::new(coal_bus_address(id), false)));
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: CoalInstruction::AddBusses.to_vec(),
+    }
+}
+
+/// Builds an add busses instruction to grow a resource from `bus_count` to `new_bus_count` busses.
+pub fn add_busses_wood(signer: Pubkey, bus_count: u64, new_bus_count: u64) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(WOOD_CONFIG_ADDRESS, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    accounts.extend((bus_count..new_bus_count).map(|id| AccountMeta::new(wood_bus_address(id), false)));
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: CoalInstruction::AddBusses.to_vec(),
+    }
+}
+
+/// Builds an instruction to create the leaderboard of a resource.
+pub fn init_leaderboard_coal(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(COAL_CONFIG_ADDRESS, false),
+            AccountMeta::new(COAL_LEADERBOARD_ADDRESS, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: CoalInstruction::InitLeaderboard.to_vec(),
+    }
+}
+
+/// Builds an instruction to create the leaderboard of a resource.
+pub fn init_leaderboard_wood(signer: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new_readonly(WOOD_CONFIG_ADDRESS, false),
+            AccountMeta::new(WOOD_LEADERBOARD_ADDRESS, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: CoalInstruction::InitLeaderboard.to_vec(),
+    }
+}
+
+/// Builds a configure reaping instruction.
+pub fn configure_reaping(
+    signer: Pubkey,
+    config: Pubkey,
+    reap_after: i64,
+    reaper_bonus_bps: u64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config, false),
+        ],
+        data: [
+            CoalInstruction::ConfigureReaping.to_vec(),
+            ConfigureReapingArgs {
+                reap_after: reap_after.to_le_bytes(),
+                reaper_bonus_bps: reaper_bonus_bps.to_le_bytes(),
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds a configure jackpot instruction.
+pub fn configure_jackpot(
+    signer: Pubkey,
+    config: Pubkey,
+    jackpot_bps: u64,
+    jackpot_difficulty: u64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config, false),
+        ],
+        data: [
+            CoalInstruction::ConfigureJackpot.to_vec(),
+            ConfigureJackpotArgs {
+                jackpot_bps: jackpot_bps.to_le_bytes(),
+                jackpot_difficulty: jackpot_difficulty.to_le_bytes(),
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds a configure epoch instruction.
+pub fn configure_epoch(signer: Pubkey, config: Pubkey, epoch_duration: i64) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config, false),
+        ],
+        data: [
+            CoalInstruction::ConfigureEpoch.to_vec(),
+            ConfigureEpochArgs {
+                epoch_duration: epoch_duration.to_le_bytes(),
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds an instruction to initialize the ingot mint.
+pub fn init_ingot(signer: Pubkey) -> Instruction {
+    let config_pda = Pubkey::find_program_address(&[INGOT_CONFIG], &crate::id());
+    let mint_pda = Pubkey::find_program_address(&[INGOT_MINT, MINT_NOISE.as_slice()], &crate::id());
+    let treasury_pda = (TREASURY_ADDRESS, TREASURY_BUMP);
+    let metadata_pda = Pubkey::find_program_address(
+        &[
+            METADATA,
+            mpl_token_metadata::ID.as_ref(),
+            mint_pda.0.as_ref(),
+        ],
+        &mpl_token_metadata::ID,
+    );
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(config_pda.0, false),
+            AccountMeta::new(metadata_pda.0, false),
+            AccountMeta::new(mint_pda.0, false),
+            AccountMeta::new_readonly(treasury_pda.0, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+        ],
+        data: [
+            CoalInstruction::InitIngot.to_vec(),
+            InitIngotArgs {
+                config_bump: config_pda.1,
+                metadata_bump: metadata_pda.1,
+                mint_bump: mint_pda.1,
+                treasury_bump: treasury_pda.1,
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds an instruction to update the smelting recipe and output cap.
+pub fn configure_smelting(
+    signer: Pubkey,
+    coal_cost: u64,
+    wood_cost: u64,
+    epoch_output_cap: u64,
+) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(INGOT_CONFIG_ADDRESS, false),
+        ],
+        data: [
+            CoalInstruction::ConfigureSmelting.to_vec(),
+            ConfigureSmeltingArgs {
+                coal_cost: coal_cost.to_le_bytes(),
+                wood_cost: wood_cost.to_le_bytes(),
+                epoch_output_cap: epoch_output_cap.to_le_bytes(),
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Builds an instruction to create the forge collection.
+pub fn init_forge(signer: Pubkey) -> Instruction {
+    let collection_pda = Pubkey::find_program_address(&[FORGE_COLLECTION], &crate::id());
+    let plugin_authority_pda = (PLUGIN_UPDATE_AUTHORITY_ADDRESS, PLUGIN_UPDATE_AUTHORITY_BUMP);
+    Instruction {
+        program_id: crate::id(),
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(collection_pda.0, false),
+            AccountMeta::new_readonly(plugin_authority_pda.0, false),
+            AccountMeta::new_readonly(mpl_core::ID, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        data: [
+            CoalInstruction::InitForge.to_vec(),
+            InitForgeArgs {
+                collection_bump: collection_pda.1,
+                plugin_authority_bump: plugin_authority_pda.1,
+            }
+            .to_bytes()
+            .to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pda_with_stored_bump() {
+        let authority = Pubkey::new_unique();
+        let seeds: &[&[u8]] = &[COAL_PROOF, authority.as_ref()];
+        let (address, bump) = Pubkey::find_program_address(seeds, &crate::id());
+        assert_eq!(pda(seeds, Some(bump as u64)), (address, bump));
+        assert_eq!(pda(seeds, None), (address, bump));
+
+        // Proofs from before the bump was stored read it as zero.
+        assert_eq!(pda(seeds, Some(0)), (address, bump));
+    }
+
+    #[test]
+    fn test_builders_with_stored_bumps() {
+        let authority = Pubkey::new_unique();
+        let solution = Solution::new([0; 16], [0; 8]);
+        let bumps = Bumps {
+            proof: Some(pda(&[COAL_PROOF, authority.as_ref()], None).1 as u64),
+            tool: Some(pda(&[COAL_MAIN_HAND_TOOL, authority.as_ref()], None).1 as u64),
+            ..Default::default()
+        };
+        assert_eq!(
+            mine_coal(authority, authority, COAL_BUS_ADDRESSES[0], solution, None, None, bumps),
+            mine_coal(authority, authority, COAL_BUS_ADDRESSES[0], solution, None, None, Bumps::default())
+        );
+        assert_eq!(close_coal(authority, bumps), close_coal(authority, Bumps::default()));
+    }
+}
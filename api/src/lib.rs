@@ -1,9 +1,16 @@
+pub mod auth;
 pub mod consts;
+pub mod emission;
 pub mod error;
 pub mod event;
+pub mod forge;
 pub mod instruction;
 pub mod loaders;
+pub mod ore;
+pub mod relay;
 pub mod state;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 
 pub(crate) use coal_utils as utils;
 
@@ -1,18 +1,19 @@
 use solana_program::{
-    account_info::AccountInfo, msg, program_error::ProgramError, program_pack::Pack, pubkey::Pubkey, system_program, sysvar
+    account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey, system_program, sysvar
 };
-use spl_token::state::Mint;
+use spl_token_2022::{extension::StateWithExtensions, state::{Account, Mint}};
 use mpl_core::{Asset, types::UpdateAuthority};
 
 use crate::{
     consts::*,
-    state::{Bus, Config, Proof, ProofV2, Treasury, Tool, WoodConfig},
+    ore::OreProof,
+    state::{Bus, Config, IngotConfig, Leaderboard, MinerKeys, Proof, ProofV2, Treasury, Tool, Vesting, WoodConfig},
     utils::{AccountDeserialize, Discriminator},
 };
 
 /// Errors if:
 /// - Account is not a signer.
-pub fn load_signer<'a, 'info>(info: &'a AccountInfo<'info>) -> Result<(), ProgramError> {
+pub fn load_signer(info: &AccountInfo<'_>) -> Result<(), ProgramError> {
     if !info.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
     }
@@ -22,13 +23,13 @@ pub fn load_signer<'a, 'info>(info: &'a AccountInfo<'info>) -> Result<(), Progra
 
 /// Errors if:
 /// - Owner is not Coal program.
-/// - Address does not match the expected bus address.
 /// - Data is empty.
 /// - Data cannot deserialize into a coal bus account.
 /// - Bus ID does not match the expected ID.
+/// - Address does not match the coal bus PDA for the ID and stored bump.
 /// - Expected to be writable, but is not.
-pub fn load_coal_bus<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_coal_bus(
+    info: &AccountInfo<'_>,
     id: u64,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
@@ -36,21 +37,19 @@ pub fn load_coal_bus<'a, 'info>(
         return Err(ProgramError::InvalidAccountOwner);
     }
 
-    if info.key.ne(&COAL_BUS_ADDRESSES[id as usize]) {
-        return Err(ProgramError::InvalidSeeds);
-    }
-
     if info.data_is_empty() {
         return Err(ProgramError::UninitializedAccount);
     }
 
     let bus_data = info.data.borrow();
-    let bus = Bus::try_from_bytes(&bus_data)?;
+    let bus = Bus::read_from_bytes(&bus_data)?;
 
     if bus.id.ne(&id) {
         return Err(ProgramError::InvalidAccountData);
     }
 
+    load_bus_pda(info, COAL_BUS, &bus)?;
+
     if is_writable && !info.is_writable {
         return Err(ProgramError::InvalidAccountData);
     }
@@ -60,13 +59,13 @@ pub fn load_coal_bus<'a, 'info>(
 
 /// Errors if:
 /// - Owner is not Coal program.
-/// - Address does not match the expected bus address.
 /// - Data is empty.
 /// - Data cannot deserialize into a bus account.
 /// - Bus ID does not match the expected ID.
+/// - Address does not match the wood bus PDA for the ID and stored bump.
 /// - Expected to be writable, but is not.
-pub fn load_wood_bus<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_wood_bus(
+    info: &AccountInfo<'_>,
     id: u64,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
@@ -74,21 +73,19 @@ pub fn load_wood_bus<'a, 'info>(
         return Err(ProgramError::InvalidAccountOwner);
     }
 
-    if info.key.ne(&WOOD_BUS_ADDRESSES[id as usize]) {
-        return Err(ProgramError::InvalidSeeds);
-    }
-
     if info.data_is_empty() {
         return Err(ProgramError::UninitializedAccount);
     }
 
     let bus_data = info.data.borrow();
-    let bus = Bus::try_from_bytes(&bus_data)?;
+    let bus = Bus::read_from_bytes(&bus_data)?;
 
     if bus.id.ne(&id) {
         return Err(ProgramError::InvalidAccountData);
     }
 
+    load_bus_pda(info, WOOD_BUS, &bus)?;
+
     if is_writable && !info.is_writable {
         return Err(ProgramError::InvalidAccountData);
     }
@@ -97,14 +94,13 @@ pub fn load_wood_bus<'a, 'info>(
 }
 
 /// Errors if:
-/// - Owner is not Ore program.
+/// - Owner is not Coal program.
 /// - Data is empty.
 /// - Data cannot deserialize into a coal bus account.
-/// - Bus ID is not in the expected range.
-/// - Address is not in set of valid coal bus address.
+/// - Address does not match the coal bus PDA for the stored ID and bump.
 /// - Expected to be writable, but is not.
-pub fn load_any_coal_bus<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_any_coal_bus(
+    info: &AccountInfo<'_>,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
     if info.owner.ne(&crate::id()) {
@@ -115,14 +111,39 @@ pub fn load_any_coal_bus<'a, 'info>(
         return Err(ProgramError::UninitializedAccount);
     }
 
-    if info.data.borrow()[0].ne(&(Bus::discriminator() as u8)) {
-        return Err(solana_program::program_error::ProgramError::InvalidAccountData);
+    let bus_data = info.data.borrow();
+    let bus = Bus::read_from_bytes(&bus_data)?;
+    load_bus_pda(info, COAL_BUS, &bus)?;
+
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
     }
 
-    if !COAL_BUS_ADDRESSES.contains(info.key) {
-        return Err(ProgramError::InvalidSeeds);
+    Ok(())
+}
+
+/// Errors if:
+/// - Owner is not Coal program.
+/// - Data is empty.
+/// - Data cannot deserialize into a wood bus account.
+/// - Address does not match the wood bus PDA for the stored ID and bump.
+/// - Expected to be writable, but is not.
+pub fn load_any_wood_bus(
+    info: &AccountInfo<'_>,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
     }
 
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let bus_data = info.data.borrow();
+    let bus = Bus::read_from_bytes(&bus_data)?;
+    load_bus_pda(info, WOOD_BUS, &bus)?;
+
     if is_writable && !info.is_writable {
         return Err(ProgramError::InvalidAccountData);
     }
@@ -131,14 +152,13 @@ pub fn load_any_coal_bus<'a, 'info>(
 }
 
 /// Errors if:
-/// - Owner is not COAL program.
+/// - Owner is not Ore program.
 /// - Data is empty.
-/// - Data cannot deserialize into a wood bus account.
-/// - Bus ID is not in the expected range.
-/// - Address is not in set of valid wood bus address.
+/// - Data cannot deserialize into a coal config account.
+/// - Address does not match the config PDA for the stored bump.
 /// - Expected to be writable, but is not.
-pub fn load_any_wood_bus<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_coal_config(
+    info: &AccountInfo<'_>,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
     if info.owner.ne(&crate::id()) {
@@ -149,13 +169,12 @@ pub fn load_any_wood_bus<'a, 'info>(
         return Err(ProgramError::UninitializedAccount);
     }
 
-    if info.data.borrow()[0].ne(&(Bus::discriminator() as u8)) {
+    if info.data.borrow()[0].ne(&Config::discriminator()) {
         return Err(solana_program::program_error::ProgramError::InvalidAccountData);
     }
 
-    if !WOOD_BUS_ADDRESSES.contains(info.key) {
-        return Err(ProgramError::InvalidSeeds);
-    }
+    let config = Config::read_from_bytes(&info.data.borrow())?;
+    load_pda(info, &[COAL_CONFIG], config.bump)?;
 
     if is_writable && !info.is_writable {
         return Err(ProgramError::InvalidAccountData);
@@ -166,30 +185,29 @@ pub fn load_any_wood_bus<'a, 'info>(
 
 /// Errors if:
 /// - Owner is not Ore program.
-/// - Address does not match the expected address.
 /// - Data is empty.
-/// - Data cannot deserialize into a coal config account.
+/// - Data cannot deserialize into a config account.
+/// - Address does not match the config PDA for the stored bump.
 /// - Expected to be writable, but is not.
-pub fn load_coal_config<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_wood_config(
+    info: &AccountInfo<'_>,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
     if info.owner.ne(&crate::id()) {
         return Err(ProgramError::InvalidAccountOwner);
     }
 
-    if info.key.ne(&COAL_CONFIG_ADDRESS) {
-        return Err(ProgramError::InvalidSeeds);
-    }
-
     if info.data_is_empty() {
         return Err(ProgramError::UninitializedAccount);
     }
 
-    if info.data.borrow()[0].ne(&(Config::discriminator() as u8)) {
+    if info.data.borrow()[0].ne(&WoodConfig::discriminator()) {
         return Err(solana_program::program_error::ProgramError::InvalidAccountData);
     }
 
+    let config = WoodConfig::read_from_bytes(&info.data.borrow())?;
+    load_pda(info, &[WOOD_CONFIG], config.bump)?;
+
     if is_writable && !info.is_writable {
         return Err(ProgramError::InvalidAccountData);
     }
@@ -198,20 +216,20 @@ pub fn load_coal_config<'a, 'info>(
 }
 
 /// Errors if:
-/// - Owner is not Ore program.
+/// - Owner is not Coal program.
 /// - Address does not match the expected address.
 /// - Data is empty.
-/// - Data cannot deserialize into a config account.
+/// - Data cannot deserialize into an ingot config account.
 /// - Expected to be writable, but is not.
-pub fn load_wood_config<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_ingot_config(
+    info: &AccountInfo<'_>,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
     if info.owner.ne(&crate::id()) {
         return Err(ProgramError::InvalidAccountOwner);
     }
 
-    if info.key.ne(&WOOD_CONFIG_ADDRESS) {
+    if info.key.ne(&INGOT_CONFIG_ADDRESS) {
         return Err(ProgramError::InvalidSeeds);
     }
 
@@ -219,7 +237,7 @@ pub fn load_wood_config<'a, 'info>(
         return Err(ProgramError::UninitializedAccount);
     }
 
-    if info.data.borrow()[0].ne(&(WoodConfig::discriminator() as u8)) {
+    if info.data.borrow()[0].ne(&IngotConfig::discriminator()) {
         return Err(solana_program::program_error::ProgramError::InvalidAccountData);
     }
 
@@ -235,9 +253,10 @@ pub fn load_wood_config<'a, 'info>(
 /// - Data is empty.
 /// - Data cannot deserialize into a proof account.
 /// - Proof authority does not match the expected address.
+/// - Address does not match the proof PDA of the authority.
 /// - Expected to be writable, but is not.
-pub fn load_coal_proof<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_coal_proof(
+    info: &AccountInfo<'_>,
     authority: &Pubkey,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
@@ -250,12 +269,14 @@ pub fn load_coal_proof<'a, 'info>(
     }
 
     let proof_data = info.data.borrow();
-    let proof = Proof::try_from_bytes(&proof_data)?;
+    let proof = Proof::read_from_bytes(&proof_data)?;
 
-    if proof.authority.ne(&authority) {
+    if proof.authority.ne(authority) {
         return Err(ProgramError::InvalidAccountData);
     }
 
+    load_pda(info, &[COAL_PROOF, authority.as_ref()], proof.bump)?;
+
     if is_writable && !info.is_writable {
         return Err(ProgramError::InvalidAccountData);
     }
@@ -268,9 +289,10 @@ pub fn load_coal_proof<'a, 'info>(
 /// - Data is empty.
 /// - Data cannot deserialize into a proof account.
 /// - Proof authority does not match the expected address.
+/// - Address does not match the proof PDA of the authority.
 /// - Expected to be writable, but is not.
-pub fn load_proof_v2<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_proof_v2(
+    info: &AccountInfo<'_>,
     authority: &Pubkey,
     resource: &Pubkey,
     is_writable: bool,
@@ -284,16 +306,18 @@ pub fn load_proof_v2<'a, 'info>(
     }
 
     let proof_data = info.data.borrow();
-    let proof = ProofV2::try_from_bytes(&proof_data)?;
+    let proof = ProofV2::read_from_bytes(&proof_data)?;
 
-    if proof.resource.ne(&resource) {
+    if proof.resource.ne(resource) {
         return Err(ProgramError::InvalidAccountData);
     }
 
-    if proof.authority.ne(&authority) {
+    if proof.authority.ne(authority) {
         return Err(ProgramError::InvalidAccountData);
     }
 
+    load_pda(info, &[WOOD_PROOF, authority.as_ref()], proof.bump)?;
+
     if is_writable && !info.is_writable {
         return Err(ProgramError::InvalidAccountData);
     }
@@ -307,8 +331,8 @@ pub fn load_proof_v2<'a, 'info>(
 /// - Data cannot deserialize into a proof account.
 /// - Proof miner does not match the expected address.
 /// - Expected to be writable, but is not.
-pub fn load_coal_proof_with_miner<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_coal_proof_with_miner(
+    info: &AccountInfo<'_>,
     miner: &Pubkey,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
@@ -321,9 +345,9 @@ pub fn load_coal_proof_with_miner<'a, 'info>(
     }
 
     let proof_data = info.data.borrow();
-    let proof = Proof::try_from_bytes(&proof_data)?;
+    let proof = Proof::read_from_bytes(&proof_data)?;
 
-    if proof.miner.ne(&miner) {
+    if proof.miner.ne(miner) {
         return Err(ProgramError::InvalidAccountData);
     }
 
@@ -340,8 +364,8 @@ pub fn load_coal_proof_with_miner<'a, 'info>(
 /// - Data cannot deserialize into a proof account.
 /// - Proof miner does not match the expected address.
 /// - Expected to be writable, but is not.
-pub fn load_proof_v2_with_miner<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_proof_v2_with_miner(
+    info: &AccountInfo<'_>,
     miner: &Pubkey,
     resource: &Pubkey,
     is_writable: bool,
@@ -355,13 +379,13 @@ pub fn load_proof_v2_with_miner<'a, 'info>(
     }
 
     let proof_data = info.data.borrow();
-    let proof = ProofV2::try_from_bytes(&proof_data)?;
+    let proof = ProofV2::read_from_bytes(&proof_data)?;
 
-    if proof.resource.ne(&resource) {
+    if proof.resource.ne(resource) {
         return Err(ProgramError::InvalidAccountData);
     }
 
-    if proof.miner.ne(&miner) {
+    if proof.miner.ne(miner) {
         return Err(ProgramError::InvalidAccountData);
     }
 
@@ -377,8 +401,8 @@ pub fn load_proof_v2_with_miner<'a, 'info>(
 /// - Data is empty.
 /// - Data cannot deserialize into a proof account.
 /// - Expected to be writable, but is not.
-pub fn load_any_coal_proof<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_any_coal_proof(
+    info: &AccountInfo<'_>,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
     if info.owner.ne(&crate::id()) {
@@ -389,7 +413,7 @@ pub fn load_any_coal_proof<'a, 'info>(
         return Err(ProgramError::UninitializedAccount);
     }
 
-    if info.data.borrow()[0].ne(&(Proof::discriminator() as u8)) {
+    if info.data.borrow()[0].ne(&Proof::discriminator()) {
         return Err(solana_program::program_error::ProgramError::InvalidAccountData);
     }
 
@@ -405,8 +429,8 @@ pub fn load_any_coal_proof<'a, 'info>(
 /// - Data is empty.
 /// - Data cannot deserialize into a proof account.
 /// - Expected to be writable, but is not.
-pub fn load_any_proof_v2<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_any_proof_v2(
+    info: &AccountInfo<'_>,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
     if info.owner.ne(&crate::id()) {
@@ -417,7 +441,7 @@ pub fn load_any_proof_v2<'a, 'info>(
         return Err(ProgramError::UninitializedAccount);
     }
 
-    if info.data.borrow()[0].ne(&(ProofV2::discriminator() as u8)) {
+    if info.data.borrow()[0].ne(&ProofV2::discriminator()) {
         return Err(solana_program::program_error::ProgramError::InvalidAccountData);
     }
 
@@ -428,14 +452,174 @@ pub fn load_any_proof_v2<'a, 'info>(
     Ok(())
 }
 
+/// Errors if:
+/// - Owner is not Coal program.
+/// - Data is empty.
+/// - Data cannot deserialize into a coal or wood proof account.
+/// - Proof authority does not match the expected address.
+/// - Expected to be writable, but is not.
+pub fn load_any_proof_with_authority(
+    info: &AccountInfo<'_>,
+    authority: &Pubkey,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    if info.data.borrow()[0].eq(&Proof::discriminator()) {
+        return load_coal_proof(info, authority, is_writable);
+    }
+
+    load_proof_v2(info, authority, &WOOD_MINT_ADDRESS, is_writable)
+}
+
+/// Errors if:
+/// - Owner is not Coal program.
+/// - Data is empty.
+/// - Data cannot deserialize into a miner keys account.
+/// - Miner keys proof does not match the expected address.
+/// - Expected to be writable, but is not.
+pub fn load_miner_keys(
+    info: &AccountInfo<'_>,
+    proof: &Pubkey,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let miner_keys_data = info.data.borrow();
+    let miner_keys = MinerKeys::try_from_bytes(&miner_keys_data)?;
+
+    if miner_keys.proof.ne(proof) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Errors if:
+/// - Owner is not Coal program.
+/// - Address does not match the expected address.
+/// - Data is empty.
+/// - Data cannot deserialize into a leaderboard account.
+/// - Expected to be writable, but is not.
+pub fn load_coal_leaderboard(
+    info: &AccountInfo<'_>,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if info.key.ne(&COAL_LEADERBOARD_ADDRESS) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    if info.data.borrow()[0].ne(&Leaderboard::discriminator()) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Errors if:
+/// - Owner is not Coal program.
+/// - Address does not match the expected address.
+/// - Data is empty.
+/// - Data cannot deserialize into a leaderboard account.
+/// - Expected to be writable, but is not.
+pub fn load_wood_leaderboard(
+    info: &AccountInfo<'_>,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if info.key.ne(&WOOD_LEADERBOARD_ADDRESS) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    if info.data.borrow()[0].ne(&Leaderboard::discriminator()) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Errors if:
+/// - Owner is not Coal program.
+/// - Data is empty.
+/// - Data cannot deserialize into a vesting account.
+/// - Address does not match the vesting PDA of the stored proof and bump.
+/// - Vesting authority does not match the expected address.
+/// - Expected to be writable, but is not.
+pub fn load_vesting(
+    info: &AccountInfo<'_>,
+    authority: &Pubkey,
+    is_writable: bool,
+) -> Result<(), ProgramError> {
+    if info.owner.ne(&crate::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    let vesting_data = info.data.borrow();
+    let vesting = Vesting::try_from_bytes(&vesting_data)?;
+    load_pda(info, &[VESTING, vesting.proof.as_ref()], vesting.bump)?;
+
+    if vesting.authority.ne(authority) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if is_writable && !info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
 /// Errors if:
 /// - Owner is not Ore program.
 /// - Address does not match the expected address.
 /// - Data is empty.
 /// - Data cannot deserialize into a treasury account.
 /// - Expected to be writable, but is not.
-pub fn load_treasury<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_treasury(
+    info: &AccountInfo<'_>,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
     if info.owner.ne(&crate::id()) {
@@ -450,7 +634,7 @@ pub fn load_treasury<'a, 'info>(
         return Err(ProgramError::UninitializedAccount);
     }
 
-    if info.data.borrow()[0].ne(&(Treasury::discriminator() as u8)) {
+    if info.data.borrow()[0].ne(&Treasury::discriminator()) {
         return Err(solana_program::program_error::ProgramError::InvalidAccountData);
     }
 
@@ -464,8 +648,8 @@ pub fn load_treasury<'a, 'info>(
 /// Errors if:
 /// - Address does not match the expected treasury tokens address.
 /// - Cannot load as a token account
-pub fn load_coal_treasury_tokens<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_coal_treasury_tokens(
+    info: &AccountInfo<'_>,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
     if info.key.ne(&COAL_TREASURY_TOKENS_ADDRESS) {
@@ -478,8 +662,8 @@ pub fn load_coal_treasury_tokens<'a, 'info>(
 /// Errors if:
 /// - Address does not match the expected treasury tokens address.
 /// - Cannot load as a token account
-pub fn load_wood_treasury_tokens<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_wood_treasury_tokens(
+    info: &AccountInfo<'_>,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
     if info.key.ne(&WOOD_TREASURY_TOKENS_ADDRESS) {
@@ -490,17 +674,17 @@ pub fn load_wood_treasury_tokens<'a, 'info>(
 }
 
 /// Errors if:
-/// - Owner is not SPL token program.
+/// - Owner is not a SPL token program.
 /// - Address does not match the expected mint address.
 /// - Data is empty.
 /// - Data cannot deserialize into a mint account.
 /// - Expected to be writable, but is not.
-pub fn load_mint<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_mint(
+    info: &AccountInfo<'_>,
     address: Pubkey,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
-    if info.owner.ne(&spl_token::id()) {
+    if !is_token_program(info.owner) {
         return Err(ProgramError::InvalidAccountOwner);
     }
 
@@ -512,7 +696,7 @@ pub fn load_mint<'a, 'info>(
         return Err(ProgramError::UninitializedAccount);
     }
 
-    Mint::unpack(&info.data.borrow())?;
+    StateWithExtensions::<Mint>::unpack(&info.data.borrow())?;
 
     if is_writable && !info.is_writable {
         return Err(ProgramError::InvalidAccountData);
@@ -522,19 +706,19 @@ pub fn load_mint<'a, 'info>(
 }
 
 /// Errors if:
-/// - Owner is not SPL token program.
+/// - Owner is not a SPL token program.
 /// - Data is empty.
 /// - Data cannot deserialize into a token account.
 /// - Token account owner does not match the expected owner address.
 /// - Token account mint does not match the expected mint address.
 /// - Expected to be writable, but is not.
-pub fn load_token_account<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_token_account(
+    info: &AccountInfo<'_>,
     owner: Option<&Pubkey>,
     mint: &Pubkey,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
-    if info.owner.ne(&spl_token::id()) {
+    if !is_token_program(info.owner) {
         return Err(ProgramError::InvalidAccountOwner);
     }
 
@@ -543,9 +727,9 @@ pub fn load_token_account<'a, 'info>(
     }
 
     let account_data = info.data.borrow();
-    let account = spl_token::state::Account::unpack(&account_data)?;
+    let account = StateWithExtensions::<Account>::unpack(&account_data)?.base;
 
-    if account.mint.ne(&mint) {
+    if account.mint.ne(mint) {
         msg!("Invalid mint: {:?} == {:?}", account.mint, mint);
         return Err(ProgramError::InvalidAccountData);
     }
@@ -565,11 +749,76 @@ pub fn load_token_account<'a, 'info>(
     Ok(())
 }
 
+/// Errors if:
+/// - Address is not the SPL token program or the SPL token 2022 program.
+/// - Address is not the program which owns the mint.
+/// - Account is not executable.
+///
+/// The COAL, WOOD and INGOT mints are owned by the legacy SPL token program, so only that program
+/// passes today. Token 2022 is accepted for mints created under it, but the existing mints cannot
+/// move to it, and the treasury token accounts are derived for the legacy program.
+pub fn load_token_program(info: &AccountInfo<'_>, mint_info: &AccountInfo<'_>) -> Result<(), ProgramError> {
+    if !is_token_program(info.key) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if info.key.ne(mint_info.owner) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    if !info.executable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+/// Returns true if the address is the SPL token program or the SPL token 2022 program.
+pub fn is_token_program(address: &Pubkey) -> bool {
+    address.eq(&spl_token::id()) || address.eq(&spl_token_2022::id())
+}
+
+/// Errors if:
+/// - Address does not match the PDA created from the provided seeds and stored bump.
+///
+/// This is much cheaper than deriving the canonical bump with `find_program_address`, so it
+/// should be used for accounts which store the bump they were created with.
+pub fn load_pda(
+    info: &AccountInfo<'_>,
+    seeds: &[&[u8]],
+    bump: u64,
+) -> Result<(), ProgramError> {
+    let bump = [u8::try_from(bump).or(Err(ProgramError::InvalidSeeds))?];
+    let pda = Pubkey::create_program_address(&[seeds, &[bump.as_slice()]].concat(), &crate::id())
+        .or(Err(ProgramError::InvalidSeeds))?;
+
+    if info.key.ne(&pda) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    Ok(())
+}
+
+/// Errors if:
+/// - Bus ID is not in the expected range.
+/// - Address does not match the bus PDA created from the seed, stored ID and stored bump.
+fn load_bus_pda(
+    info: &AccountInfo<'_>,
+    seed: &[u8],
+    bus: &Bus,
+) -> Result<(), ProgramError> {
+    if bus.id.ge(&MAX_BUS_COUNT) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    load_pda(info, &[seed, &[bus.id as u8]], bus.bump)
+}
+
 /// Errors if:
 /// - Address does not match PDA derived from provided seeds.
 /// - Cannot load as an uninitialized account.
-pub fn load_uninitialized_pda<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_uninitialized_pda(
+    info: &AccountInfo<'_>,
     seeds: &[&[u8]],
     bump: u8,
     program_id: &Pubkey,
@@ -591,8 +840,8 @@ pub fn load_uninitialized_pda<'a, 'info>(
 /// - Owner is not the system program.
 /// - Data is not empty.
 /// - Account is not writable.
-pub fn load_system_account<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_system_account(
+    info: &AccountInfo<'_>,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
     if info.owner.ne(&system_program::id()) {
@@ -613,8 +862,8 @@ pub fn load_system_account<'a, 'info>(
 /// Errors if:
 /// - Owner is not the sysvar address.
 /// - Account cannot load with the expected address.
-pub fn load_sysvar<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_sysvar(
+    info: &AccountInfo<'_>,
     key: Pubkey,
 ) -> Result<(), ProgramError> {
     if info.owner.ne(&sysvar::id()) {
@@ -627,8 +876,8 @@ pub fn load_sysvar<'a, 'info>(
 /// Errors if:
 /// - Address does not match the expected value.
 /// - Expected to be writable, but is not.
-pub fn load_account<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_account(
+    info: &AccountInfo<'_>,
     key: Pubkey,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
@@ -646,8 +895,8 @@ pub fn load_account<'a, 'info>(
 /// Errors if:
 /// - Address does not match the expected value.
 /// - Account is not executable.
-pub fn load_program<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_program(
+    info: &AccountInfo<'_>,
     key: Pubkey,
 ) -> Result<(), ProgramError> {
     if info.key.ne(&key) {
@@ -663,8 +912,8 @@ pub fn load_program<'a, 'info>(
 
 /// Errors if:
 /// - Account is not writable.
-pub fn load_any<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_any(
+    info: &AccountInfo<'_>,
     is_writable: bool,
 ) -> Result<(), ProgramError> {
     if is_writable && !info.is_writable {
@@ -680,8 +929,8 @@ pub fn load_any<'a, 'info>(
 /// - Attributes plugin is not present.
 /// - Durability attribute is not present.
 /// - Multiplier attribute is not present.
-pub fn load_asset<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+pub fn load_asset(
+    info: &AccountInfo<'_>,
 ) -> Result<(f64, u64), ProgramError> {
     if info.owner.ne(&mpl_core::ID) {
         return Err(ProgramError::InvalidAccountOwner);
@@ -695,8 +944,8 @@ pub fn load_asset<'a, 'info>(
 
     match asset.base.update_authority {
         UpdateAuthority::Collection(address) => {
-            if address.ne(&FORGE_PICKAXE_COLLECTION) {
-                msg!("Invalid collection: {:?} == {:?}", address, FORGE_PICKAXE_COLLECTION);
+            if address.ne(&FORGE_PICKAXE_COLLECTION) && address.ne(&FORGE_COLLECTION_ADDRESS) {
+                msg!("Invalid collection: {:?}", address);
                 return Err(ProgramError::InvalidAccountData);
             }
         }
@@ -716,8 +965,30 @@ pub fn load_asset<'a, 'info>(
     Ok((durability, multiplier))
 }
 
-pub fn load_tool<'a, 'info>(
-    info: &'a AccountInfo<'info>,
+/// Errors if:
+/// - Owner is not the ORE program.
+/// - Data cannot deserialize into an ORE proof account.
+/// - Proof authority does not match the expected address.
+pub fn load_ore_proof(
+    info: &AccountInfo<'_>,
+    authority: &Pubkey,
+) -> Result<(), ProgramError> {
+    if info.owner.ne(&ORE_PROGRAM_ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let proof_data = info.data.borrow();
+    let proof = OreProof::try_from_bytes(&proof_data).ok_or(ProgramError::InvalidAccountData)?;
+
+    if proof.authority.ne(authority) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+pub fn load_tool(
+    info: &AccountInfo<'_>,
     miner: &Pubkey,
     is_writable: bool,
 ) -> Result<(u64, u64), ProgramError> {    
@@ -730,12 +1001,14 @@ pub fn load_tool<'a, 'info>(
     }
     
     let tool_data = info.data.borrow();
-    let tool = Tool::try_from_bytes(&tool_data).unwrap();
+    let tool = Tool::read_from_bytes(&tool_data)?;
 
-    if tool.miner.ne(&miner) {
+    if tool.miner.ne(miner) {
         return Err(ProgramError::InvalidAccountData);
     }
 
+    load_pda(info, &[COAL_MAIN_HAND_TOOL, tool.authority.as_ref()], tool.bump)?;
+
     if is_writable && !info.is_writable {
         return Err(ProgramError::InvalidAccountData);
     }
@@ -752,3 +1025,135 @@ pub fn amount_f64_to_u64(amount: f64) -> u64 {
     (amount * 10f64.powf(TOKEN_DECIMALS as f64)) as u64
 }
 
+
+#[cfg(test)]
+mod tests {
+    use solana_program::{account_info::AccountInfo, pubkey::Pubkey};
+
+    use super::*;
+    use crate::utils::AccountVersion;
+
+    fn with_account_info<F: FnOnce(&AccountInfo)>(key: Pubkey, f: F) {
+        let owner = crate::id();
+        let mut lamports = 0;
+        let mut data = [];
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        f(&info);
+    }
+
+    #[test]
+    fn test_load_token_program() {
+        let mut lamports = (0, 0);
+        let (mut program_data, mut mint_data) = ([], []);
+        let program = spl_token::id();
+        let mint = COAL_MINT_ADDRESS;
+        let program_info =
+            AccountInfo::new(&program, false, false, &mut lamports.0, &mut program_data, &program, true, 0);
+        let mint_info = AccountInfo::new(&mint, false, false, &mut lamports.1, &mut mint_data, &program, false, 0);
+        assert!(load_token_program(&program_info, &mint_info).is_ok());
+    }
+
+    #[test]
+    fn test_load_token_program_rejects_other_mint_owner() {
+        let mut lamports = (0, 0);
+        let (mut program_data, mut mint_data) = ([], []);
+        let program = spl_token_2022::id();
+        let mint_owner = spl_token::id();
+        let mint = COAL_MINT_ADDRESS;
+        let program_info =
+            AccountInfo::new(&program, false, false, &mut lamports.0, &mut program_data, &program, true, 0);
+        let mint_info =
+            AccountInfo::new(&mint, false, false, &mut lamports.1, &mut mint_data, &mint_owner, false, 0);
+        assert_eq!(
+            load_token_program(&program_info, &mint_info),
+            Err(ProgramError::IncorrectProgramId)
+        );
+    }
+
+    #[test]
+    fn test_load_token_program_rejects_other_program() {
+        let mut lamports = (0, 0);
+        let (mut program_data, mut mint_data) = ([], []);
+        let program = system_program::id();
+        let mint = COAL_MINT_ADDRESS;
+        let program_info =
+            AccountInfo::new(&program, false, false, &mut lamports.0, &mut program_data, &program, true, 0);
+        let mint_info = AccountInfo::new(&mint, false, false, &mut lamports.1, &mut mint_data, &program, false, 0);
+        assert_eq!(
+            load_token_program(&program_info, &mint_info),
+            Err(ProgramError::IncorrectProgramId)
+        );
+    }
+
+    #[test]
+    fn test_load_pda_with_stored_bump() {
+        let authority = Pubkey::new_unique();
+        let (address, bump) =
+            Pubkey::find_program_address(&[COAL_PROOF, authority.as_ref()], &crate::id());
+        with_account_info(address, |info| {
+            assert!(load_pda(info, &[COAL_PROOF, authority.as_ref()], bump as u64).is_ok());
+            assert!(load_pda(info, &[WOOD_PROOF, authority.as_ref()], bump as u64).is_err());
+        });
+    }
+
+    #[test]
+    fn test_load_pda_rejects_wrong_bump() {
+        let authority = Pubkey::new_unique();
+        let (address, bump) =
+            Pubkey::find_program_address(&[COAL_MAIN_HAND_TOOL, authority.as_ref()], &crate::id());
+        with_account_info(address, |info| {
+            let seeds: &[&[u8]] = &[COAL_MAIN_HAND_TOOL, authority.as_ref()];
+            assert!(load_pda(info, seeds, bump.wrapping_sub(1) as u64).is_err());
+            assert!(load_pda(info, seeds, u8::MAX as u64 + 1 + bump as u64).is_err());
+        });
+    }
+
+    #[test]
+    fn test_load_coal_proof_checks_address() {
+        let authority = Pubkey::new_unique();
+        let (address, bump) =
+            Pubkey::find_program_address(&[COAL_PROOF, authority.as_ref()], &crate::id());
+        let proof = Proof {
+            authority,
+            bump: bump as u64,
+            ..bytemuck::Zeroable::zeroed()
+        };
+        let mut data =
+            [[Proof::discriminator(), Proof::version(), 0, 0, 0, 0, 0, 0].as_slice(), proof.to_bytes()].concat();
+        let owner = crate::id();
+        let mut lamports = 0;
+        let info = AccountInfo::new(&address, false, true, &mut lamports, &mut data, &owner, false, 0);
+        assert!(load_coal_proof(&info, &authority, true).is_ok());
+
+        // A proof with the right authority at any other address is rejected.
+        let other = Pubkey::new_unique();
+        let info = AccountInfo { key: &other, ..info };
+        assert_eq!(load_coal_proof(&info, &authority, true), Err(ProgramError::InvalidSeeds));
+    }
+
+    #[test]
+    fn test_load_bus_beyond_initial_count() {
+        let id = MAX_BUS_COUNT - 1;
+        let (address, bump) = Pubkey::find_program_address(&[COAL_BUS, &[id as u8]], &crate::id());
+        assert_eq!(coal_bus_address(id), address);
+
+        let bus = Bus {
+            id,
+            rewards: 0,
+            theoretical_rewards: 0,
+            top_balance: 0,
+            bump: bump as u64,
+            liabilities: 0,
+            jackpot: 0,
+        };
+        let mut data = [[Bus::discriminator(), Bus::version(), 0, 0, 0, 0, 0, 0].as_slice(), bus.to_bytes()].concat();
+        let owner = crate::id();
+        let mut lamports = 0;
+        let info = AccountInfo::new(&address, false, true, &mut lamports, &mut data, &owner, false, 0);
+        assert!(load_coal_bus(&info, id, true).is_ok());
+        assert!(load_any_coal_bus(&info, true).is_ok());
+        assert!(load_coal_bus(&info, 0, true).is_err());
+        assert!(load_wood_bus(&info, id, true).is_err());
+        assert!(load_any_wood_bus(&info, true).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+//! ORE interop.
+//!
+//! Miners who stake ORE earn a bounded bonus on their COAL rewards. The ORE proof layout is
+//! mirrored here, so the program can read ORE proofs without depending on the ORE crate.
+
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+
+use crate::consts::*;
+
+/// The account discriminator of ORE proof accounts.
+pub const ORE_PROOF_DISCRIMINATOR: u8 = 102;
+
+/// An ORE proof account, as laid out by the ORE program after its 8 byte account header.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct OreProof {
+    /// The signer authorized to use this proof.
+    pub authority: Pubkey,
+
+    /// The quantity of tokens this miner has staked or earned.
+    pub balance: u64,
+
+    /// The current mining challenge.
+    pub challenge: [u8; 32],
+
+    /// The last hash the miner provided.
+    pub last_hash: [u8; 32],
+
+    /// The last time this account provided a hash.
+    pub last_hash_at: i64,
+
+    /// The last time stake was deposited into this account.
+    pub last_stake_at: i64,
+
+    /// The keypair which has permission to submit hashes for mining.
+    pub miner: Pubkey,
+
+    /// The total lifetime hashes provided by this miner.
+    pub total_hashes: u64,
+
+    /// The total lifetime rewards distributed to this miner.
+    pub total_rewards: u64,
+}
+
+impl OreProof {
+    /// Returns the proof if the data is an ORE proof account.
+    pub fn try_from_bytes(data: &[u8]) -> Option<&Self> {
+        if data.first()?.ne(&ORE_PROOF_DISCRIMINATOR) {
+            return None;
+        }
+        let size = 8 + std::mem::size_of::<Self>();
+        bytemuck::try_from_bytes::<Self>(data.get(8..size)?).ok()
+    }
+}
+
+/// Returns the bonus COAL reward earned for the given ORE stake.
+///
+/// The bonus scales linearly with the stake up to `ORE_BOOST_MAX_STAKE`, at which point it is
+/// capped at `ORE_BOOST_MAX_BPS` of the reward.
+pub fn ore_boost(reward: u64, ore_balance: u64) -> u64 {
+    (reward as u128)
+        .saturating_mul(ORE_BOOST_MAX_BPS as u128)
+        .saturating_mul(ore_balance.min(ORE_BOOST_MAX_STAKE) as u128)
+        .saturating_div(ORE_BOOST_MAX_STAKE as u128)
+        .saturating_div(BASIS_POINTS as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ore_boost_scales_with_stake() {
+        let reward = ONE_COAL;
+        assert_eq!(ore_boost(reward, 0), 0);
+        assert_eq!(
+            ore_boost(reward, ORE_BOOST_MAX_STAKE / 2),
+            reward * ORE_BOOST_MAX_BPS / BASIS_POINTS / 2
+        );
+    }
+
+    #[test]
+    fn test_ore_boost_is_bounded() {
+        let reward = ONE_COAL;
+        let max = reward * ORE_BOOST_MAX_BPS / BASIS_POINTS;
+        assert_eq!(ore_boost(reward, ORE_BOOST_MAX_STAKE), max);
+        assert_eq!(ore_boost(reward, u64::MAX), max);
+    }
+
+    #[test]
+    fn test_ore_proof_from_bytes() {
+        let proof = OreProof {
+            authority: Pubkey::new_unique(),
+            balance: ONE_ORE,
+            ..Zeroable::zeroed()
+        };
+        let mut data = vec![0u8; 8];
+        data.extend_from_slice(bytemuck::bytes_of(&proof));
+        assert_eq!(OreProof::try_from_bytes(&data), None);
+
+        data[0] = ORE_PROOF_DISCRIMINATOR;
+        assert_eq!(OreProof::try_from_bytes(&data), Some(&proof));
+        assert_eq!(OreProof::try_from_bytes(&data[..data.len() - 1]), None);
+    }
+}
@@ -0,0 +1,224 @@
+//! Relayed mining with ed25519-signed solutions.
+//!
+//! A miner without SOL for fees can sign its solution offchain and hand it to a relayer. The relayer
+//! submits the mine transaction along with an instruction to the native Ed25519 program, which
+//! verifies the miner's signature over a relay message. Mine and chop parse the instructions sysvar
+//! to find that signature, then pay the relayer the fee the miner signed out of the reward. The
+//! message names the relayer's proof, so a copied signature cannot redirect the fee.
+
+use bytemuck::{Pod, Zeroable};
+use drillx::Solution;
+use solana_program::{
+    ed25519_program, instruction::Instruction, program_error::ProgramError, pubkey::Pubkey,
+};
+
+use crate::{
+    auth::{instructions, read_u16},
+    error::CoalError,
+};
+
+/// The size of the signature offsets of each signature in an Ed25519 program instruction.
+const SIGNATURE_OFFSETS_SIZE: usize = 14;
+
+/// The start of the signature offsets in an Ed25519 program instruction.
+const SIGNATURE_OFFSETS_START: usize = 2;
+
+/// The instruction index which refers to the Ed25519 program instruction itself.
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// The message a miner signs to authorize a relayer to submit its solution.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct RelayMessage {
+    /// The proof account the solution is submitted for.
+    pub proof: Pubkey,
+
+    /// The proof account credited with the relayer fee.
+    pub relayer_proof: Pubkey,
+
+    /// The challenge the solution solves.
+    pub challenge: [u8; 32],
+
+    /// The digest of the solution.
+    pub digest: [u8; 16],
+
+    /// The nonce of the solution.
+    pub nonce: [u8; 8],
+
+    /// The share of the reward paid to the relayer, in basis points.
+    pub fee_bps: [u8; 8],
+}
+
+impl RelayMessage {
+    pub fn new(
+        proof: Pubkey,
+        relayer_proof: Pubkey,
+        challenge: [u8; 32],
+        solution: Solution,
+        fee_bps: u64,
+    ) -> Self {
+        Self {
+            proof,
+            relayer_proof,
+            challenge,
+            digest: solution.d,
+            nonce: solution.n,
+            fee_bps: fee_bps.to_le_bytes(),
+        }
+    }
+
+    pub fn to_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+}
+
+/// Builds an Ed25519 program instruction which verifies a signature over a relay message.
+pub fn relay_signature(miner: Pubkey, signature: [u8; 64], message: &RelayMessage) -> Instruction {
+    let public_key_offset = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SIZE;
+    let signature_offset = public_key_offset + 32;
+    let message_offset = signature_offset + 64;
+    let offsets = [
+        signature_offset as u16,
+        CURRENT_INSTRUCTION,
+        public_key_offset as u16,
+        CURRENT_INSTRUCTION,
+        message_offset as u16,
+        message.to_bytes().len() as u16,
+        CURRENT_INSTRUCTION,
+    ];
+    Instruction {
+        program_id: ed25519_program::id(),
+        accounts: vec![],
+        data: [
+            vec![1, 0],
+            offsets.iter().flat_map(|offset| offset.to_le_bytes()).collect(),
+            miner.to_bytes().to_vec(),
+            signature.to_vec(),
+            message.to_bytes().to_vec(),
+        ]
+        .concat(),
+    }
+}
+
+/// Errors if the transaction does not verify a signature by the miner over the relay message.
+pub fn verify_relay_signature(
+    instructions_data: &[u8],
+    miner: &Pubkey,
+    message: &RelayMessage,
+) -> Result<(), ProgramError> {
+    let signed = instructions(instructions_data)
+        .filter(|(program_id, _)| program_id.eq(&ed25519_program::id().as_ref()))
+        .any(|(_, data)| is_signed_by(data, miner, message.to_bytes()));
+    if !signed {
+        return Err(CoalError::RelaySignatureInvalid.into());
+    }
+    Ok(())
+}
+
+/// Returns true if the Ed25519 program instruction data verifies a signature by the signer over the
+/// message.
+///
+/// The Ed25519 program fails the transaction if any signature is invalid, so only the public key and
+/// message need to be matched. Offsets into other instructions are skipped, so the signed data must be
+/// carried by the Ed25519 program instruction itself.
+fn is_signed_by(data: &[u8], signer: &Pubkey, message: &[u8]) -> bool {
+    let Some(num_signatures) = data.first() else {
+        return false;
+    };
+    (0..*num_signatures as usize).any(|i| {
+        let start = SIGNATURE_OFFSETS_START + i * SIGNATURE_OFFSETS_SIZE;
+        let read = |field: usize| read_u16(data, start + field * 2);
+        let (
+            Some(CURRENT_INSTRUCTION),
+            Some(public_key_offset),
+            Some(CURRENT_INSTRUCTION),
+            Some(message_offset),
+            Some(message_size),
+            Some(CURRENT_INSTRUCTION),
+        ) = (read(1), read(2), read(3), read(4), read(5), read(6))
+        else {
+            return false;
+        };
+        let public_key_offset = public_key_offset as usize;
+        let message_offset = message_offset as usize;
+        let public_key = data.get(public_key_offset..public_key_offset + 32);
+        let signed_message = data.get(message_offset..message_offset + message_size as usize);
+        public_key.eq(&Some(signer.as_ref())) && signed_message.eq(&Some(message))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::sysvar_data;
+
+    fn message(fee_bps: u64) -> RelayMessage {
+        let solution = Solution::new([1; 16], [2; 8]);
+        RelayMessage::new(Pubkey::new_unique(), Pubkey::new_unique(), [3; 32], solution, fee_bps)
+    }
+
+    #[test]
+    fn test_verify_relay_signature() {
+        let miner = Pubkey::new_unique();
+        let message = message(500);
+        let data = sysvar_data(&[relay_signature(miner, [4; 64], &message)]);
+        assert!(verify_relay_signature(&data, &miner, &message).is_ok());
+    }
+
+    #[test]
+    fn test_verify_relay_signature_rejects_other_signer() {
+        let message = message(500);
+        let data = sysvar_data(&[relay_signature(Pubkey::new_unique(), [4; 64], &message)]);
+        assert!(verify_relay_signature(&data, &Pubkey::new_unique(), &message).is_err());
+    }
+
+    #[test]
+    fn test_verify_relay_signature_rejects_other_fee() {
+        let miner = Pubkey::new_unique();
+        let signed = message(500);
+        let mut submitted = signed;
+        submitted.fee_bps = 5_000u64.to_le_bytes();
+        let data = sysvar_data(&[relay_signature(miner, [4; 64], &signed)]);
+        assert!(verify_relay_signature(&data, &miner, &submitted).is_err());
+    }
+
+    #[test]
+    fn test_verify_relay_signature_rejects_other_relayer() {
+        let miner = Pubkey::new_unique();
+        let signed = message(500);
+        let mut submitted = signed;
+        submitted.relayer_proof = Pubkey::new_unique();
+        let data = sysvar_data(&[relay_signature(miner, [4; 64], &signed)]);
+        assert!(verify_relay_signature(&data, &miner, &submitted).is_err());
+    }
+
+    #[test]
+    fn test_verify_relay_signature_rejects_other_program() {
+        let miner = Pubkey::new_unique();
+        let message = message(500);
+        let mut instruction = relay_signature(miner, [4; 64], &message);
+        instruction.program_id = Pubkey::new_unique();
+        let data = sysvar_data(&[instruction]);
+        assert!(verify_relay_signature(&data, &miner, &message).is_err());
+    }
+
+    #[test]
+    fn test_verify_relay_signature_rejects_external_offsets() {
+        let miner = Pubkey::new_unique();
+        let message = message(500);
+        let mut instruction = relay_signature(miner, [4; 64], &message);
+        instruction.data[SIGNATURE_OFFSETS_START + 12..SIGNATURE_OFFSETS_START + 14]
+            .copy_from_slice(&0u16.to_le_bytes());
+        let data = sysvar_data(&[instruction]);
+        assert!(verify_relay_signature(&data, &miner, &message).is_err());
+    }
+
+    #[test]
+    fn test_is_signed_by_malformed_data() {
+        let miner = Pubkey::new_unique();
+        let message = message(500);
+        assert!(!is_signed_by(&[], &miner, message.to_bytes()));
+        assert!(!is_signed_by(&[1, 0, 0xff], &miner, message.to_bytes()));
+        assert!(!is_signed_by(&[8, 0], &miner, message.to_bytes()));
+    }
+}
@@ -4,8 +4,8 @@ use crate::utils::{impl_account_from_bytes, impl_to_bytes, Discriminator};
 
 use super::AccountDiscriminator;
 
-/// Bus accounts are responsible for distributing mining rewards. There are 8 busses total
-/// to minimize write-lock contention and allow Solana to process mine instructions in parallel.
+/// Bus accounts are responsible for distributing mining rewards. Each resource has a configurable
+/// number of busses to minimize write-lock contention and allow Solana to process mine instructions in parallel.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
 pub struct Bus {
@@ -21,6 +21,17 @@ pub struct Bus {
 
     /// The largest known stake balance seen by the bus this epoch.
     pub top_balance: u64,
+
+    /// The bump of the bus PDA.
+    pub bump: u64,
+
+    /// The rewards, jackpots and referral bonuses this bus credited to proofs since the last reset.
+    /// These are owed to miners, and are moved into the config's outstanding liabilities at reset.
+    pub liabilities: u64,
+
+    /// This bus's share of the jackpot. The jackpot is split evenly across the busses at reset, so
+    /// mining does not need to write the config.
+    pub jackpot: u64,
 }
 
 impl Discriminator for Bus {
@@ -30,4 +41,4 @@ impl Discriminator for Bus {
 }
 
 impl_to_bytes!(Bus);
-impl_account_from_bytes!(Bus);
+impl_account_from_bytes!(Bus, 1);
@@ -19,6 +19,51 @@ pub struct Config {
 
     /// The largest known stake balance on the network from the last epoch.
     pub top_balance: u64,
+
+    /// The bonus paid to referrers, in basis points of the referred miner's reward.
+    pub referral_bonus_bps: u64,
+
+    /// The number of epochs a referral remains valid after the referred proof is opened.
+    pub referral_epochs: u64,
+
+    /// The quantity of tokens in the treasury owed to miners, as of the last reset, adjusted for
+    /// stakes and claims since. Mining credits are held on the busses until the next reset, so this
+    /// falls below zero while the rewards of the current epoch are claimed.
+    pub outstanding_liabilities: i64,
+
+    /// The bump of the config PDA.
+    pub bump: u64,
+
+    /// The number of bus accounts distributing rewards.
+    pub bus_count: u64,
+
+    /// The time without hashes after which an empty proof can be reaped by anyone. Zero disables reaping.
+    pub reap_after: i64,
+
+    /// The share of the rent of a reaped proof paid to the reaper, in basis points.
+    pub reaper_bonus_bps: u64,
+
+    /// The quantity of tokens in the treasury held for the jackpot, not yet split across the busses.
+    pub jackpot: u64,
+
+    /// The share of each epoch's emissions added to the jackpot, in basis points.
+    pub jackpot_bps: u64,
+
+    /// The difficulty above the minimum a hash must reach to win the jackpot. Zero disables the jackpot.
+    pub jackpot_difficulty: u64,
+
+    /// The last time the remaining rewards were rebalanced across the busses.
+    pub last_rebalance_at: i64,
+
+    /// The quantity of rewards the busses were funded with at the last reset. Zero if the busses were
+    /// funded before this was tracked.
+    pub total_epoch_rewards: u64,
+
+    /// The duration of an epoch, in seconds.
+    pub epoch_duration: i64,
+
+    /// The number of consecutive resets which happened more than half an epoch late.
+    pub late_resets: u64,
 }
 
 impl Discriminator for Config {
@@ -45,6 +90,47 @@ pub struct WoodConfig {
 
     /// The current epoch emission rate for the program.
     pub total_epoch_rewards: u64,
+
+    /// The bonus paid to referrers, in basis points of the referred miner's reward.
+    pub referral_bonus_bps: u64,
+
+    /// The number of epochs a referral remains valid after the referred proof is opened.
+    pub referral_epochs: u64,
+
+    /// The quantity of tokens in the treasury owed to miners, as of the last reset, adjusted for
+    /// stakes and claims since. Mining credits are held on the busses until the next reset, so this
+    /// falls below zero while the rewards of the current epoch are claimed.
+    pub outstanding_liabilities: i64,
+
+    /// The bump of the config PDA.
+    pub bump: u64,
+
+    /// The number of bus accounts distributing rewards.
+    pub bus_count: u64,
+
+    /// The time without hashes after which an empty proof can be reaped by anyone. Zero disables reaping.
+    pub reap_after: i64,
+
+    /// The share of the rent of a reaped proof paid to the reaper, in basis points.
+    pub reaper_bonus_bps: u64,
+
+    /// The quantity of tokens in the treasury held for the jackpot, not yet split across the busses.
+    pub jackpot: u64,
+
+    /// The share of each epoch's emissions added to the jackpot, in basis points.
+    pub jackpot_bps: u64,
+
+    /// The difficulty above the minimum a hash must reach to win the jackpot. Zero disables the jackpot.
+    pub jackpot_difficulty: u64,
+
+    /// The last time the remaining rewards were rebalanced across the busses.
+    pub last_rebalance_at: i64,
+
+    /// The duration of an epoch, in seconds.
+    pub epoch_duration: i64,
+
+    /// The number of consecutive resets which happened more than half an epoch late.
+    pub late_resets: u64,
 }
 
 impl Discriminator for WoodConfig {
@@ -53,7 +139,35 @@ impl Discriminator for WoodConfig {
     }
 }
 
+/// Ingot config is a singleton account which manages the smelting recipe and output limits.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct IngotConfig {
+    /// The quantity of COAL burned per INGOT smelted.
+    pub coal_cost: u64,
+
+    /// The quantity of WOOD burned per INGOT smelted.
+    pub wood_cost: u64,
+
+    /// The maximum quantity of INGOT that can be smelted per smelting epoch.
+    pub epoch_output_cap: u64,
+
+    /// The quantity of INGOT smelted in the current smelting epoch.
+    pub epoch_output: u64,
+
+    /// The timestamp the current smelting epoch started at.
+    pub epoch_start_at: i64,
+}
+
+impl Discriminator for IngotConfig {
+    fn discriminator() -> u8 {
+        AccountDiscriminator::IngotConfig.into()
+    }
+}
+
 impl_to_bytes!(Config);
-impl_account_from_bytes!(Config);
+impl_account_from_bytes!(Config, 8);
 impl_to_bytes!(WoodConfig);
-impl_account_from_bytes!(WoodConfig);
+impl_account_from_bytes!(WoodConfig, 8);
+impl_to_bytes!(IngotConfig);
+impl_account_from_bytes!(IngotConfig);
@@ -0,0 +1,165 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    consts::LEADERBOARD_SIZE,
+    utils::{impl_account_from_bytes, impl_to_bytes, Discriminator},
+};
+
+use super::AccountDiscriminator;
+
+/// Leaderboard accounts (1 per resource) rank the proofs with the most rewards and the best hash
+/// difficulty in the current and previous epoch, so standings can be read with a single fetch.
+///
+/// Every mine instruction of a resource writes its leaderboard, so mine transactions of a resource
+/// are not processed in parallel even across busses. Reset rolls the standings at each new epoch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct Leaderboard {
+    /// The mint of the resource this leaderboard ranks miners of.
+    pub resource: Pubkey,
+
+    /// The bump of the leaderboard PDA.
+    pub bump: u64,
+
+    /// The standings of the current epoch.
+    pub current: Standings,
+
+    /// The final standings of the previous epoch.
+    pub previous: Standings,
+}
+
+/// The standings of a single epoch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct Standings {
+    /// The reset timestamp of the epoch these standings are for.
+    pub epoch_reset_at: i64,
+
+    /// The proofs with the most rewards in the epoch, highest first. Unused slots are zeroed.
+    pub rewards: [LeaderboardEntry; LEADERBOARD_SIZE],
+
+    /// The proofs with the best hash difficulty in the epoch, highest first. Unused slots are zeroed.
+    pub difficulty: [LeaderboardEntry; LEADERBOARD_SIZE],
+}
+
+/// A ranked proof.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct LeaderboardEntry {
+    /// The ranked proof account.
+    pub proof: Pubkey,
+
+    /// The score the proof is ranked by.
+    pub score: u64,
+}
+
+impl Leaderboard {
+    /// Moves the current standings into the previous epoch if they belong to an epoch other than
+    /// the one which started at the given time.
+    pub fn roll(&mut self, epoch_reset_at: i64) {
+        if self.current.epoch_reset_at.eq(&epoch_reset_at) {
+            return;
+        }
+        self.previous = self.current;
+        self.current = Standings {
+            epoch_reset_at,
+            ..Standings::zeroed()
+        };
+    }
+
+    /// Ranks a proof by its rewards in the current epoch and the difficulty of its latest hash.
+    pub fn record(&mut self, proof: Pubkey, epoch_rewards: u64, difficulty: u64) {
+        rank(&mut self.current.rewards, proof, epoch_rewards);
+        rank(&mut self.current.difficulty, proof, difficulty);
+    }
+}
+
+/// Inserts a proof into the sorted entries, unless it already ranks with an equal or higher score.
+/// The lowest entry drops off when the entries are full.
+fn rank(entries: &mut [LeaderboardEntry], proof: Pubkey, score: u64) {
+    // Remove the existing entry of the proof.
+    if let Some(i) = entries.iter().position(|entry| entry.proof.eq(&proof)) {
+        if entries[i].score.ge(&score) {
+            return;
+        }
+        entries[i..].rotate_left(1);
+        entries[entries.len() - 1] = LeaderboardEntry::zeroed();
+    }
+
+    // Insert the proof ahead of the first entry with a lower score.
+    let Some(i) = entries.iter().position(|entry| score.gt(&entry.score)) else {
+        return;
+    };
+    entries[i..].rotate_right(1);
+    entries[i] = LeaderboardEntry { proof, score };
+}
+
+impl Discriminator for Leaderboard {
+    fn discriminator() -> u8 {
+        AccountDiscriminator::Leaderboard.into()
+    }
+}
+
+impl_to_bytes!(Leaderboard);
+impl_account_from_bytes!(Leaderboard);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scores(entries: &[LeaderboardEntry]) -> Vec<u64> {
+        entries.iter().map(|entry| entry.score).collect()
+    }
+
+    #[test]
+    fn test_rank_sorted() {
+        let mut entries = [LeaderboardEntry::zeroed(); 3];
+        rank(&mut entries, Pubkey::new_unique(), 5);
+        rank(&mut entries, Pubkey::new_unique(), 9);
+        rank(&mut entries, Pubkey::new_unique(), 7);
+        assert_eq!(scores(&entries), vec![9, 7, 5]);
+        rank(&mut entries, Pubkey::new_unique(), 6);
+        assert_eq!(scores(&entries), vec![9, 7, 6]);
+        rank(&mut entries, Pubkey::new_unique(), 1);
+        assert_eq!(scores(&entries), vec![9, 7, 6]);
+    }
+
+    #[test]
+    fn test_rank_moves_existing_proof() {
+        let mut entries = [LeaderboardEntry::zeroed(); 3];
+        let proof = Pubkey::new_unique();
+        rank(&mut entries, Pubkey::new_unique(), 9);
+        rank(&mut entries, Pubkey::new_unique(), 7);
+        rank(&mut entries, proof, 5);
+        rank(&mut entries, proof, 8);
+        assert_eq!(scores(&entries), vec![9, 8, 7]);
+        assert_eq!(entries[1].proof, proof);
+        rank(&mut entries, proof, 4);
+        assert_eq!(scores(&entries), vec![9, 8, 7]);
+        assert_eq!(entries.iter().filter(|entry| entry.proof.eq(&proof)).count(), 1);
+    }
+
+    #[test]
+    fn test_rank_ignores_zero_score() {
+        let mut entries = [LeaderboardEntry::zeroed(); 3];
+        rank(&mut entries, Pubkey::new_unique(), 0);
+        assert_eq!(entries, [LeaderboardEntry::zeroed(); 3]);
+    }
+
+    #[test]
+    fn test_roll() {
+        let mut leaderboard = Leaderboard::zeroed();
+        let proof = Pubkey::new_unique();
+        leaderboard.roll(100);
+        leaderboard.record(proof, 50, 20);
+        leaderboard.roll(100);
+        assert_eq!(leaderboard.current.rewards[0].proof, proof);
+        leaderboard.roll(160);
+        assert_eq!(leaderboard.current.epoch_reset_at, 160);
+        assert_eq!(leaderboard.current.rewards[0], LeaderboardEntry::zeroed());
+        assert_eq!(leaderboard.previous.epoch_reset_at, 100);
+        assert_eq!(leaderboard.previous.rewards[0].score, 50);
+        assert_eq!(leaderboard.previous.difficulty[0].score, 20);
+    }
+}
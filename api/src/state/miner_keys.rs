@@ -0,0 +1,50 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    consts::MAX_MINER_KEYS,
+    utils::{impl_account_from_bytes, impl_to_bytes, Discriminator},
+};
+
+use super::AccountDiscriminator;
+
+/// Miner keys extend a proof account with additional keypairs authorized to submit hashes.
+/// Each key can expire and be limited to a number of hashes per epoch.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct MinerKeys {
+    /// The proof account these keys are authorized to mine for.
+    pub proof: Pubkey,
+
+    /// The authorized keys. Unused slots are zeroed.
+    pub keys: [MinerKey; MAX_MINER_KEYS],
+}
+
+/// A keypair authorized to submit hashes for a proof.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct MinerKey {
+    /// The keypair which has permission to submit hashes for mining.
+    pub miner: Pubkey,
+
+    /// The time after which the key can no longer submit hashes.
+    pub expires_at: i64,
+
+    /// The maximum number of hashes the key can submit per epoch. Zero is unlimited.
+    pub hash_quota: u64,
+
+    /// The number of hashes the key has submitted in the current epoch.
+    pub epoch_hashes: u64,
+
+    /// The reset timestamp of the epoch in which the hashes were counted.
+    pub epoch_reset_at: i64,
+}
+
+impl Discriminator for MinerKeys {
+    fn discriminator() -> u8 {
+        AccountDiscriminator::MinerKeys.into()
+    }
+}
+
+impl_to_bytes!(MinerKeys);
+impl_account_from_bytes!(MinerKeys);
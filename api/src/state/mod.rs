@@ -1,16 +1,22 @@
 mod bus;
 mod config;
+mod leaderboard;
+mod miner_keys;
 mod proof;
 mod proof_v2;
 mod treasury;
 mod tool;
+mod vesting;
 
 pub use bus::*;
 pub use config::*;
+pub use leaderboard::*;
+pub use miner_keys::*;
 pub use proof::*;
 pub use proof_v2::*;
 pub use treasury::*;
 pub use tool::*;
+pub use vesting::*;
 
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
@@ -24,4 +30,8 @@ pub enum AccountDiscriminator {
     ProofV2 = 104,
     WoodConfig = 106,
     Tool = 107,
+    MinerKeys = 108,
+    IngotConfig = 109,
+    Vesting = 110,
+    Leaderboard = 111,
 }
@@ -36,6 +36,34 @@ pub struct Proof {
 
     /// The total lifetime rewards distributed to this miner.
     pub total_rewards: u64,
+
+    /// The proof account which referred this miner.
+    pub referrer: Pubkey,
+
+    /// The time this miner was referred.
+    pub referred_at: i64,
+
+    /// The total lifetime rewards distributed to this miner for referrals.
+    pub total_referral_rewards: u64,
+
+    /// The authority proposed to take ownership of this proof.
+    pub pending_authority: Pubkey,
+
+    /// The bump of the proof PDA.
+    pub bump: u64,
+
+    /// The duration claimed rewards vest over. Zero pays out claims at once.
+    pub vesting_duration: i64,
+
+    /// The rewards distributed to this miner in the epoch which started at `epoch_reset_at`.
+    pub epoch_rewards: u64,
+
+    /// The reset timestamp of the epoch in which the rewards were counted.
+    pub epoch_reset_at: i64,
+
+    /// The time the referrer stops earning a bonus on this miner's rewards. Fixed when the proof
+    /// is opened, so later changes to the referral duration or epoch length do not affect it.
+    pub referral_expires_at: i64,
 }
 
 impl Discriminator for Proof {
@@ -45,4 +73,4 @@ impl Discriminator for Proof {
 }
 
 impl_to_bytes!(Proof);
-impl_account_from_bytes!(Proof);
+impl_account_from_bytes!(Proof, 6);
@@ -43,6 +43,34 @@ pub struct ProofV2 {
 
     /// The tool equipped by the miner.
     pub equipped_tool: Pubkey,
+
+    /// The proof account which referred this miner.
+    pub referrer: Pubkey,
+
+    /// The time this miner was referred.
+    pub referred_at: i64,
+
+    /// The total lifetime rewards distributed to this miner for referrals.
+    pub total_referral_rewards: u64,
+
+    /// The authority proposed to take ownership of this proof.
+    pub pending_authority: Pubkey,
+
+    /// The bump of the proof PDA.
+    pub bump: u64,
+
+    /// The duration claimed rewards vest over. Zero pays out claims at once.
+    pub vesting_duration: i64,
+
+    /// The rewards distributed to this miner in the epoch which started at `epoch_reset_at`.
+    pub epoch_rewards: u64,
+
+    /// The reset timestamp of the epoch in which the rewards were counted.
+    pub epoch_reset_at: i64,
+
+    /// The time the referrer stops earning a bonus on this miner's rewards. Fixed when the proof
+    /// is opened, so later changes to the referral duration or epoch length do not affect it.
+    pub referral_expires_at: i64,
 }
 
 impl Discriminator for ProofV2 {
@@ -52,4 +80,4 @@ impl Discriminator for ProofV2 {
 }
 
 impl_to_bytes!(ProofV2);
-impl_account_from_bytes!(ProofV2);
+impl_account_from_bytes!(ProofV2, 6);
@@ -24,6 +24,9 @@ pub struct Tool {
 
     /// The multiplier of the tool.
     pub multiplier: u64,
+
+    /// The bump of the tool PDA.
+    pub bump: u64,
 }
 
 impl Discriminator for Tool {
@@ -33,4 +36,4 @@ impl Discriminator for Tool {
 }
 
 impl_to_bytes!(Tool);
-impl_account_from_bytes!(Tool);
+impl_account_from_bytes!(Tool, 1);
@@ -0,0 +1,177 @@
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+
+use crate::utils::{impl_account_from_bytes, impl_to_bytes, Discriminator};
+
+use super::AccountDiscriminator;
+
+/// Vesting accounts hold claimed rewards of a proof which unlock linearly over time.
+/// The tokens remain in the treasury until they are released.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct Vesting {
+    /// The signer authorized to release unlocked tokens.
+    pub authority: Pubkey,
+
+    /// The proof account whose claims are vesting.
+    pub proof: Pubkey,
+
+    /// The mint of the vesting tokens.
+    pub mint: Pubkey,
+
+    /// The quantity of tokens in the current vesting schedule.
+    pub amount: u64,
+
+    /// The quantity of tokens released from the current vesting schedule.
+    pub released: u64,
+
+    /// The time the current vesting schedule started.
+    pub start_at: i64,
+
+    /// The duration of the current vesting schedule.
+    pub duration: i64,
+
+    /// The bump of the vesting PDA.
+    pub bump: u64,
+}
+
+impl Vesting {
+    /// Returns the quantity of tokens unlocked by the given time which have not yet been released.
+    pub fn unlocked(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.start_at).max(0);
+        let vested = if elapsed.ge(&self.duration) {
+            self.amount
+        } else {
+            (self.amount as u128)
+                .saturating_mul(elapsed as u128)
+                .saturating_div(self.duration as u128) as u64
+        };
+        vested.saturating_sub(self.released)
+    }
+
+    /// Adds a claimed amount vesting over the given duration, and returns the quantity of tokens
+    /// unlocked by the given time which have not yet been released.
+    ///
+    /// The locked remainder of the current schedule and the new amount are combined into a schedule
+    /// starting now. It ends at the average of when each would have finished vesting, weighted by
+    /// their amounts, so a new claim does not restart the remainder over the full duration.
+    pub fn add(&mut self, now: i64, amount: u64, duration: i64) -> u64 {
+        let unlocked = self.unlocked(now);
+        let locked = self
+            .amount
+            .saturating_sub(self.released)
+            .saturating_sub(unlocked);
+        let remaining = self
+            .start_at
+            .saturating_add(self.duration)
+            .saturating_sub(now)
+            .max(0);
+        let total = locked.saturating_add(amount);
+        if total.gt(&0) {
+            self.duration = (locked as i128)
+                .saturating_mul(remaining as i128)
+                .saturating_add((amount as i128).saturating_mul(duration.max(0) as i128))
+                .saturating_div(total as i128) as i64;
+        }
+        self.amount = total;
+        self.released = 0;
+        self.start_at = now;
+        unlocked
+    }
+}
+
+impl Discriminator for Vesting {
+    fn discriminator() -> u8 {
+        AccountDiscriminator::Vesting.into()
+    }
+}
+
+impl_to_bytes!(Vesting);
+impl_account_from_bytes!(Vesting);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vesting(amount: u64, released: u64, duration: i64) -> Vesting {
+        Vesting {
+            authority: Pubkey::new_unique(),
+            proof: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            amount,
+            released,
+            start_at: 1_000,
+            duration,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_unlocked_linear() {
+        let vesting = vesting(1_000, 0, 100);
+        assert_eq!(vesting.unlocked(0), 0);
+        assert_eq!(vesting.unlocked(1_000), 0);
+        assert_eq!(vesting.unlocked(1_025), 250);
+        assert_eq!(vesting.unlocked(1_100), 1_000);
+        assert_eq!(vesting.unlocked(i64::MAX), 1_000);
+    }
+
+    #[test]
+    fn test_unlocked_excludes_released() {
+        let vesting = vesting(1_000, 250, 100);
+        assert_eq!(vesting.unlocked(1_025), 0);
+        assert_eq!(vesting.unlocked(1_050), 250);
+        assert_eq!(vesting.unlocked(1_100), 750);
+    }
+
+    #[test]
+    fn test_unlocked_without_duration() {
+        assert_eq!(vesting(1_000, 0, 0).unlocked(1_000), 1_000);
+    }
+
+    #[test]
+    fn test_unlocked_overflow() {
+        let vesting = vesting(u64::MAX, 0, i64::MAX);
+        assert!(vesting.unlocked(i64::MAX) < u64::MAX);
+        assert_eq!(vesting.unlocked(i64::MIN), 0);
+    }
+
+    #[test]
+    fn test_add_weights_end_time() {
+        // Half of the first claim is locked with half its duration left, and the second claim
+        // vests over the full duration.
+        let mut vesting = vesting(1_000, 0, 100);
+        assert_eq!(vesting.add(1_050, 1_000, 100), 500);
+        assert_eq!(vesting.amount, 1_500);
+        assert_eq!(vesting.start_at, 1_050);
+        assert_eq!(vesting.duration, (500 * 50 + 1_000 * 100) / 1_500);
+    }
+
+    #[test]
+    fn test_add_after_schedule_ended() {
+        let mut vesting = vesting(1_000, 0, 100);
+        assert_eq!(vesting.add(2_000, 1_000, 100), 1_000);
+        assert_eq!(vesting.amount, 1_000);
+        assert_eq!(vesting.duration, 100);
+    }
+
+    #[test]
+    fn test_add_several_claims() {
+        // Claim every 10 seconds, releasing what has unlocked each time. The schedule never runs past
+        // the end of the latest claim, and every claimed token unlocks by then.
+        let mut vesting = vesting(0, 0, 0);
+        let mut claimed = 0;
+        let mut paid = 0;
+        let mut now = 1_000;
+        for i in 1..=20u64 {
+            paid += vesting.add(now, i * 100, 100);
+            claimed += i * 100;
+            assert!(vesting.start_at.saturating_add(vesting.duration).le(&(now + 100)));
+            now += 10;
+        }
+        let ends_at = vesting.start_at.saturating_add(vesting.duration);
+        assert!(ends_at.lt(&(now - 10 + 100)));
+        paid += vesting.unlocked(ends_at);
+        assert_eq!(paid, claimed);
+    }
+}
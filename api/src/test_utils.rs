@@ -0,0 +1,25 @@
+use solana_program::{
+    instruction::Instruction,
+    sysvar::instructions::{construct_instructions_data, BorrowedAccountMeta, BorrowedInstruction},
+};
+
+/// Serializes instructions as the instructions sysvar holds them in a transaction.
+pub fn sysvar_data(instructions: &[Instruction]) -> Vec<u8> {
+    let borrowed: Vec<BorrowedInstruction> = instructions
+        .iter()
+        .map(|ix| BorrowedInstruction {
+            program_id: &ix.program_id,
+            accounts: ix
+                .accounts
+                .iter()
+                .map(|meta| BorrowedAccountMeta {
+                    pubkey: &meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: &ix.data,
+        })
+        .collect();
+    construct_instructions_data(&borrowed)
+}
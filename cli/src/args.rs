@@ -0,0 +1,147 @@
+use clap::{Parser, ValueEnum};
+use coal_api::consts::*;
+use solana_sdk::pubkey::Pubkey;
+
+/// The resource a command acts on.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Resource {
+    #[default]
+    Coal,
+    Wood,
+}
+
+impl Resource {
+    pub fn mint(&self) -> Pubkey {
+        match self {
+            Resource::Coal => COAL_MINT_ADDRESS,
+            Resource::Wood => WOOD_MINT_ADDRESS,
+        }
+    }
+
+    pub fn config_address(&self) -> Pubkey {
+        match self {
+            Resource::Coal => COAL_CONFIG_ADDRESS,
+            Resource::Wood => WOOD_CONFIG_ADDRESS,
+        }
+    }
+
+    pub fn bus_address(&self, id: u64) -> Pubkey {
+        match self {
+            Resource::Coal => coal_bus_address(id),
+            Resource::Wood => wood_bus_address(id),
+        }
+    }
+
+    pub fn proof_address(&self, authority: Pubkey) -> Pubkey {
+        let seed = match self {
+            Resource::Coal => COAL_PROOF,
+            Resource::Wood => WOOD_PROOF,
+        };
+        Pubkey::find_program_address(&[seed, authority.as_ref()], &coal_api::id()).0
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct OpenArgs {
+    #[arg(long, value_enum, default_value_t, help = "The resource to open a proof for")]
+    pub resource: Resource,
+
+    #[arg(long, value_name = "ADDRESS", help = "The proof account of the miner who referred you")]
+    pub referrer: Option<Pubkey>,
+}
+
+#[derive(Parser, Debug)]
+pub struct MineArgs {
+    #[arg(long, value_enum, default_value_t, help = "The resource to mine")]
+    pub resource: Resource,
+
+    #[arg(long, value_name = "ID", help = "The bus to mine on. Defaults to the bus with the most rewards")]
+    pub bus: Option<u64>,
+
+    #[arg(long, value_name = "ADDRESS", help = "The authority of the proof to mine for. Defaults to your keypair")]
+    pub authority: Option<Pubkey>,
+}
+
+#[derive(Parser, Debug)]
+pub struct ClaimArgs {
+    #[arg(long, value_enum, default_value_t, help = "The resource to claim")]
+    pub resource: Resource,
+
+    #[arg(long, help = "The amount to claim. Defaults to the full proof balance")]
+    pub amount: Option<f64>,
+
+    #[arg(long, value_name = "ADDRESS", help = "The token account to claim to. Defaults to your associated token account")]
+    pub beneficiary: Option<Pubkey>,
+}
+
+#[derive(Parser, Debug)]
+pub struct StakeArgs {
+    #[arg(long, value_enum, default_value_t, help = "The resource to stake")]
+    pub resource: Resource,
+
+    #[arg(long, help = "The amount to stake. Defaults to the full token account balance")]
+    pub amount: Option<f64>,
+
+    #[arg(long, value_name = "ADDRESS", help = "The token account to stake from. Defaults to your associated token account")]
+    pub sender: Option<Pubkey>,
+}
+
+#[derive(Parser, Debug)]
+pub struct CloseArgs {
+    #[arg(long, value_enum, default_value_t, help = "The resource of the proof to close")]
+    pub resource: Resource,
+}
+
+#[derive(Parser, Debug)]
+pub struct UpdateArgs {
+    #[arg(long, value_enum, default_value_t, help = "The resource of the proof to update")]
+    pub resource: Resource,
+
+    #[arg(long, value_name = "ADDRESS", help = "The new miner authority of the proof")]
+    pub miner: Pubkey,
+}
+
+#[derive(Parser, Debug)]
+pub struct EquipArgs {
+    #[arg(long, value_name = "ADDRESS", help = "The tool asset to equip")]
+    pub asset: Pubkey,
+
+    #[arg(long, value_name = "ADDRESS", help = "The miner to equip the tool for. Defaults to your keypair")]
+    pub miner: Option<Pubkey>,
+
+    #[arg(long, value_name = "ADDRESS", help = "The collection of the tool. Defaults to the forge collection")]
+    pub collection: Option<Pubkey>,
+}
+
+#[derive(Parser, Debug)]
+pub struct UnequipArgs {
+    #[arg(long, value_name = "ADDRESS", help = "The tool asset to unequip")]
+    pub asset: Pubkey,
+
+    #[arg(long, value_name = "ADDRESS", help = "The miner the tool is equipped for. Defaults to your keypair")]
+    pub miner: Option<Pubkey>,
+
+    #[arg(long, value_name = "ADDRESS", help = "The collection of the tool. Defaults to the forge collection")]
+    pub collection: Option<Pubkey>,
+}
+
+#[derive(Parser, Debug)]
+pub struct ResetArgs {
+    #[arg(long, value_enum, default_value_t, help = "The resource to reset")]
+    pub resource: Resource,
+
+    #[arg(long, help = "The number of busses of the resource. Defaults to the count in the config")]
+    pub bus_count: Option<u64>,
+}
+
+#[derive(Parser, Debug)]
+pub struct InitArgs {
+    #[arg(long, value_enum, default_value_t, help = "The resource to initialize")]
+    pub resource: Resource,
+}
+
+#[derive(Parser, Debug)]
+pub struct InspectArgs {
+    #[arg(value_name = "ADDRESS", help = "The program account to decode")]
+    pub address: Pubkey,
+}
@@ -0,0 +1,41 @@
+use coal_api::instruction::{claim_coal, claim_wood, Bumps};
+use solana_sdk::signature::Signer;
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+
+use crate::{
+    args::{ClaimArgs, Resource},
+    utils::amount_to_grains,
+    Cli, Result,
+};
+
+impl Cli {
+    /// Claims rewards from the signer's proof. Without a beneficiary, the signer's associated token
+    /// account is created if it does not exist yet.
+    pub fn claim(&self, args: ClaimArgs) -> Result<()> {
+        let signer = self.signer.pubkey();
+        let amount = match args.amount {
+            Some(amount) => amount_to_grains(amount)?,
+            None => self.get_proof(args.resource, signer, "claim without --amount")?.balance,
+        };
+        let mut ixs = vec![];
+        let beneficiary = match args.beneficiary {
+            Some(beneficiary) => beneficiary,
+            None => {
+                ixs.push(create_associated_token_account_idempotent(
+                    &signer,
+                    &signer,
+                    &args.resource.mint(),
+                    &spl_token::id(),
+                ));
+                get_associated_token_address(&signer, &args.resource.mint())
+            }
+        };
+        ixs.push(match args.resource {
+            Resource::Coal => claim_coal(signer, beneficiary, amount, spl_token::id(), Bumps::default()),
+            Resource::Wood => claim_wood(signer, beneficiary, amount, spl_token::id(), Bumps::default()),
+        });
+        self.send(&ixs)
+    }
+}
@@ -0,0 +1,17 @@
+use coal_api::instruction::{close_coal, close_wood, Bumps};
+use solana_sdk::signature::Signer;
+
+use crate::{
+    args::{CloseArgs, Resource},
+    Cli, Result,
+};
+
+impl Cli {
+    pub fn close(&self, args: CloseArgs) -> Result<()> {
+        let ix = match args.resource {
+            Resource::Coal => close_coal(self.signer.pubkey(), Bumps::default()),
+            Resource::Wood => close_wood(self.signer.pubkey(), Bumps::default()),
+        };
+        self.send(&[ix])
+    }
+}
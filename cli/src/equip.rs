@@ -0,0 +1,18 @@
+use coal_api::{consts::FORGE_COLLECTION_ADDRESS, instruction::equip};
+use solana_sdk::signature::Signer;
+
+use crate::{args::EquipArgs, Cli, Result};
+
+impl Cli {
+    pub fn equip(&self, args: EquipArgs) -> Result<()> {
+        let signer = self.signer.pubkey();
+        let ix = equip(
+            signer,
+            args.miner.unwrap_or(signer),
+            signer,
+            args.asset,
+            args.collection.unwrap_or(FORGE_COLLECTION_ADDRESS),
+        );
+        self.send(&[ix])
+    }
+}
@@ -0,0 +1,18 @@
+use coal_api::instruction::{init_coal, init_wood};
+use solana_sdk::signature::Signer;
+
+use crate::{
+    args::{InitArgs, Resource},
+    Cli, Result,
+};
+
+impl Cli {
+    /// Creates the program accounts of a resource. Must be signed by the initializer.
+    pub fn init(&self, args: InitArgs) -> Result<()> {
+        let ix = match args.resource {
+            Resource::Coal => init_coal(self.signer.pubkey()),
+            Resource::Wood => init_wood(self.signer.pubkey()),
+        };
+        self.send(&[ix])
+    }
+}
@@ -0,0 +1,151 @@
+mod args;
+mod claim;
+mod close;
+mod equip;
+mod init;
+mod inspect;
+mod mine;
+mod open;
+mod reset;
+mod send;
+mod stake;
+mod unequip;
+mod update;
+mod utils;
+
+use args::*;
+use clap::{command, Parser, Subcommand};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    signature::{read_keypair_file, Keypair},
+};
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// Submits transactions for the signer, or prints them signed for broadcast elsewhere when offline.
+pub struct Cli {
+    /// The RPC client.
+    pub rpc: RpcClient,
+
+    /// The keypair which signs and pays for transactions.
+    pub signer: Keypair,
+
+    /// The blockhash to sign offline transactions with. Set in offline mode.
+    pub offline_blockhash: Option<Hash>,
+}
+
+#[derive(Parser, Debug)]
+#[command(about, version)]
+struct Args {
+    #[arg(
+        long,
+        value_name = "URL",
+        help = "Network address of your RPC provider, or a moniker (mainnet-beta, devnet, localhost)",
+        default_value = "mainnet-beta",
+        global = true
+    )]
+    url: String,
+
+    #[arg(
+        long,
+        value_name = "KEYPAIR_FILEPATH",
+        help = "Filepath to signer keypair. Defaults to ~/.config/solana/id.json",
+        global = true
+    )]
+    keypair: Option<String>,
+
+    #[arg(
+        long,
+        help = "Sign transactions without a connection and print them as base64 instead of sending them",
+        requires = "blockhash",
+        global = true
+    )]
+    offline: bool,
+
+    #[arg(
+        long,
+        value_name = "HASH",
+        help = "The recent blockhash to sign offline transactions with",
+        global = true
+    )]
+    blockhash: Option<Hash>,
+
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    #[command(about = "Open a proof account to mine with")]
+    Open(OpenArgs),
+
+    #[command(about = "Find a single solution for your proof and submit it")]
+    Mine(MineArgs),
+
+    #[command(about = "Claim your mining rewards")]
+    Claim(ClaimArgs),
+
+    #[command(about = "Stake tokens into your proof to increase your multiplier")]
+    Stake(StakeArgs),
+
+    #[command(about = "Close your proof account and recover the rent")]
+    Close(CloseArgs),
+
+    #[command(about = "Update the miner authority of your proof")]
+    Update(UpdateArgs),
+
+    #[command(about = "Equip a forged tool")]
+    Equip(EquipArgs),
+
+    #[command(about = "Unequip a forged tool")]
+    Unequip(UnequipArgs),
+
+    #[command(about = "Reset a resource for a new epoch")]
+    Reset(ResetArgs),
+
+    #[command(about = "Initialize the program accounts of a resource (admin)")]
+    Init(InitArgs),
+
+    #[command(about = "Decode and print any program account")]
+    Inspect(InspectArgs),
+}
+
+fn main() {
+    let args = Args::parse();
+    let keypair = args.keypair.unwrap_or_else(utils::default_keypair_path);
+    let signer = match read_keypair_file(&keypair) {
+        Ok(signer) => signer,
+        Err(err) => {
+            eprintln!("Failed to read keypair {}: {}", keypair, err);
+            std::process::exit(1);
+        }
+    };
+    let cli = Cli {
+        rpc: RpcClient::new_with_commitment(
+            utils::resolve_url(&args.url),
+            CommitmentConfig::confirmed(),
+        ),
+        signer,
+        offline_blockhash: if args.offline { args.blockhash } else { None },
+    };
+
+    let result = match args.command {
+        Commands::Open(args) => cli.open(args),
+        Commands::Mine(args) => cli.mine(args),
+        Commands::Claim(args) => cli.claim(args),
+        Commands::Stake(args) => cli.stake(args),
+        Commands::Close(args) => cli.close(args),
+        Commands::Update(args) => cli.update(args),
+        Commands::Equip(args) => cli.equip(args),
+        Commands::Unequip(args) => cli.unequip(args),
+        Commands::Reset(args) => cli.reset(args),
+        Commands::Init(args) => cli.init(args),
+        Commands::Inspect(args) => cli.inspect(args),
+    };
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    }
+}
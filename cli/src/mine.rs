@@ -0,0 +1,90 @@
+use coal_api::{
+    auth::auth,
+    instruction::{chop_wood, mine_coal, Bumps},
+};
+use drillx::{equix::SolverMemory, Solution};
+use solana_sdk::{compute_budget::ComputeBudgetInstruction, signature::Signer};
+
+use crate::{
+    args::{MineArgs, Resource},
+    Cli, Result,
+};
+
+/// The compute budget of a transaction with a single mine instruction.
+const MINE_COMPUTE_UNIT_LIMIT: u32 = 500_000;
+
+impl Cli {
+    /// Hashes the proof's current challenge until a solution meets the minimum difficulty, and submits
+    /// it as a single mine transaction.
+    pub fn mine(&self, args: MineArgs) -> Result<()> {
+        let signer = self.signer.pubkey();
+        let authority = args.authority.unwrap_or(signer);
+        let config = self.get_config(args.resource, "mine")?;
+        let proof = self.get_proof(args.resource, authority, "mine")?;
+        let bus = match args.bus {
+            Some(bus) => bus,
+            None => self.find_bus(args.resource, config.bus_count)?,
+        };
+
+        // Find a solution.
+        let solution = find_solution(&proof.challenge, config.min_difficulty as u32);
+        println!("Found a hash of difficulty {}", solution.to_hash().difficulty());
+
+        // Submit the solution, crediting the referrer while the referral is active.
+        let proof_address = args.resource.proof_address(authority);
+        let bus_address = args.resource.bus_address(bus);
+        let referrer = proof.active_referrer(self.get_clock("mine")?.unix_timestamp);
+        let bumps = Bumps {
+            proof: Some(proof.bump),
+            ..Default::default()
+        };
+        let ix = match args.resource {
+            Resource::Coal => mine_coal(signer, authority, bus_address, solution, referrer, None, bumps),
+            Resource::Wood => chop_wood(signer, authority, bus_address, solution, referrer, bumps),
+        };
+        self.send(&[
+            ComputeBudgetInstruction::set_compute_unit_limit(MINE_COMPUTE_UNIT_LIMIT),
+            auth(proof_address, args.resource.mint()),
+            ix,
+        ])
+    }
+
+    /// Returns the id of the bus with the most rewards left this epoch.
+    fn find_bus(&self, resource: Resource, bus_count: u64) -> Result<u64> {
+        let mut best = (0, 0);
+        for id in 0..bus_count {
+            let rewards = self.get_bus(resource, id, "mine")?.rewards;
+            if rewards.gt(&best.1) {
+                best = (id, rewards);
+            }
+        }
+        Ok(best.0)
+    }
+}
+
+/// Returns the first solution of the challenge with at least the given difficulty.
+pub fn find_solution(challenge: &[u8; 32], min_difficulty: u32) -> Solution {
+    let mut memory = SolverMemory::new();
+    let mut nonce = 0u64;
+    loop {
+        for hash in drillx::hashes_with_memory(&mut memory, challenge, &nonce.to_le_bytes()) {
+            if hash.difficulty().ge(&min_difficulty) {
+                return Solution::new(hash.d, nonce.to_le_bytes());
+            }
+        }
+        nonce += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_solution() {
+        let challenge = [7; 32];
+        let solution = find_solution(&challenge, 4);
+        assert!(solution.is_valid(&challenge));
+        assert!(solution.to_hash().difficulty().ge(&4));
+    }
+}
@@ -0,0 +1,18 @@
+use coal_api::instruction::{open_coal, open_wood};
+use solana_sdk::signature::Signer;
+
+use crate::{
+    args::{OpenArgs, Resource},
+    Cli, Result,
+};
+
+impl Cli {
+    pub fn open(&self, args: OpenArgs) -> Result<()> {
+        let signer = self.signer.pubkey();
+        let ix = match args.resource {
+            Resource::Coal => open_coal(signer, signer, signer, args.referrer),
+            Resource::Wood => open_wood(signer, signer, signer, args.referrer),
+        };
+        self.send(&[ix])
+    }
+}
@@ -0,0 +1,23 @@
+use coal_api::instruction::{reset_coal, reset_wood};
+use solana_sdk::signature::Signer;
+
+use crate::{
+    args::{ResetArgs, Resource},
+    Cli, Result,
+};
+
+impl Cli {
+    /// Resets a resource for a new epoch. The bus count is read from the config unless given, so
+    /// offline resets must pass it.
+    pub fn reset(&self, args: ResetArgs) -> Result<()> {
+        let bus_count = match args.bus_count {
+            Some(bus_count) => bus_count,
+            None => self.get_config(args.resource, "reset without --bus-count")?.bus_count,
+        };
+        let ix = match args.resource {
+            Resource::Coal => reset_coal(self.signer.pubkey(), bus_count),
+            Resource::Wood => reset_wood(self.signer.pubkey(), bus_count),
+        };
+        self.send(&[ix])
+    }
+}
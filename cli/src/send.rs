@@ -0,0 +1,59 @@
+use base64::{prelude::BASE64_STANDARD, Engine};
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+
+use crate::{Cli, Result};
+
+impl Cli {
+    /// Signs the instructions into a single transaction paid for by the signer.
+    ///
+    /// Online, the transaction is sent and its signature printed. Offline, it is signed with the given
+    /// blockhash and printed as base64, to be broadcast from a connected machine.
+    pub fn send(&self, ixs: &[Instruction]) -> Result<()> {
+        match self.offline_blockhash {
+            Some(blockhash) => {
+                let tx = sign_transaction(ixs, &self.signer, blockhash);
+                println!("{}", encode_transaction(&tx)?);
+            }
+            None => {
+                let blockhash = self.rpc.get_latest_blockhash()?;
+                let tx = sign_transaction(ixs, &self.signer, blockhash);
+                let signature = self.rpc.send_and_confirm_transaction(&tx)?;
+                println!("{}", signature);
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn sign_transaction(ixs: &[Instruction], signer: &Keypair, blockhash: Hash) -> Transaction {
+    Transaction::new_signed_with_payer(ixs, Some(&signer.pubkey()), &[signer], blockhash)
+}
+
+/// Encodes a transaction in the wire format accepted by the `sendTransaction` RPC method.
+pub fn encode_transaction(tx: &Transaction) -> Result<String> {
+    Ok(BASE64_STANDARD.encode(bincode::serialize(tx)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use coal_api::instruction::{close_coal, Bumps};
+
+    use super::*;
+
+    #[test]
+    fn test_encode_transaction() {
+        let signer = Keypair::new();
+        let blockhash = Hash::new_unique();
+        let tx = sign_transaction(&[close_coal(signer.pubkey(), Bumps::default())], &signer, blockhash);
+        let bytes = BASE64_STANDARD.decode(encode_transaction(&tx).unwrap()).unwrap();
+        let decoded: Transaction = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, tx);
+        assert_eq!(decoded.message.recent_blockhash, blockhash);
+        assert!(decoded.verify().is_ok());
+    }
+}
@@ -0,0 +1,31 @@
+use coal_api::instruction::{stake_coal, stake_wood, Bumps};
+use solana_sdk::signature::Signer;
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::{
+    args::{Resource, StakeArgs},
+    utils::amount_to_grains,
+    Cli, Result,
+};
+
+impl Cli {
+    pub fn stake(&self, args: StakeArgs) -> Result<()> {
+        let signer = self.signer.pubkey();
+        let sender = args
+            .sender
+            .unwrap_or_else(|| get_associated_token_address(&signer, &args.resource.mint()));
+        let amount = match args.amount {
+            Some(amount) => amount_to_grains(amount)?,
+            None => self
+                .connection("stake without --amount")?
+                .get_token_account_balance(&sender)?
+                .amount
+                .parse()?,
+        };
+        let ix = match args.resource {
+            Resource::Coal => stake_coal(signer, sender, amount, spl_token::id(), Bumps::default()),
+            Resource::Wood => stake_wood(signer, sender, amount, spl_token::id(), Bumps::default()),
+        };
+        self.send(&[ix])
+    }
+}
@@ -0,0 +1,18 @@
+use coal_api::{consts::FORGE_COLLECTION_ADDRESS, instruction::unequip};
+use solana_sdk::signature::Signer;
+
+use crate::{args::UnequipArgs, Cli, Result};
+
+impl Cli {
+    pub fn unequip(&self, args: UnequipArgs) -> Result<()> {
+        let signer = self.signer.pubkey();
+        let ix = unequip(
+            signer,
+            args.miner.unwrap_or(signer),
+            signer,
+            args.asset,
+            args.collection.unwrap_or(FORGE_COLLECTION_ADDRESS),
+        );
+        self.send(&[ix])
+    }
+}
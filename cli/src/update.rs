@@ -0,0 +1,17 @@
+use coal_api::instruction::{update_coal, update_wood, Bumps};
+use solana_sdk::signature::Signer;
+
+use crate::{
+    args::{Resource, UpdateArgs},
+    Cli, Result,
+};
+
+impl Cli {
+    pub fn update(&self, args: UpdateArgs) -> Result<()> {
+        let ix = match args.resource {
+            Resource::Coal => update_coal(self.signer.pubkey(), args.miner, Bumps::default()),
+            Resource::Wood => update_wood(self.signer.pubkey(), args.miner, Bumps::default()),
+        };
+        self.send(&[ix])
+    }
+}
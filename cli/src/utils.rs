@@ -0,0 +1,182 @@
+use coal_api::{
+    consts::TOKEN_DECIMALS,
+    state::{Bus, Config, Proof, ProofV2, WoodConfig},
+};
+use coal_utils::AccountDeserialize;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::{clock::Clock, pubkey::Pubkey, sysvar};
+
+use crate::{args::Resource, Cli, Result};
+
+/// The fields shared by the proofs of both resources.
+pub struct ProofState {
+    pub balance: u64,
+    pub challenge: [u8; 32],
+    pub referrer: Pubkey,
+    pub referral_expires_at: i64,
+    pub bump: u64,
+}
+
+impl ProofState {
+    /// Returns the referrer which mining must credit at the given time, if the proof was referred
+    /// and the referral has not expired.
+    ///
+    /// Proofs which predate the referral expiry read it as zero. Mining backfills it when it upgrades
+    /// the proof, so the referrer is passed in case the referral is still active.
+    pub fn active_referrer(&self, now: i64) -> Option<Pubkey> {
+        (self.referrer.ne(&Pubkey::default())
+            && (self.referral_expires_at.eq(&0) || now.lt(&self.referral_expires_at)))
+            .then_some(self.referrer)
+    }
+}
+
+/// The fields shared by the configs of both resources.
+pub struct ConfigState {
+    pub bus_count: u64,
+    pub min_difficulty: u64,
+}
+
+impl Cli {
+    /// Returns the RPC client, or errors if the command needs on-chain state in offline mode.
+    pub fn connection(&self, command: &str) -> Result<&RpcClient> {
+        if self.offline_blockhash.is_some() {
+            return Err(format!("{} reads on-chain state and cannot run offline", command).into());
+        }
+        Ok(&self.rpc)
+    }
+
+    pub fn get_proof(&self, resource: Resource, authority: Pubkey, command: &str) -> Result<ProofState> {
+        let data = self
+            .connection(command)?
+            .get_account_data(&resource.proof_address(authority))?;
+        Ok(match resource {
+            Resource::Coal => {
+                let proof = Proof::read_from_bytes(&data)?;
+                ProofState {
+                    balance: proof.balance,
+                    challenge: proof.challenge,
+                    referrer: proof.referrer,
+                    referral_expires_at: proof.referral_expires_at,
+                    bump: proof.bump,
+                }
+            }
+            Resource::Wood => {
+                let proof = ProofV2::read_from_bytes(&data)?;
+                ProofState {
+                    balance: proof.balance,
+                    challenge: proof.challenge,
+                    referrer: proof.referrer,
+                    referral_expires_at: proof.referral_expires_at,
+                    bump: proof.bump,
+                }
+            }
+        })
+    }
+
+    pub fn get_config(&self, resource: Resource, command: &str) -> Result<ConfigState> {
+        let data = self
+            .connection(command)?
+            .get_account_data(&resource.config_address())?;
+        Ok(match resource {
+            Resource::Coal => {
+                let config = Config::read_from_bytes(&data)?;
+                ConfigState {
+                    bus_count: config.bus_count,
+                    min_difficulty: config.min_difficulty,
+                }
+            }
+            Resource::Wood => {
+                let config = WoodConfig::read_from_bytes(&data)?;
+                ConfigState {
+                    bus_count: config.bus_count,
+                    min_difficulty: config.min_difficulty,
+                }
+            }
+        })
+    }
+
+    pub fn get_bus(&self, resource: Resource, id: u64, command: &str) -> Result<Bus> {
+        let data = self
+            .connection(command)?
+            .get_account_data(&resource.bus_address(id))?;
+        Ok(Bus::read_from_bytes(&data)?)
+    }
+
+    /// Returns the cluster clock, which the program checks referral expiries against.
+    pub fn get_clock(&self, command: &str) -> Result<Clock> {
+        let data = self.connection(command)?.get_account_data(&sysvar::clock::id())?;
+        Ok(bincode::deserialize(&data)?)
+    }
+}
+
+/// Converts a token amount to grains, erroring on negative or unrepresentable amounts.
+pub fn amount_to_grains(amount: f64) -> Result<u64> {
+    let grains = amount * 10f64.powi(TOKEN_DECIMALS as i32);
+    if !grains.is_finite() || grains.lt(&0.0) || grains.ge(&(u64::MAX as f64)) {
+        return Err(format!("invalid amount {}", amount).into());
+    }
+    Ok(grains.round() as u64)
+}
+
+/// Expands the cluster monikers accepted by the Solana CLI into RPC URLs.
+pub fn resolve_url(url: &str) -> String {
+    match url {
+        "m" | "mainnet-beta" => "https://api.mainnet-beta.solana.com",
+        "d" | "devnet" => "https://api.devnet.solana.com",
+        "t" | "testnet" => "https://api.testnet.solana.com",
+        "l" | "localhost" => "http://127.0.0.1:8899",
+        url => url,
+    }
+    .to_string()
+}
+
+pub fn default_keypair_path() -> String {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    format!("{}/.config/solana/id.json", home)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_amount_to_grains() {
+        assert_eq!(amount_to_grains(1.0).unwrap(), 10u64.pow(TOKEN_DECIMALS as u32));
+        assert_eq!(amount_to_grains(0.5).unwrap(), 10u64.pow(TOKEN_DECIMALS as u32) / 2);
+        assert_eq!(amount_to_grains(0.0).unwrap(), 0);
+        assert!(amount_to_grains(-1.0).is_err());
+        assert!(amount_to_grains(f64::NAN).is_err());
+        assert!(amount_to_grains(1e20).is_err());
+    }
+
+    #[test]
+    fn test_active_referrer() {
+        let referrer = Pubkey::new_unique();
+        let proof = ProofState {
+            balance: 0,
+            challenge: [0; 32],
+            referrer,
+            referral_expires_at: 100,
+            bump: 0,
+        };
+        assert_eq!(proof.active_referrer(99), Some(referrer));
+        assert_eq!(proof.active_referrer(100), None);
+        let proof = ProofState {
+            referral_expires_at: 0,
+            ..proof
+        };
+        assert_eq!(proof.active_referrer(100), Some(referrer));
+        let proof = ProofState {
+            referrer: Pubkey::default(),
+            ..proof
+        };
+        assert_eq!(proof.active_referrer(99), None);
+    }
+
+    #[test]
+    fn test_resolve_url() {
+        assert_eq!(resolve_url("localhost"), "http://127.0.0.1:8899");
+        assert_eq!(resolve_url("l"), "http://127.0.0.1:8899");
+        assert_eq!(resolve_url("http://10.0.0.1:8899"), "http://10.0.0.1:8899");
+    }
+}
@@ -0,0 +1,105 @@
+mod miner;
+mod resource;
+mod search;
+mod stats;
+mod timing;
+
+use std::path::PathBuf;
+
+use clap::{command, Parser};
+use coal_api::consts::TOLERANCE;
+use miner::Miner;
+use resource::Resource;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, signature::read_keypair_file};
+use stats::Stats;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Parser, Debug)]
+#[command(about, version)]
+struct Args {
+    #[arg(
+        long,
+        value_name = "URL",
+        help = "Network address of your RPC provider, or a moniker (mainnet-beta, devnet, localhost)",
+        default_value = "mainnet-beta"
+    )]
+    url: String,
+
+    #[arg(
+        long,
+        value_name = "KEYPAIR_FILEPATH",
+        help = "Filepath to the keypair of the proof authority. Defaults to ~/.config/solana/id.json"
+    )]
+    keypair: Option<String>,
+
+    #[arg(long, value_enum, default_value_t, help = "The resource to mine")]
+    resource: Resource,
+
+    #[arg(long, help = "The number of threads to hash with. Defaults to the available parallelism")]
+    cores: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        help = "The seconds before the target time to stop hashing and submit",
+        default_value_t = TOLERANCE / 2
+    )]
+    buffer_time: i64,
+
+    #[arg(long, value_name = "ID", help = "The bus to mine on. Defaults to the bus with the most rewards each round")]
+    bus: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "The CSV file to append submission stats to",
+        default_value = "coal-miner-stats.csv"
+    )]
+    stats: PathBuf,
+}
+
+fn main() {
+    let args = Args::parse();
+    let keypair = args.keypair.unwrap_or_else(default_keypair_path);
+    let signer = match read_keypair_file(&keypair) {
+        Ok(signer) => signer,
+        Err(err) => {
+            eprintln!("Failed to read keypair {}: {}", keypair, err);
+            std::process::exit(1);
+        }
+    };
+    let cores = args.cores.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|cores| cores.get() as u64)
+            .unwrap_or(1)
+    });
+    let mut miner = Miner {
+        rpc: RpcClient::new_with_commitment(resolve_url(&args.url), CommitmentConfig::confirmed()),
+        signer,
+        resource: args.resource,
+        cores,
+        buffer_time: args.buffer_time,
+        bus: args.bus,
+        stats: Stats::new(args.stats),
+    };
+    miner.run();
+}
+
+/// Expands the cluster monikers accepted by the Solana CLI into RPC URLs.
+fn resolve_url(url: &str) -> String {
+    match url {
+        "m" | "mainnet-beta" => "https://api.mainnet-beta.solana.com",
+        "d" | "devnet" => "https://api.devnet.solana.com",
+        "t" | "testnet" => "https://api.testnet.solana.com",
+        "l" | "localhost" => "http://127.0.0.1:8899",
+        url => url,
+    }
+    .to_string()
+}
+
+fn default_keypair_path() -> String {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    format!("{}/.config/solana/id.json", home)
+}
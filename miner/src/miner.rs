@@ -0,0 +1,368 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use coal_api::{
+    auth::auth,
+    instruction::{chop_wood, mine_coal, reset_coal, reset_wood, Bumps},
+    state::{Bus, Config, Proof, ProofV2, WoodConfig},
+};
+use coal_utils::AccountDeserialize;
+use drillx::Solution;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::{
+    clock::Clock,
+    compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature, Signer},
+    sysvar,
+    transaction::Transaction,
+};
+
+use crate::{
+    resource::Resource,
+    search::search,
+    stats::{Stats, Submission},
+    timing::{hashing_seconds, needs_reset, submit_delay},
+    Result,
+};
+
+/// The compute budget of a mine instruction.
+const MINE_COMPUTE_UNIT_LIMIT: u32 = 500_000;
+
+/// The additional compute budget of a reset instruction submitted ahead of the mine instruction.
+const RESET_COMPUTE_UNIT_LIMIT: u32 = 300_000;
+
+/// The seconds to wait before retrying after a failed round.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// The fields of a proof the miner reads each round.
+struct ProofState {
+    balance: u64,
+    challenge: [u8; 32],
+    last_hash_at: i64,
+    referrer: Pubkey,
+    referral_expires_at: i64,
+    bump: u64,
+}
+
+impl ProofState {
+    /// Returns the referrer which mining must credit at the given time, if the proof was referred
+    /// and the referral has not expired.
+    ///
+    /// Proofs which predate the referral expiry read it as zero. Mining backfills it when it upgrades
+    /// the proof, so the referrer is passed in case the referral is still active.
+    fn active_referrer(&self, now: i64) -> Option<Pubkey> {
+        (self.referrer.ne(&Pubkey::default())
+            && (self.referral_expires_at.eq(&0) || now.lt(&self.referral_expires_at)))
+            .then_some(self.referrer)
+    }
+}
+
+/// The fields of a config the miner reads each round.
+struct ConfigState {
+    bus_count: u64,
+    epoch_duration: i64,
+    last_reset_at: i64,
+    min_difficulty: u64,
+}
+
+/// Mines a resource with the signer's proof, one solution per round.
+pub struct Miner {
+    pub rpc: RpcClient,
+    pub signer: Keypair,
+    pub resource: Resource,
+    pub cores: u64,
+    pub buffer_time: i64,
+    pub bus: Option<u64>,
+    pub stats: Stats,
+}
+
+impl Miner {
+    /// Mines until the process is stopped. Failed rounds are logged and retried.
+    pub fn run(&mut self) {
+        loop {
+            if let Err(err) = self.mine_once() {
+                eprintln!("Error: {}", err);
+                thread::sleep(RETRY_DELAY);
+            }
+        }
+    }
+
+    /// Hashes the proof's current challenge until shortly before its target time, then submits the
+    /// hardest solution as soon as the program accepts it.
+    fn mine_once(&mut self) -> Result<()> {
+        let authority = self.signer.pubkey();
+        let proof = self.get_proof(authority)?;
+        let config = self.get_config()?;
+
+        // Hash until shortly before the target time.
+        let seconds = hashing_seconds(proof.last_hash_at, self.buffer_time, self.get_clock()?.unix_timestamp);
+        println!(
+            "Hashing for {}s on {} cores (min difficulty {})",
+            seconds, self.cores, config.min_difficulty
+        );
+        let deadline = Instant::now() + Duration::from_secs(seconds);
+        let result = search(&proof.challenge, config.min_difficulty as u32, self.cores, deadline);
+        println!("Best hash: difficulty {} of {} hashes", result.difficulty, result.hashes);
+
+        // Wait until the solution would not be rejected as spam.
+        let delay = submit_delay(proof.last_hash_at, self.get_clock()?.unix_timestamp);
+        thread::sleep(Duration::from_secs(delay));
+
+        // Submit the solution, resetting the epoch first if it has ended.
+        let bus = match self.bus {
+            Some(bus) => bus,
+            None => self.find_bus(config.bus_count)?,
+        };
+        let now = self.get_clock()?.unix_timestamp;
+        let reset_bus_count =
+            needs_reset(config.last_reset_at, config.epoch_duration, now).then_some(config.bus_count);
+        let ixs = submit_instructions(
+            self.resource,
+            self.signer.pubkey(),
+            authority,
+            bus,
+            result.solution,
+            proof.active_referrer(now),
+            reset_bus_count,
+            Bumps {
+                proof: Some(proof.bump),
+                ..Default::default()
+            },
+        );
+        let signature = self.send(&ixs)?;
+
+        // Record the reward.
+        let reward = self.get_proof(authority)?.balance.saturating_sub(proof.balance);
+        self.stats.record(&Submission {
+            timestamp: self.get_clock()?.unix_timestamp,
+            bus,
+            difficulty: result.difficulty,
+            hashes: result.hashes,
+            reward,
+            signature: signature.to_string(),
+        })?;
+        println!(
+            "Submitted {} on bus {} for {} grains ({} submissions, {} grains, best difficulty {})",
+            signature,
+            bus,
+            reward,
+            self.stats.submissions,
+            self.stats.rewards,
+            self.stats.best_difficulty
+        );
+        Ok(())
+    }
+
+    /// Returns the id of the bus with the most rewards left this epoch.
+    fn find_bus(&self, bus_count: u64) -> Result<u64> {
+        let addresses: Vec<_> = (0..bus_count).map(|id| self.resource.bus_address(id)).collect();
+        let mut rewards = vec![];
+        for account in self.rpc.get_multiple_accounts(&addresses)? {
+            let account = account.ok_or("bus account not found")?;
+            rewards.push(Bus::read_from_bytes(&account.data)?.rewards);
+        }
+        Ok(richest_bus(&rewards))
+    }
+
+    fn get_proof(&self, authority: Pubkey) -> Result<ProofState> {
+        let data = self.rpc.get_account_data(&self.resource.proof_address(authority))?;
+        Ok(match self.resource {
+            Resource::Coal => {
+                let proof = Proof::read_from_bytes(&data)?;
+                ProofState {
+                    balance: proof.balance,
+                    challenge: proof.challenge,
+                    last_hash_at: proof.last_hash_at,
+                    referrer: proof.referrer,
+                    referral_expires_at: proof.referral_expires_at,
+                    bump: proof.bump,
+                }
+            }
+            Resource::Wood => {
+                let proof = ProofV2::read_from_bytes(&data)?;
+                ProofState {
+                    balance: proof.balance,
+                    challenge: proof.challenge,
+                    last_hash_at: proof.last_hash_at,
+                    referrer: proof.referrer,
+                    referral_expires_at: proof.referral_expires_at,
+                    bump: proof.bump,
+                }
+            }
+        })
+    }
+
+    fn get_config(&self) -> Result<ConfigState> {
+        let data = self.rpc.get_account_data(&self.resource.config_address())?;
+        Ok(match self.resource {
+            Resource::Coal => {
+                let config = Config::read_from_bytes(&data)?;
+                ConfigState {
+                    bus_count: config.bus_count,
+                    epoch_duration: config.epoch_duration,
+                    last_reset_at: config.last_reset_at,
+                    min_difficulty: config.min_difficulty,
+                }
+            }
+            Resource::Wood => {
+                let config = WoodConfig::read_from_bytes(&data)?;
+                ConfigState {
+                    bus_count: config.bus_count,
+                    epoch_duration: config.epoch_duration,
+                    last_reset_at: config.last_reset_at,
+                    min_difficulty: config.min_difficulty,
+                }
+            }
+        })
+    }
+
+    /// Returns the cluster clock. Submission is timed by the cluster clock, which may drift from the
+    /// local one.
+    fn get_clock(&self) -> Result<Clock> {
+        let data = self.rpc.get_account_data(&sysvar::clock::id())?;
+        Ok(bincode::deserialize(&data)?)
+    }
+
+    fn send(&self, ixs: &[Instruction]) -> Result<Signature> {
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&self.signer.pubkey()),
+            &[&self.signer],
+            blockhash,
+        );
+        Ok(self.rpc.send_and_confirm_transaction(&tx)?)
+    }
+}
+
+/// Returns the instructions which submit a solution on the given bus, crediting the referrer if
+/// given and resetting the epoch first if the bus count to reset is given.
+#[allow(clippy::too_many_arguments)]
+fn submit_instructions(
+    resource: Resource,
+    signer: Pubkey,
+    authority: Pubkey,
+    bus: u64,
+    solution: Solution,
+    referrer: Option<Pubkey>,
+    reset_bus_count: Option<u64>,
+    bumps: Bumps,
+) -> Vec<Instruction> {
+    let mut compute_unit_limit = MINE_COMPUTE_UNIT_LIMIT;
+    let mut ixs = vec![auth(resource.proof_address(authority), resource.mint())];
+    if let Some(bus_count) = reset_bus_count {
+        compute_unit_limit += RESET_COMPUTE_UNIT_LIMIT;
+        ixs.push(match resource {
+            Resource::Coal => reset_coal(signer, bus_count),
+            Resource::Wood => reset_wood(signer, bus_count),
+        });
+    }
+    let bus_address = resource.bus_address(bus);
+    ixs.push(match resource {
+        Resource::Coal => mine_coal(signer, authority, bus_address, solution, referrer, None, bumps),
+        Resource::Wood => chop_wood(signer, authority, bus_address, solution, referrer, bumps),
+    });
+    ixs.insert(0, ComputeBudgetInstruction::set_compute_unit_limit(compute_unit_limit));
+    ixs
+}
+
+/// Returns the id of the bus with the most rewards. Ties go to the lowest id.
+fn richest_bus(rewards: &[u64]) -> u64 {
+    rewards
+        .iter()
+        .enumerate()
+        .fold((0, 0), |best, (id, rewards)| {
+            if rewards.gt(&best.1) {
+                (id as u64, *rewards)
+            } else {
+                best
+            }
+        })
+        .0
+}
+
+#[cfg(test)]
+mod tests {
+    use coal_api::consts::MAX_BUS_COUNT;
+    use solana_sdk::{hash::Hash, packet::PACKET_DATA_SIZE};
+
+    use super::*;
+
+    #[test]
+    fn test_richest_bus() {
+        assert_eq!(richest_bus(&[5, 9, 3, 9]), 1);
+        assert_eq!(richest_bus(&[0, 0]), 0);
+        assert_eq!(richest_bus(&[]), 0);
+    }
+
+    #[test]
+    fn test_active_referrer() {
+        let referrer = Pubkey::new_unique();
+        let proof = ProofState {
+            balance: 0,
+            challenge: [0; 32],
+            last_hash_at: 0,
+            referrer,
+            referral_expires_at: 100,
+            bump: 0,
+        };
+        assert_eq!(proof.active_referrer(99), Some(referrer));
+        assert_eq!(proof.active_referrer(100), None);
+        let proof = ProofState {
+            referral_expires_at: 0,
+            ..proof
+        };
+        assert_eq!(proof.active_referrer(100), Some(referrer));
+        let proof = ProofState {
+            referrer: Pubkey::default(),
+            ..proof
+        };
+        assert_eq!(proof.active_referrer(99), None);
+    }
+
+    #[test]
+    fn test_submit_instructions_pass_the_referrer() {
+        let signer = Pubkey::new_unique();
+        let referrer = Pubkey::new_unique();
+        for resource in [Resource::Coal, Resource::Wood] {
+            let solution = Solution::new([0; 16], [0; 8]);
+            let ixs = submit_instructions(resource, signer, signer, 0, solution, Some(referrer), None, Bumps::default());
+            let mine_ix = ixs.last().unwrap();
+            assert!(mine_ix
+                .accounts
+                .iter()
+                .any(|account| account.pubkey.eq(&referrer) && account.is_writable));
+        }
+    }
+
+    #[test]
+    fn test_reset_and_mine_fit_in_a_transaction() {
+        // Leave room for an ORE proof and a compute unit price.
+        let headroom = (32 + 1) + 12;
+        let signer = Keypair::new();
+        for resource in [Resource::Coal, Resource::Wood] {
+            let ixs = submit_instructions(
+                resource,
+                signer.pubkey(),
+                signer.pubkey(),
+                MAX_BUS_COUNT - 1,
+                Solution::new([0; 16], [0; 8]),
+                Some(Pubkey::new_unique()),
+                Some(MAX_BUS_COUNT),
+                Bumps::default(),
+            );
+            let tx = Transaction::new_signed_with_payer(
+                &ixs,
+                Some(&signer.pubkey()),
+                &[&signer],
+                Hash::default(),
+            );
+            let size = bincode::serialize(&tx).unwrap().len();
+            assert!(size.saturating_add(headroom).le(&PACKET_DATA_SIZE));
+        }
+    }
+}
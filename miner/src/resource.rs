@@ -0,0 +1,42 @@
+use clap::ValueEnum;
+use coal_api::consts::*;
+use solana_sdk::pubkey::Pubkey;
+
+/// The resource to mine.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum Resource {
+    #[default]
+    Coal,
+    Wood,
+}
+
+impl Resource {
+    pub fn mint(&self) -> Pubkey {
+        match self {
+            Resource::Coal => COAL_MINT_ADDRESS,
+            Resource::Wood => WOOD_MINT_ADDRESS,
+        }
+    }
+
+    pub fn config_address(&self) -> Pubkey {
+        match self {
+            Resource::Coal => COAL_CONFIG_ADDRESS,
+            Resource::Wood => WOOD_CONFIG_ADDRESS,
+        }
+    }
+
+    pub fn bus_address(&self, id: u64) -> Pubkey {
+        match self {
+            Resource::Coal => coal_bus_address(id),
+            Resource::Wood => wood_bus_address(id),
+        }
+    }
+
+    pub fn proof_address(&self, authority: Pubkey) -> Pubkey {
+        let seed = match self {
+            Resource::Coal => COAL_PROOF,
+            Resource::Wood => WOOD_PROOF,
+        };
+        Pubkey::find_program_address(&[seed, authority.as_ref()], &coal_api::id()).0
+    }
+}
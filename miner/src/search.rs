@@ -0,0 +1,96 @@
+use std::{
+    sync::atomic::{AtomicU32, Ordering},
+    thread,
+    time::Instant,
+};
+
+use drillx::{equix::SolverMemory, Solution};
+
+/// The best solution found by a search.
+pub struct SearchResult {
+    pub solution: Solution,
+    pub difficulty: u32,
+    pub hashes: u64,
+}
+
+/// Searches for the hardest solution of the challenge across the given number of threads.
+///
+/// Each thread hashes its own slice of the nonce space. The search stops at the deadline, unless no
+/// solution meets the minimum difficulty yet, in which case it continues until one does.
+pub fn search(challenge: &[u8; 32], min_difficulty: u32, cores: u64, deadline: Instant) -> SearchResult {
+    let cores = cores.max(1);
+    let min_difficulty = min_difficulty.max(1);
+    let global_best = AtomicU32::new(0);
+    let results: Vec<SearchResult> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..cores)
+            .map(|core| {
+                let global_best = &global_best;
+                scope.spawn(move || {
+                    let mut memory = SolverMemory::new();
+                    let mut nonce = nonce_start(core, cores);
+                    let mut best = SearchResult {
+                        solution: Solution::new([0; 16], [0; 8]),
+                        difficulty: 0,
+                        hashes: 0,
+                    };
+                    loop {
+                        for hash in drillx::hashes_with_memory(&mut memory, challenge, &nonce.to_le_bytes()) {
+                            best.hashes += 1;
+                            let difficulty = hash.difficulty();
+                            if difficulty.gt(&best.difficulty) {
+                                best.solution = Solution::new(hash.d, nonce.to_le_bytes());
+                                best.difficulty = difficulty;
+                                global_best.fetch_max(difficulty, Ordering::Relaxed);
+                            }
+                        }
+                        nonce = nonce.wrapping_add(1);
+                        if Instant::now().ge(&deadline)
+                            && global_best.load(Ordering::Relaxed).ge(&min_difficulty)
+                        {
+                            return best;
+                        }
+                    }
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("hashing thread panicked"))
+            .collect()
+    });
+
+    // Pick the hardest solution across threads.
+    let hashes = results.iter().map(|result| result.hashes).sum();
+    let best = results
+        .into_iter()
+        .max_by_key(|result| result.difficulty)
+        .expect("at least one hashing thread");
+    SearchResult { hashes, ..best }
+}
+
+/// Returns the first nonce of a thread, splitting the nonce space evenly across threads.
+fn nonce_start(core: u64, cores: u64) -> u64 {
+    (u64::MAX / cores).saturating_mul(core)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nonce_start() {
+        assert_eq!(nonce_start(0, 4), 0);
+        assert_eq!(nonce_start(1, 4), u64::MAX / 4);
+        assert_eq!(nonce_start(0, 1), 0);
+    }
+
+    #[test]
+    fn test_search_meets_min_difficulty_after_deadline() {
+        let challenge = [3; 32];
+        let result = search(&challenge, 4, 2, Instant::now());
+        assert!(result.difficulty.ge(&4));
+        assert!(result.hashes.gt(&0));
+        assert!(result.solution.is_valid(&challenge));
+        assert_eq!(result.solution.to_hash().difficulty(), result.difficulty);
+    }
+}
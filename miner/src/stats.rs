@@ -0,0 +1,96 @@
+use std::{
+    fs::OpenOptions,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+/// The header of the stats file.
+const HEADER: &str = "timestamp,bus,difficulty,hashes,reward,signature";
+
+/// A single submitted solution.
+pub struct Submission {
+    pub timestamp: i64,
+    pub bus: u64,
+    pub difficulty: u32,
+    pub hashes: u64,
+    pub reward: u64,
+    pub signature: String,
+}
+
+impl Submission {
+    fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{}",
+            self.timestamp, self.bus, self.difficulty, self.hashes, self.reward, self.signature
+        )
+    }
+}
+
+/// Totals of the current session, also appended submission by submission to a CSV file so they
+/// survive restarts.
+pub struct Stats {
+    path: PathBuf,
+    pub submissions: u64,
+    pub hashes: u64,
+    pub rewards: u64,
+    pub best_difficulty: u32,
+}
+
+impl Stats {
+    pub fn new(path: PathBuf) -> Self {
+        Stats {
+            path,
+            submissions: 0,
+            hashes: 0,
+            rewards: 0,
+            best_difficulty: 0,
+        }
+    }
+
+    /// Adds the submission to the session totals and appends it to the stats file.
+    pub fn record(&mut self, submission: &Submission) -> io::Result<()> {
+        self.submissions += 1;
+        self.hashes = self.hashes.saturating_add(submission.hashes);
+        self.rewards = self.rewards.saturating_add(submission.reward);
+        self.best_difficulty = self.best_difficulty.max(submission.difficulty);
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        if file.metadata()?.len().eq(&0) {
+            writeln!(file, "{}", HEADER)?;
+        }
+        writeln!(file, "{}", submission.to_csv())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let path = std::env::temp_dir().join(format!("coal-miner-stats-{}.csv", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut stats = Stats::new(path.clone());
+        for difficulty in [12, 9] {
+            stats
+                .record(&Submission {
+                    timestamp: 1_000,
+                    bus: 2,
+                    difficulty,
+                    hashes: 500,
+                    reward: 40,
+                    signature: "sig".to_string(),
+                })
+                .unwrap();
+        }
+        assert_eq!(stats.submissions, 2);
+        assert_eq!(stats.hashes, 1_000);
+        assert_eq!(stats.rewards, 80);
+        assert_eq!(stats.best_difficulty, 12);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines, vec![HEADER, "1000,2,12,500,40,sig", "1000,2,9,500,40,sig"]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,51 @@
+use coal_api::consts::{ONE_MINUTE, TOLERANCE};
+
+/// Returns the number of seconds to hash for, leaving the buffer to submit the solution by the
+/// proof's target time of one minute after its last hash.
+pub fn hashing_seconds(last_hash_at: i64, buffer: i64, now: i64) -> u64 {
+    last_hash_at
+        .saturating_add(ONE_MINUTE)
+        .saturating_sub(buffer)
+        .saturating_sub(now)
+        .max(0) as u64
+}
+
+/// Returns the number of seconds to wait before submitting, so the solution does not land before the
+/// program accepts it and fail as spam.
+pub fn submit_delay(last_hash_at: i64, now: i64) -> u64 {
+    last_hash_at
+        .saturating_add(ONE_MINUTE)
+        .saturating_sub(TOLERANCE)
+        .saturating_sub(now)
+        .max(0) as u64
+}
+
+/// Returns true if the epoch has ended and must be reset before mining.
+pub fn needs_reset(last_reset_at: i64, epoch_duration: i64, now: i64) -> bool {
+    last_reset_at.saturating_add(epoch_duration).le(&now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hashing_seconds() {
+        assert_eq!(hashing_seconds(1_000, 2, 1_000), (ONE_MINUTE - 2) as u64);
+        assert_eq!(hashing_seconds(1_000, 2, 1_050), (ONE_MINUTE - 52) as u64);
+        assert_eq!(hashing_seconds(1_000, 2, 1_100), 0);
+    }
+
+    #[test]
+    fn test_submit_delay() {
+        assert_eq!(submit_delay(1_000, 1_000), (ONE_MINUTE - TOLERANCE) as u64);
+        assert_eq!(submit_delay(1_000, 1_000 + ONE_MINUTE - TOLERANCE), 0);
+        assert_eq!(submit_delay(1_000, 1_100), 0);
+    }
+
+    #[test]
+    fn test_needs_reset() {
+        assert!(!needs_reset(1_000, 120, 1_119));
+        assert!(needs_reset(1_000, 120, 1_120));
+    }
+}
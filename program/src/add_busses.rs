@@ -0,0 +1,90 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    loaders::*,
+    state::{Bus, Config, WoodConfig},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey, system_program,
+};
+
+use crate::utils::{create_pda, AccountDeserialize, AccountVersion, Discriminator};
+
+/// Add busses grows the number of bus accounts distributing rewards for a resource.
+///
+/// New busses start the epoch empty and are funded at the next reset.
+pub fn process_add_busses(
+    accounts: &[AccountInfo<'_>],
+    _data: &[u8],
+) -> ProgramResult {
+    // Load accounts.
+    let (required_accounts, bus_infos) = accounts.split_at(accounts.len().min(3));
+    let [signer, config_info, system_program] = required_accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_program(system_program, system_program::id())?;
+
+    // Check signer.
+    if signer.key.ne(&INITIALIZER_ADDRESS) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Lookup the bus seed and current bus count of the resource.
+    let (seed, bus_count) = if config_info.data.borrow()[0].eq(&Config::discriminator()) {
+        load_coal_config(config_info, true)?;
+        let config_data = config_info.data.borrow();
+        (COAL_BUS, Config::try_from_bytes(&config_data)?.bus_count)
+    } else if config_info.data.borrow()[0].eq(&WoodConfig::discriminator()) {
+        load_wood_config(config_info, true)?;
+        let config_data = config_info.data.borrow();
+        (WOOD_BUS, WoodConfig::try_from_bytes(&config_data)?.bus_count)
+    } else {
+        return Err(ProgramError::InvalidAccountData);
+    };
+
+    // Validate the new bus count.
+    let new_bus_count = bus_count.saturating_add(bus_infos.len() as u64);
+    if bus_infos.is_empty() || new_bus_count.gt(&MAX_BUS_COUNT) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Initialize the new bus accounts.
+    for (i, bus_info) in bus_infos.iter().enumerate() {
+        let id = bus_count.saturating_add(i as u64);
+        let (address, bump) = Pubkey::find_program_address(&[seed, &[id as u8]], &coal_api::id());
+        if bus_info.key.ne(&address) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        load_system_account(bus_info, true)?;
+        create_pda(
+            bus_info,
+            &coal_api::id(),
+            8 + size_of::<Bus>(),
+            &[seed, &[id as u8], &[bump]],
+            system_program,
+            signer,
+        )?;
+        let mut bus_data = bus_info.try_borrow_mut_data()?;
+        bus_data[0] = Bus::discriminator();
+        bus_data[1] = Bus::version();
+        let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
+        bus.id = id;
+        bus.rewards = 0;
+        bus.theoretical_rewards = 0;
+        bus.top_balance = 0;
+        bus.bump = bump as u64;
+    }
+
+    // Update the bus count.
+    let mut config_data = config_info.data.borrow_mut();
+    if seed.eq(COAL_BUS) {
+        Config::try_from_bytes_mut(&mut config_data)?.bus_count = new_bus_count;
+    } else {
+        WoodConfig::try_from_bytes_mut(&mut config_data)?.bus_count = new_bus_count;
+    }
+
+    Ok(())
+}
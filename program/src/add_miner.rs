@@ -0,0 +1,86 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    error::CoalError,
+    instruction::AddMinerArgs,
+    loaders::*,
+    state::{MinerKey, MinerKeys},
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, pubkey::Pubkey, system_program, sysvar::Sysvar,
+};
+
+use crate::utils::{create_pda, AccountDeserialize, AccountVersion, Discriminator};
+
+/// Add miner authorizes an additional keypair to submit hashes for a proof account.
+///
+/// If the keypair is already authorized, its expiry and hash quota are updated.
+pub fn process_add_miner(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = AddMinerArgs::try_from_bytes(data)?;
+    let expires_at = i64::from_le_bytes(args.expires_at);
+    let hash_quota = u64::from_le_bytes(args.hash_quota);
+
+    // Load accounts.
+    let [signer, proof_info, miner_keys_info, miner_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any_proof_with_authority(proof_info, signer.key, false)?;
+    load_any(miner_info, false)?;
+    load_program(system_program, system_program::id())?;
+
+    // Initialize the miner keys account, if needed.
+    if miner_keys_info.data_is_empty() {
+        load_uninitialized_pda(
+            miner_keys_info,
+            &[MINER_KEYS, proof_info.key.as_ref()],
+            args.bump,
+            &coal_api::id(),
+        )?;
+        create_pda(
+            miner_keys_info,
+            &coal_api::id(),
+            8 + size_of::<MinerKeys>(),
+            &[MINER_KEYS, proof_info.key.as_ref(), &[args.bump]],
+            system_program,
+            signer,
+        )?;
+        let mut miner_keys_data = miner_keys_info.data.borrow_mut();
+        miner_keys_data[0] = MinerKeys::discriminator();
+        miner_keys_data[1] = MinerKeys::version();
+        let miner_keys = MinerKeys::try_from_bytes_mut(&mut miner_keys_data)?;
+        miner_keys.proof = *proof_info.key;
+    }
+    load_miner_keys(miner_keys_info, proof_info.key, true)?;
+
+    // Update the key if it is already authorized. Otherwise, take the first unused or expired slot.
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    let mut miner_keys_data = miner_keys_info.data.borrow_mut();
+    let miner_keys = MinerKeys::try_from_bytes_mut(&mut miner_keys_data)?;
+    let slot = match miner_keys
+        .keys
+        .iter()
+        .position(|key| key.miner.eq(miner_info.key))
+    {
+        Some(i) => i,
+        None => miner_keys
+            .keys
+            .iter()
+            .position(|key| {
+                key.miner.eq(&Pubkey::default()) || key.expires_at.le(&clock.unix_timestamp)
+            })
+            .ok_or(CoalError::TooManyMinerKeys)?,
+    };
+    miner_keys.keys[slot] = MinerKey {
+        miner: *miner_info.key,
+        expires_at,
+        hash_quota,
+        epoch_hashes: 0,
+        epoch_reset_at: 0,
+    };
+
+    Ok(())
+}
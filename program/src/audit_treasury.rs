@@ -0,0 +1,164 @@
+use coal_api::{
+    event::SolvencyEvent,
+    loaders::*,
+    state::{Bus, Config, WoodConfig},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::set_return_data,
+    program_error::ProgramError,
+};
+use spl_token_2022::{extension::StateWithExtensions, state::Account};
+
+use crate::utils::{AccountDeserialize, Discriminator};
+
+/// Audit treasury compares the liabilities owed to miners of a resource with the treasury token
+/// balance and logs the result. It is permissionless.
+pub fn process_audit_treasury(
+    accounts: &[AccountInfo<'_>],
+    _data: &[u8],
+) -> ProgramResult {
+    // Load accounts.
+    let (required_accounts, busses) = accounts.split_at(accounts.len().min(2));
+    let [config_info, treasury_tokens_info] = required_accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Read the liabilities recorded on the config.
+    //
+    // The jackpot is held in the treasury as well, so it is counted with the balances owed to miners.
+    let is_coal = config_info.data.borrow()[0].eq(&Config::discriminator());
+    let (outstanding_liabilities, jackpot, bus_count) = if is_coal {
+        load_coal_config(config_info, false)?;
+        load_coal_treasury_tokens(treasury_tokens_info, false)?;
+        let config_data = config_info.data.borrow();
+        let config = Config::try_from_bytes(&config_data)?;
+        (config.outstanding_liabilities, config.jackpot, config.bus_count)
+    } else if config_info.data.borrow()[0].eq(&WoodConfig::discriminator()) {
+        load_wood_config(config_info, false)?;
+        load_wood_treasury_tokens(treasury_tokens_info, false)?;
+        let config_data = config_info.data.borrow();
+        let config = WoodConfig::try_from_bytes(&config_data)?;
+        (config.outstanding_liabilities, config.jackpot, config.bus_count)
+    } else {
+        return Err(ProgramError::InvalidAccountData);
+    };
+
+    // Add the liabilities held on the busses.
+    //
+    // Every bus of the resource must be provided, ordered by id, since mining credits and jackpot
+    // shares are only moved onto the config at reset.
+    if (busses.len() as u64).ne(&bus_count) {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let mut bus_liabilities = vec![];
+    for (i, bus_info) in busses.iter().enumerate() {
+        if is_coal {
+            load_coal_bus(bus_info, i as u64, false)?;
+        } else {
+            load_wood_bus(bus_info, i as u64, false)?;
+        }
+        let bus_data = bus_info.data.borrow();
+        let bus = Bus::read_from_bytes(&bus_data)?;
+        bus_liabilities.push((bus.liabilities, bus.jackpot));
+    }
+    let outstanding_liabilities =
+        total_liabilities(outstanding_liabilities, jackpot, &bus_liabilities)?;
+
+    // Log the solvency of the treasury.
+    //
+    // This data can be used by off-chain monitors to alert on drift between the treasury balance and
+    // the balances owed to miners.
+    let treasury_balance = StateWithExtensions::<Account>::unpack(&treasury_tokens_info.data.borrow())?
+        .base
+        .amount;
+    set_return_data(
+        SolvencyEvent {
+            outstanding_liabilities,
+            treasury_balance,
+            surplus: surplus(treasury_balance, outstanding_liabilities),
+        }
+        .to_bytes(),
+    );
+
+    Ok(())
+}
+
+/// Returns the total owed to miners from the liabilities and jackpot on the config, and the
+/// liabilities and jackpot share of each bus.
+///
+/// Errors if the total is negative, which means more was paid out than was ever credited.
+fn total_liabilities(
+    outstanding_liabilities: i64,
+    jackpot: u64,
+    bus_liabilities: &[(u64, u64)],
+) -> Result<u64, ProgramError> {
+    let total = bus_liabilities.iter().fold(
+        (outstanding_liabilities as i128).saturating_add(jackpot as i128),
+        |total, (liabilities, jackpot)| {
+            total
+                .saturating_add(*liabilities as i128)
+                .saturating_add(*jackpot as i128)
+        },
+    );
+    u64::try_from(total).or(Err(ProgramError::ArithmeticOverflow))
+}
+
+/// Returns the treasury balance in excess of the liabilities, saturating at the bounds of an i64.
+fn surplus(treasury_balance: u64, outstanding_liabilities: u64) -> i64 {
+    let surplus = (treasury_balance as i128).saturating_sub(outstanding_liabilities as i128);
+    i64::try_from(surplus).unwrap_or(if surplus.is_negative() { i64::MIN } else { i64::MAX })
+}
+
+/// Returns the outstanding liabilities after crediting the amount to miners.
+pub(crate) fn add_liabilities(outstanding_liabilities: i64, amount: u64) -> Result<i64, ProgramError> {
+    i64::try_from(amount)
+        .ok()
+        .and_then(|amount| outstanding_liabilities.checked_add(amount))
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+/// Returns the outstanding liabilities after paying the amount out to miners.
+pub(crate) fn sub_liabilities(outstanding_liabilities: i64, amount: u64) -> Result<i64, ProgramError> {
+    i64::try_from(amount)
+        .ok()
+        .and_then(|amount| outstanding_liabilities.checked_sub(amount))
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total_liabilities() {
+        assert_eq!(total_liabilities(1_000, 50, &[(10, 5), (20, 5)]), Ok(1_090));
+        assert_eq!(total_liabilities(0, 0, &[]), Ok(0));
+    }
+
+    #[test]
+    fn test_total_liabilities_with_claims_of_current_epoch() {
+        // Rewards credited by the busses this epoch were claimed before the reset moved them onto
+        // the config.
+        assert_eq!(total_liabilities(-300, 0, &[(200, 0), (150, 0)]), Ok(50));
+        assert_eq!(
+            total_liabilities(-300, 0, &[(200, 0)]),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn test_surplus() {
+        assert_eq!(surplus(1_000, 400), 600);
+        assert_eq!(surplus(400, 1_000), -600);
+        assert_eq!(surplus(u64::MAX, 0), i64::MAX);
+        assert_eq!(surplus(0, u64::MAX), i64::MIN);
+    }
+
+    #[test]
+    fn test_liabilities_arithmetic() {
+        assert_eq!(add_liabilities(-100, 250), Ok(150));
+        assert_eq!(sub_liabilities(100, 250), Ok(-150));
+        assert_eq!(add_liabilities(0, u64::MAX), Err(ProgramError::ArithmeticOverflow));
+        assert_eq!(sub_liabilities(i64::MIN, 1), Err(ProgramError::ArithmeticOverflow));
+    }
+}
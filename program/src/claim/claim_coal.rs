@@ -1,10 +1,10 @@
-use coal_api::{consts::*, error::CoalError, instruction::ClaimArgs, loaders::*, state::Proof};
+use coal_api::{consts::*, error::CoalError, instruction::ClaimArgs, loaders::*, state::{Config, Proof}};
 use coal_utils::spl::transfer_signed;
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
 };
 
-use crate::utils::AccountDeserialize;
+use crate::{utils::AccountDeserialize, vest};
 
 pub fn process_claim_coal(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     // Parse args.
@@ -12,17 +12,29 @@ pub fn process_claim_coal(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
     let amount = u64::from_le_bytes(args.amount);
 
     // Load accounts.
-    let [signer, beneficiary_info, proof_info, treasury_info, treasury_tokens_info, token_program] =
-        accounts
+    let (required_accounts, optional_accounts) = accounts.split_at(accounts.len().min(8));
+    let [signer, beneficiary_info, proof_info, config_info, treasury_info, treasury_tokens_info, token_program, mint_info] =
+        required_accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     load_signer(signer)?;
+
+    // Upgrade a proof which predates the latest layout. The signer covers any additional rent.
+    crate::upgrade_in_place(
+        proof_info,
+        signer,
+        optional_accounts.get(1),
+        std::slice::from_ref(config_info),
+    )?;
+
     load_token_account(beneficiary_info, None, &COAL_MINT_ADDRESS, true)?;
     load_coal_proof(proof_info, signer.key, true)?;
+    load_coal_config(config_info, true)?;
     load_treasury(treasury_info, false)?;
     load_coal_treasury_tokens(treasury_tokens_info, true)?;
-    load_program(token_program, spl_token::id())?;
+    load_mint(mint_info, COAL_MINT_ADDRESS, false)?;
+    load_token_program(token_program, mint_info)?;
 
     // Update miner balance.
     let mut proof_data = proof_info.data.borrow_mut();
@@ -32,11 +44,37 @@ pub fn process_claim_coal(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
         .checked_sub(amount)
         .ok_or(CoalError::ClaimTooLarge)?;
 
+    // Lock the claim in the vesting account, if the proof vests its claims.
+    //
+    // Only the portion of a prior vesting schedule which has already unlocked is paid out now.
+    let amount = if proof.vesting_duration.gt(&0) {
+        let [vesting_info, system_program] = optional_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        vest(
+            signer,
+            proof_info.key,
+            vesting_info,
+            system_program,
+            &COAL_MINT_ADDRESS,
+            amount,
+            proof.vesting_duration,
+        )?
+    } else {
+        amount
+    };
+
+    // Release the liability owed to the miner.
+    let mut config_data = config_info.data.borrow_mut();
+    let config = Config::try_from_bytes_mut(&mut config_data)?;
+    config.outstanding_liabilities = crate::sub_liabilities(config.outstanding_liabilities, amount)?;
+
     // Transfer tokens from treasury to beneficiary.
     transfer_signed(
         treasury_info,
         treasury_tokens_info,
         beneficiary_info,
+        mint_info,
         token_program,
         amount,
         &[&[TREASURY, &[TREASURY_BUMP]]],
@@ -1,7 +1,15 @@
-use coal_api::state::{Proof, ProofV2};
-use coal_utils::Discriminator;
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    error::CoalError,
+    loaders::*,
+    state::{Proof, ProofV2, Vesting},
+};
+use coal_utils::{create_pda, AccountDeserialize, AccountVersion, Discriminator};
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, pubkey::Pubkey, system_program, sysvar::Sysvar,
 };
 
 use claim_coal::*;
@@ -10,16 +18,183 @@ mod claim_coal;
 mod claim_wood;
 
 /// Claim distributes claimable ORE from the treasury to a miner.
-pub fn process_claim<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+pub fn process_claim(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     let proof_info = &accounts[2];
 
-    if proof_info.data.borrow()[0].eq(&(Proof::discriminator() as u8)) {
+    if proof_info.data.borrow()[0].eq(&Proof::discriminator()) {
         return process_claim_coal(accounts, data)
     }
 
-    if proof_info.data.borrow()[0].eq(&(ProofV2::discriminator() as u8)) {
+    if proof_info.data.borrow()[0].eq(&ProofV2::discriminator()) {
         return process_claim_wood(accounts, data)
     }
 
-    return Err(solana_program::program_error::ProgramError::InvalidAccountData);
-}
\ No newline at end of file
+    Err(solana_program::program_error::ProgramError::InvalidAccountData)
+}
+
+/// Locks a claimed amount in the vesting account of a proof, creating the account if needed.
+///
+/// The portion of any existing schedule which has already unlocked is returned to be paid out. The
+/// locked remainder is combined with the new amount, ending at the average of their end times
+/// weighted by amount.
+pub(crate) fn vest<'a, 'info>(
+    signer: &'a AccountInfo<'info>,
+    proof: &Pubkey,
+    vesting_info: &'a AccountInfo<'info>,
+    system_program: &'a AccountInfo<'info>,
+    mint: &Pubkey,
+    amount: u64,
+    duration: i64,
+) -> Result<u64, ProgramError> {
+    // Initialize the vesting account, if needed.
+    if vesting_info.data_is_empty() {
+        load_program(system_program, system_program::id())?;
+        let (address, bump) = Pubkey::find_program_address(&[VESTING, proof.as_ref()], &coal_api::id());
+        if vesting_info.key.ne(&address) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        create_pda(
+            vesting_info,
+            &coal_api::id(),
+            8 + size_of::<Vesting>(),
+            &[VESTING, proof.as_ref(), &[bump]],
+            system_program,
+            signer,
+        )?;
+        let mut vesting_data = vesting_info.data.borrow_mut();
+        vesting_data[0] = Vesting::discriminator();
+        vesting_data[1] = Vesting::version();
+        let vesting = Vesting::try_from_bytes_mut(&mut vesting_data)?;
+        vesting.authority = *signer.key;
+        vesting.proof = *proof;
+        vesting.mint = *mint;
+        vesting.bump = bump as u64;
+    }
+
+    // Validate the vesting account. The authority may have changed hands with the proof.
+    if vesting_info.owner.ne(&coal_api::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    let mut vesting_data = vesting_info.data.borrow_mut();
+    let vesting = Vesting::try_from_bytes_mut(&mut vesting_data)?;
+    load_pda(vesting_info, &[VESTING, proof.as_ref()], vesting.bump)?;
+    if vesting.proof.ne(proof) || vesting.mint.ne(mint) || !vesting_info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Add the new amount to the schedule.
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    vesting.authority = *signer.key;
+    Ok(vesting.add(clock.unix_timestamp, amount, duration))
+}
+
+/// Closes the vesting account of a proof, if it exists, and sends its rent to the recipient.
+///
+/// Vesting accounts are seeded by the proof address, so they cannot follow a proof which moves to a
+/// new authority. Errors if any tokens have not been released yet.
+pub(crate) fn close_vesting<'info>(
+    vesting_info: &AccountInfo<'info>,
+    proof_info: &AccountInfo<'info>,
+    recipient_info: &AccountInfo<'info>,
+) -> ProgramResult {
+    if !has_released_vesting(vesting_info, proof_info.key)? {
+        return Ok(());
+    }
+
+    // Realloc data to zero.
+    vesting_info.realloc(0, true)?;
+
+    // Send remaining lamports to the recipient.
+    **recipient_info.lamports.borrow_mut() += vesting_info.lamports();
+    **vesting_info.lamports.borrow_mut() = 0;
+
+    Ok(())
+}
+
+/// Returns whether the vesting account of a proof was ever created.
+///
+/// Errors if the account is not the vesting PDA of the proof, holds the schedule of another proof,
+/// or has tokens which have not been released yet.
+fn has_released_vesting(vesting_info: &AccountInfo, proof: &Pubkey) -> Result<bool, ProgramError> {
+    let vesting_address =
+        Pubkey::find_program_address(&[VESTING, proof.as_ref()], &coal_api::id()).0;
+    if vesting_info.key.ne(&vesting_address) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if vesting_info.data_is_empty() {
+        return Ok(false);
+    }
+    if vesting_info.owner.ne(&coal_api::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    let vesting_data = vesting_info.data.borrow();
+    let vesting = Vesting::try_from_bytes(&vesting_data)?;
+    if vesting.proof.ne(proof) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if vesting.amount.gt(&vesting.released) {
+        return Err(CoalError::VestingNotReleased.into());
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+
+    use super::*;
+
+    /// Serializes a vesting account of a proof.
+    fn vesting_data(proof: Pubkey, amount: u64, released: u64) -> Vec<u8> {
+        let mut data = vec![0; 8];
+        data[0] = Vesting::discriminator();
+        data.extend_from_slice(bytemuck::bytes_of(&Vesting {
+            proof,
+            amount,
+            released,
+            ..Vesting::zeroed()
+        }));
+        data
+    }
+
+    /// Runs has_released_vesting on the account data at the vesting address of the proof.
+    fn run_has_released_vesting(proof: Pubkey, data: &mut [u8]) -> Result<bool, ProgramError> {
+        let owner = coal_api::id();
+        let address = Pubkey::find_program_address(&[VESTING, proof.as_ref()], &owner).0;
+        let mut lamports = 500;
+        let info = AccountInfo::new(&address, false, true, &mut lamports, data, &owner, false, 0);
+        has_released_vesting(&info, &proof)
+    }
+
+    #[test]
+    fn test_has_released_vesting() {
+        let proof = Pubkey::new_unique();
+        let mut data = vesting_data(proof, 1_000, 1_000);
+        assert_eq!(run_has_released_vesting(proof, &mut data), Ok(true));
+    }
+
+    #[test]
+    fn test_has_released_vesting_skips_uncreated_account() {
+        assert_eq!(run_has_released_vesting(Pubkey::new_unique(), &mut []), Ok(false));
+    }
+
+    #[test]
+    fn test_has_released_vesting_rejects_unreleased_tokens() {
+        let proof = Pubkey::new_unique();
+        let mut data = vesting_data(proof, 1_000, 999);
+        assert_eq!(
+            run_has_released_vesting(proof, &mut data),
+            Err(CoalError::VestingNotReleased.into())
+        );
+    }
+
+    #[test]
+    fn test_has_released_vesting_rejects_schedule_of_other_proof() {
+        let proof = Pubkey::new_unique();
+        let mut data = vesting_data(Pubkey::new_unique(), 1_000, 1_000);
+        assert_eq!(
+            run_has_released_vesting(proof, &mut data),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+}
@@ -7,9 +7,9 @@ use solana_program::{
 use crate::utils::AccountDeserialize;
 
 /// Close closes a proof account and returns the rent to the owner.
-pub fn process_close_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data: &[u8]) -> ProgramResult {
+pub fn process_close_coal(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
-    let [signer, proof_info, system_program] = accounts else {
+    let [signer, proof_info, system_program, miner_keys_info] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     load_signer(signer)?;
@@ -18,12 +18,15 @@ pub fn process_close_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
 
     // Validate balance is zero.
     let proof_data = proof_info.data.borrow();
-    let proof = Proof::try_from_bytes(&proof_data)?;
+    let proof = Proof::read_from_bytes(&proof_data)?;
     if proof.balance.gt(&0) {
         return Err(ProgramError::InvalidAccountData);
     }
     drop(proof_data);
 
+    // Close the miner keys, if any.
+    crate::close_miner_keys(miner_keys_info, proof_info, signer)?;
+
     // Realloc data to zero.
     proof_info.realloc(0, true)?;
 
@@ -0,0 +1,109 @@
+use coal_api::{
+    consts::*,
+    error::CoalError,
+    instruction::CloseAndClaimArgs,
+    loaders::*,
+    state::{Config, Proof},
+};
+use coal_utils::spl::transfer_signed;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    system_program,
+};
+
+use crate::{unequip_tool, utils::AccountDeserialize};
+
+/// Close and claim pays out the full coal proof balance, unequips any tool, then closes the proof
+/// account and returns the rent to the owner.
+pub fn process_close_and_claim_coal(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = CloseAndClaimArgs::try_from_bytes(data)?;
+
+    // Load accounts.
+    let (required_accounts, optional_accounts) = accounts.split_at(accounts.len().min(11));
+    let [signer, beneficiary_info, proof_info, config_info, treasury_info, treasury_tokens_info, token_program, system_program, mint_info, miner_keys_info, tool_info] =
+        required_accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_token_account(beneficiary_info, None, &COAL_MINT_ADDRESS, true)?;
+    load_coal_proof(proof_info, signer.key, true)?;
+    load_coal_config(config_info, true)?;
+    load_treasury(treasury_info, false)?;
+    load_coal_treasury_tokens(treasury_tokens_info, true)?;
+    load_mint(mint_info, COAL_MINT_ADDRESS, false)?;
+    load_token_program(token_program, mint_info)?;
+    load_program(system_program, system_program::id())?;
+    load_pda(tool_info, &[COAL_MAIN_HAND_TOOL, signer.key.as_ref()], args.tool_bump as u64)?;
+
+    // Clear the miner balance.
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+    if proof.vesting_duration.gt(&0) {
+        return Err(CoalError::VestingEnabled.into());
+    }
+    let amount = proof.balance;
+    let miner = proof.miner;
+    proof.balance = 0;
+    drop(proof_data);
+
+    // Release the liability owed to the miner.
+    let mut config_data = config_info.data.borrow_mut();
+    let config = Config::try_from_bytes_mut(&mut config_data)?;
+    config.outstanding_liabilities = crate::sub_liabilities(config.outstanding_liabilities, amount)?;
+    drop(config_data);
+
+    // Transfer the full balance from treasury to beneficiary.
+    if amount.gt(&0) {
+        transfer_signed(
+            treasury_info,
+            treasury_tokens_info,
+            beneficiary_info,
+            mint_info,
+            token_program,
+            amount,
+            &[&[TREASURY, &[TREASURY_BUMP]]],
+        )?;
+    }
+
+    // Unequip the tool, if any.
+    //
+    // An equipped tool must be unequipped, so a closed proof never leaves a tool equipped behind it.
+    if !tool_info.data_is_empty() {
+        let [asset_info, collection_info, plugin_update_authority, mpl_core_program] =
+            optional_accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        load_program(mpl_core_program, mpl_core::ID)?;
+        unequip_tool(
+            signer,
+            &miner,
+            signer,
+            asset_info,
+            collection_info,
+            tool_info,
+            plugin_update_authority,
+            mpl_core_program,
+            system_program,
+            args.tool_bump,
+            args.plugin_authority_bump,
+        )?;
+    }
+
+    // Close the miner keys, if any.
+    crate::close_miner_keys(miner_keys_info, proof_info, signer)?;
+
+    // Realloc data to zero.
+    proof_info.realloc(0, true)?;
+
+    // Send remaining lamports to signer.
+    **signer.lamports.borrow_mut() += proof_info.lamports();
+    **proof_info.lamports.borrow_mut() = 0;
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use coal_api::{consts::*, error::CoalError, loaders::*, state::{ProofV2, WoodConfig}};
+use coal_utils::spl::transfer_signed;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    system_program,
+};
+
+use crate::utils::AccountDeserialize;
+
+/// Close and claim pays out the full wood proof balance, then closes the proof account and
+/// returns the rent to the owner.
+pub fn process_close_and_claim_wood(
+    accounts: &[AccountInfo<'_>],
+    _data: &[u8],
+) -> ProgramResult {
+    // Load accounts.
+    let [signer, beneficiary_info, proof_info, config_info, treasury_info, treasury_tokens_info, token_program, system_program, mint_info, miner_keys_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_token_account(beneficiary_info, None, &WOOD_MINT_ADDRESS, true)?;
+    load_proof_v2(proof_info, signer.key, &WOOD_MINT_ADDRESS, true)?;
+    load_wood_config(config_info, true)?;
+    load_treasury(treasury_info, false)?;
+    load_wood_treasury_tokens(treasury_tokens_info, true)?;
+    load_mint(mint_info, WOOD_MINT_ADDRESS, false)?;
+    load_token_program(token_program, mint_info)?;
+    load_program(system_program, system_program::id())?;
+
+    // Clear the miner balance.
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = ProofV2::try_from_bytes_mut(&mut proof_data)?;
+    if proof.vesting_duration.gt(&0) {
+        return Err(CoalError::VestingEnabled.into());
+    }
+    let amount = proof.balance;
+    proof.balance = 0;
+    drop(proof_data);
+
+    // Release the liability owed to the miner.
+    let mut config_data = config_info.data.borrow_mut();
+    let config = WoodConfig::try_from_bytes_mut(&mut config_data)?;
+    config.outstanding_liabilities = crate::sub_liabilities(config.outstanding_liabilities, amount)?;
+    drop(config_data);
+
+    // Transfer the full balance from treasury to beneficiary.
+    if amount.gt(&0) {
+        transfer_signed(
+            treasury_info,
+            treasury_tokens_info,
+            beneficiary_info,
+            mint_info,
+            token_program,
+            amount,
+            &[&[TREASURY, &[TREASURY_BUMP]]],
+        )?;
+    }
+
+    // Close the miner keys, if any.
+    crate::close_miner_keys(miner_keys_info, proof_info, signer)?;
+
+    // Realloc data to zero.
+    proof_info.realloc(0, true)?;
+
+    // Send remaining lamports to signer.
+    **signer.lamports.borrow_mut() += proof_info.lamports();
+    **proof_info.lamports.borrow_mut() = 0;
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use coal_api::state::{Proof, ProofV2};
+use coal_utils::Discriminator;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use close_and_claim_coal::*;
+use close_and_claim_wood::*;
+mod close_and_claim_coal;
+mod close_and_claim_wood;
+
+/// Close and claim pays out the full proof balance, then closes the proof account and returns the rent to the owner.
+pub fn process_close_and_claim(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let Some(proof_info) = accounts.get(2) else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if proof_info.data.borrow()[0].eq(&Proof::discriminator()) {
+        return process_close_and_claim_coal(accounts, data)
+    }
+
+    if proof_info.data.borrow()[0].eq(&ProofV2::discriminator()) {
+        return process_close_and_claim_wood(accounts, data)
+    }
+
+    Err(ProgramError::InvalidAccountData)
+}
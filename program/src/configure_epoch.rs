@@ -0,0 +1,59 @@
+use coal_api::{
+    consts::*,
+    instruction::ConfigureEpochArgs,
+    loaders::*,
+    state::{Config, WoodConfig},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::utils::{AccountDeserialize, Discriminator};
+
+/// Configure epoch updates the epoch duration of a resource. The new duration applies from the
+/// current epoch, and the epoch rewards funded at the next reset scale with it.
+pub fn process_configure_epoch(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = ConfigureEpochArgs::try_from_bytes(data)?;
+    let epoch_duration = i64::from_le_bytes(args.epoch_duration);
+
+    // Load accounts.
+    let [signer, config_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+
+    // Check signer.
+    if signer.key.ne(&INITIALIZER_ADDRESS) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Validate args.
+    if epoch_duration.lt(&MIN_EPOCH_DURATION) || epoch_duration.gt(&MAX_EPOCH_DURATION) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Update the config.
+    if config_info.data.borrow()[0].eq(&Config::discriminator()) {
+        load_coal_config(config_info, true)?;
+        let mut config_data = config_info.data.borrow_mut();
+        let config = Config::try_from_bytes_mut(&mut config_data)?;
+        config.epoch_duration = epoch_duration;
+        config.late_resets = 0;
+        return Ok(());
+    }
+
+    if config_info.data.borrow()[0].eq(&WoodConfig::discriminator()) {
+        load_wood_config(config_info, true)?;
+        let mut config_data = config_info.data.borrow_mut();
+        let config = WoodConfig::try_from_bytes_mut(&mut config_data)?;
+        config.epoch_duration = epoch_duration;
+        config.late_resets = 0;
+        return Ok(());
+    }
+
+    Err(ProgramError::InvalidAccountData)
+}
@@ -0,0 +1,60 @@
+use coal_api::{
+    consts::*,
+    instruction::ConfigureJackpotArgs,
+    loaders::*,
+    state::{Config, WoodConfig},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::utils::{AccountDeserialize, Discriminator};
+
+/// Configure jackpot updates the share of emissions held for the jackpot of a resource and the
+/// difficulty a hash must reach to win it.
+pub fn process_configure_jackpot(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = ConfigureJackpotArgs::try_from_bytes(data)?;
+    let jackpot_bps = u64::from_le_bytes(args.jackpot_bps);
+    let jackpot_difficulty = u64::from_le_bytes(args.jackpot_difficulty);
+
+    // Load accounts.
+    let [signer, config_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+
+    // Check signer.
+    if signer.key.ne(&INITIALIZER_ADDRESS) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Validate args.
+    if jackpot_bps.gt(&MAX_JACKPOT_BPS) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Update the config.
+    if config_info.data.borrow()[0].eq(&Config::discriminator()) {
+        load_coal_config(config_info, true)?;
+        let mut config_data = config_info.data.borrow_mut();
+        let config = Config::try_from_bytes_mut(&mut config_data)?;
+        config.jackpot_bps = jackpot_bps;
+        config.jackpot_difficulty = jackpot_difficulty;
+        return Ok(());
+    }
+
+    if config_info.data.borrow()[0].eq(&WoodConfig::discriminator()) {
+        load_wood_config(config_info, true)?;
+        let mut config_data = config_info.data.borrow_mut();
+        let config = WoodConfig::try_from_bytes_mut(&mut config_data)?;
+        config.jackpot_bps = jackpot_bps;
+        config.jackpot_difficulty = jackpot_difficulty;
+        return Ok(());
+    }
+
+    Err(ProgramError::InvalidAccountData)
+}
@@ -0,0 +1,60 @@
+use coal_api::{
+    consts::*,
+    instruction::ConfigureReapingArgs,
+    loaders::*,
+    state::{Config, WoodConfig},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::utils::{AccountDeserialize, Discriminator};
+
+/// Configure reaping updates when abandoned proofs of a resource can be reaped and the share of
+/// their rent paid to the reaper.
+pub fn process_configure_reaping(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = ConfigureReapingArgs::try_from_bytes(data)?;
+    let reap_after = i64::from_le_bytes(args.reap_after);
+    let reaper_bonus_bps = u64::from_le_bytes(args.reaper_bonus_bps);
+
+    // Load accounts.
+    let [signer, config_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+
+    // Check signer.
+    if signer.key.ne(&INITIALIZER_ADDRESS) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Validate args.
+    if reap_after.lt(&0) || reaper_bonus_bps.gt(&MAX_REAPER_BONUS_BPS) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Update the config.
+    if config_info.data.borrow()[0].eq(&Config::discriminator()) {
+        load_coal_config(config_info, true)?;
+        let mut config_data = config_info.data.borrow_mut();
+        let config = Config::try_from_bytes_mut(&mut config_data)?;
+        config.reap_after = reap_after;
+        config.reaper_bonus_bps = reaper_bonus_bps;
+        return Ok(());
+    }
+
+    if config_info.data.borrow()[0].eq(&WoodConfig::discriminator()) {
+        load_wood_config(config_info, true)?;
+        let mut config_data = config_info.data.borrow_mut();
+        let config = WoodConfig::try_from_bytes_mut(&mut config_data)?;
+        config.reap_after = reap_after;
+        config.reaper_bonus_bps = reaper_bonus_bps;
+        return Ok(());
+    }
+
+    Err(ProgramError::InvalidAccountData)
+}
@@ -0,0 +1,59 @@
+use coal_api::{
+    consts::*,
+    instruction::ConfigureReferralsArgs,
+    loaders::*,
+    state::{Config, WoodConfig},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::utils::{AccountDeserialize, Discriminator};
+
+/// Configure referrals updates the referral bonus and duration of a resource config.
+pub fn process_configure_referrals(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = ConfigureReferralsArgs::try_from_bytes(data)?;
+    let bonus_bps = u64::from_le_bytes(args.bonus_bps);
+    let epochs = u64::from_le_bytes(args.epochs);
+
+    // Load accounts.
+    let [signer, config_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+
+    // Check signer.
+    if signer.key.ne(&INITIALIZER_ADDRESS) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Validate args.
+    if bonus_bps.gt(&MAX_REFERRAL_BONUS_BPS) || epochs.gt(&(i64::MAX as u64)) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Update the config.
+    if config_info.data.borrow()[0].eq(&Config::discriminator()) {
+        load_coal_config(config_info, true)?;
+        let mut config_data = config_info.data.borrow_mut();
+        let config = Config::try_from_bytes_mut(&mut config_data)?;
+        config.referral_bonus_bps = bonus_bps;
+        config.referral_epochs = epochs;
+        return Ok(());
+    }
+
+    if config_info.data.borrow()[0].eq(&WoodConfig::discriminator()) {
+        load_wood_config(config_info, true)?;
+        let mut config_data = config_info.data.borrow_mut();
+        let config = WoodConfig::try_from_bytes_mut(&mut config_data)?;
+        config.referral_bonus_bps = bonus_bps;
+        config.referral_epochs = epochs;
+        return Ok(());
+    }
+
+    Err(ProgramError::InvalidAccountData)
+}
@@ -0,0 +1,49 @@
+use coal_api::{
+    consts::*,
+    instruction::ConfigureSmeltingArgs,
+    loaders::*,
+    state::IngotConfig,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::utils::AccountDeserialize;
+
+/// Configure smelting updates the smelting recipe and per-epoch output cap.
+pub fn process_configure_smelting(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = ConfigureSmeltingArgs::try_from_bytes(data)?;
+    let coal_cost = u64::from_le_bytes(args.coal_cost);
+    let wood_cost = u64::from_le_bytes(args.wood_cost);
+    let epoch_output_cap = u64::from_le_bytes(args.epoch_output_cap);
+
+    // Load accounts.
+    let [signer, config_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_ingot_config(config_info, true)?;
+
+    // Check signer.
+    if signer.key.ne(&INITIALIZER_ADDRESS) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Validate args.
+    if coal_cost.eq(&0) && wood_cost.eq(&0) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Update the config.
+    let mut config_data = config_info.data.borrow_mut();
+    let config = IngotConfig::try_from_bytes_mut(&mut config_data)?;
+    config.coal_cost = coal_cost;
+    config.wood_cost = wood_cost;
+    config.epoch_output_cap = epoch_output_cap;
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use coal_api::{
+    consts::*,
+    instruction::ConfigureVestingArgs,
+    loaders::*,
+    state::{Proof, ProofV2},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::utils::{AccountDeserialize, Discriminator};
+
+/// Configure vesting sets the duration the claims of a proof vest over.
+///
+/// A duration of zero pays out future claims at once. Claims which are already vesting keep
+/// their schedule until the next claim restarts it.
+pub fn process_configure_vesting(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = ConfigureVestingArgs::try_from_bytes(data)?;
+    let duration = i64::from_le_bytes(args.duration);
+
+    // Load accounts.
+    let [signer, proof_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any_proof_with_authority(proof_info, signer.key, true)?;
+
+    // Validate args.
+    if duration.lt(&0) || duration.gt(&MAX_VESTING_DURATION) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Update the proof.
+    let mut proof_data = proof_info.data.borrow_mut();
+    if proof_data[0].eq(&Proof::discriminator()) {
+        Proof::try_from_bytes_mut(&mut proof_data)?.vesting_duration = duration;
+    } else {
+        ProofV2::try_from_bytes_mut(&mut proof_data)?.vesting_duration = duration;
+    }
+
+    Ok(())
+}
@@ -6,10 +6,10 @@ use solana_program::{
 };
 use mpl_core::instructions::TransferV1CpiBuilder;
 
-use crate::utils::{create_pda, AccountDeserialize, Discriminator};
+use crate::utils::{create_pda, AccountDeserialize, AccountVersion, Discriminator};
 
 /// Creates a new tool account and transfers the asset to the tool.
-pub fn process_equip_tool<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+pub fn process_equip_tool(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse args.
     let args = EquipArgs::try_from_bytes(data)?;
 
@@ -55,9 +55,11 @@ pub fn process_equip_tool<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &
 	msg!("multiplier: {}", multiplier);
 	
     let mut tool_data = tool_info.data.borrow_mut();
-    tool_data[0] = Tool::discriminator() as u8;
+    tool_data[0] = Tool::discriminator();
+    tool_data[1] = Tool::version();
     let tool = Tool::try_from_bytes_mut(&mut tool_data)?;
 	tool.authority = *signer.key;
+	tool.bump = args.bump as u64;
 	tool.miner = *miner_info.key;
 	tool.asset = *asset_info.key;
 	tool.durability = amount_f64_to_u64(durability);
@@ -0,0 +1,93 @@
+use coal_api::{
+    consts::*, error::CoalError, forge::forge_attributes, instruction::ForgeToolArgs, loaders::*,
+};
+use coal_utils::spl::burn;
+use mpl_core::{
+    instructions::CreateV2CpiBuilder,
+    types::{Attribute, Attributes, Plugin, PluginAuthorityPair},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    system_program,
+};
+
+/// Forge tool burns COAL and WOOD from the signer and mints a new tool into the forge collection.
+pub fn process_forge_tool(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = ForgeToolArgs::try_from_bytes(data)?;
+    let coal_amount = u64::from_le_bytes(args.coal_amount);
+    let wood_amount = u64::from_le_bytes(args.wood_amount);
+
+    // Load accounts.
+    let [signer, asset_info, collection_info, plugin_update_authority, coal_mint_info, wood_mint_info, coal_tokens_info, wood_tokens_info, token_program, mpl_core_program, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_signer(asset_info)?;
+    load_system_account(asset_info, true)?;
+    if collection_info.key.ne(&FORGE_COLLECTION_ADDRESS) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    load_mint(coal_mint_info, COAL_MINT_ADDRESS, true)?;
+    load_mint(wood_mint_info, WOOD_MINT_ADDRESS, true)?;
+    load_token_account(coal_tokens_info, Some(signer.key), &COAL_MINT_ADDRESS, true)?;
+    load_token_account(wood_tokens_info, Some(signer.key), &WOOD_MINT_ADDRESS, true)?;
+    load_program(token_program, spl_token::id())?;
+    load_program(mpl_core_program, mpl_core::ID)?;
+    load_program(system_program, system_program::id())?;
+
+    // Grade the tool.
+    let (durability, multiplier) =
+        forge_attributes(coal_amount, wood_amount).ok_or(CoalError::ForgeTierNotMet)?;
+
+    // Burn the inputs.
+    burn(
+        coal_tokens_info,
+        coal_mint_info,
+        signer,
+        token_program,
+        coal_amount,
+    )?;
+    burn(
+        wood_tokens_info,
+        wood_mint_info,
+        signer,
+        token_program,
+        wood_amount,
+    )?;
+
+    // Create the asset in the forge collection.
+    let plugin_authority_seeds = &[PLUGIN_UPDATE_AUTHORITY, &[args.plugin_authority_bump]];
+    CreateV2CpiBuilder::new(mpl_core_program)
+        .asset(asset_info)
+        .collection(Some(collection_info))
+        .authority(Some(plugin_update_authority))
+        .payer(signer)
+        .owner(Some(signer))
+        .system_program(system_program)
+        .name(FORGE_TOOL_NAME.to_string())
+        .uri(FORGE_TOOL_URI.to_string())
+        .plugins(vec![PluginAuthorityPair {
+            plugin: Plugin::Attributes(Attributes {
+                attribute_list: vec![
+                    Attribute {
+                        key: "durability".to_string(),
+                        value: amount_u64_to_f64(durability).to_string(),
+                    },
+                    Attribute {
+                        key: "multiplier".to_string(),
+                        value: multiplier.to_string(),
+                    },
+                ],
+            }),
+            authority: None,
+        }])
+        .invoke_signed(&[plugin_authority_seeds])?;
+
+    Ok(())
+}
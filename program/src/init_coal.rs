@@ -16,18 +16,18 @@ use solana_program::{
 };
 use spl_token::state::Mint;
 
-use crate::utils::{create_pda, AccountDeserialize, Discriminator};
+use crate::utils::{create_pda, AccountDeserialize, AccountVersion, Discriminator};
 
 /// Initialize sets up the ORE program to begin mining.
-pub fn process_init_coal<'a, 'info>(
-    accounts: &'a [AccountInfo<'info>],
+pub fn process_init_coal(
+    accounts: &[AccountInfo<'_>],
     data: &[u8],
 ) -> ProgramResult {
     // Parse args.
     let args = InitializeArgs::try_from_bytes(data)?;
 
     // Load accounts.
-    let [signer, bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info, bus_7_info, config_info, metadata_info, mint_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program, metadata_program, rent_sysvar] =
+    let [signer, bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info, bus_7_info, config_info, metadata_info, mint_info, treasury_info, treasury_tokens_info, system_program, token_program, associated_token_program, metadata_program, rent_sysvar, leaderboard_info] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -102,12 +102,14 @@ pub fn process_init_coal<'a, 'info>(
             signer,
         )?;
         let mut bus_data = bus_infos[i].try_borrow_mut_data()?;
-        bus_data[0] = Bus::discriminator() as u8;
+        bus_data[0] = Bus::discriminator();
+        bus_data[1] = Bus::version();
         let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
         bus.id = i as u64;
         bus.rewards = 0;
         bus.theoretical_rewards = 0;
         bus.top_balance = 0;
+        bus.bump = bus_bumps[i] as u64;
     }
 
     // Initialize config.
@@ -120,12 +122,34 @@ pub fn process_init_coal<'a, 'info>(
         signer,
     )?;
     let mut config_data = config_info.data.borrow_mut();
-    config_data[0] = Config::discriminator() as u8;
+    config_data[0] = Config::discriminator();
+    config_data[1] = Config::version();
     let config = Config::try_from_bytes_mut(&mut config_data)?;
     config.base_reward_rate = INITIAL_BASE_COAL_REWARD_RATE;
     config.last_reset_at = 0;
     config.min_difficulty = INITIAL_MIN_DIFFICULTY as u64;
     config.top_balance = 0;
+    config.referral_bonus_bps = INITIAL_REFERRAL_BONUS_BPS;
+    config.referral_epochs = INITIAL_REFERRAL_EPOCHS;
+    config.outstanding_liabilities = 0;
+    config.bump = args.config_bump as u64;
+    config.bus_count = BUS_COUNT as u64;
+    config.reap_after = INITIAL_REAP_AFTER;
+    config.reaper_bonus_bps = INITIAL_REAPER_BONUS_BPS;
+    config.jackpot = 0;
+    config.jackpot_bps = INITIAL_JACKPOT_BPS;
+    config.jackpot_difficulty = INITIAL_JACKPOT_DIFFICULTY;
+    config.epoch_duration = COAL_EPOCH_DURATION;
+
+    // Initialize leaderboard.
+    crate::create_leaderboard(
+        signer,
+        leaderboard_info,
+        system_program,
+        COAL_LEADERBOARD,
+        COAL_MINT_ADDRESS,
+        0,
+    )?;
 
     // Initialize treasury.
     create_pda(
@@ -137,7 +161,8 @@ pub fn process_init_coal<'a, 'info>(
         signer,
     )?;
     let mut treasury_data = treasury_info.data.borrow_mut();
-    treasury_data[0] = Treasury::discriminator() as u8;
+    treasury_data[0] = Treasury::discriminator();
+    treasury_data[1] = Treasury::version();
     drop(treasury_data);
 
     // Initialize mint.
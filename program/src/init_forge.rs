@@ -0,0 +1,51 @@
+use coal_api::{consts::*, instruction::InitForgeArgs, loaders::*};
+use mpl_core::instructions::CreateCollectionV2CpiBuilder;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    system_program,
+};
+
+/// Init forge creates the program-controlled collection that forged tools are minted into.
+pub fn process_init_forge(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = InitForgeArgs::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer, collection_info, plugin_update_authority, mpl_core_program, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_uninitialized_pda(
+        collection_info,
+        &[FORGE_COLLECTION],
+        args.collection_bump,
+        &coal_api::id(),
+    )?;
+    if plugin_update_authority.key.ne(&PLUGIN_UPDATE_AUTHORITY_ADDRESS) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    load_program(mpl_core_program, mpl_core::ID)?;
+    load_program(system_program, system_program::id())?;
+
+    // Check signer.
+    if signer.key.ne(&INITIALIZER_ADDRESS) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Create the collection with the plugin authority as its update authority.
+    CreateCollectionV2CpiBuilder::new(mpl_core_program)
+        .collection(collection_info)
+        .update_authority(Some(plugin_update_authority))
+        .payer(signer)
+        .system_program(system_program)
+        .name(FORGE_COLLECTION_NAME.to_string())
+        .uri(FORGE_COLLECTION_URI.to_string())
+        .invoke_signed(&[&[FORGE_COLLECTION, &[args.collection_bump]]])?;
+
+    Ok(())
+}
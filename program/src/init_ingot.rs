@@ -0,0 +1,136 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    instruction::*,
+    loaders::*,
+    state::IngotConfig,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    program_pack::Pack,
+    system_program, {self, sysvar},
+};
+use spl_token::state::Mint;
+
+use crate::utils::{create_pda, AccountDeserialize, AccountVersion, Discriminator};
+
+/// Init ingot sets up the ingot mint and smelting config.
+pub fn process_init_ingot(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = InitIngotArgs::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer, config_info, metadata_info, mint_info, treasury_info, system_program, token_program, metadata_program, rent_sysvar] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_uninitialized_pda(config_info, &[INGOT_CONFIG], args.config_bump, &coal_api::id())?;
+    load_uninitialized_pda(
+        metadata_info,
+        &[
+            METADATA,
+            mpl_token_metadata::ID.as_ref(),
+            INGOT_MINT_ADDRESS.as_ref(),
+        ],
+        args.metadata_bump,
+        &mpl_token_metadata::ID,
+    )?;
+    load_uninitialized_pda(
+        mint_info,
+        &[INGOT_MINT, MINT_NOISE.as_slice()],
+        args.mint_bump,
+        &coal_api::id(),
+    )?;
+    load_treasury(treasury_info, false)?;
+    load_program(system_program, system_program::id())?;
+    load_program(token_program, spl_token::id())?;
+    load_program(metadata_program, mpl_token_metadata::ID)?;
+    load_sysvar(rent_sysvar, sysvar::rent::id())?;
+
+    // Check signer.
+    if signer.key.ne(&INITIALIZER_ADDRESS) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Initialize config.
+    create_pda(
+        config_info,
+        &coal_api::id(),
+        8 + size_of::<IngotConfig>(),
+        &[INGOT_CONFIG, &[args.config_bump]],
+        system_program,
+        signer,
+    )?;
+    let mut config_data = config_info.data.borrow_mut();
+    config_data[0] = IngotConfig::discriminator();
+    config_data[1] = IngotConfig::version();
+    let config = IngotConfig::try_from_bytes_mut(&mut config_data)?;
+    config.coal_cost = INITIAL_SMELT_COAL_COST;
+    config.wood_cost = INITIAL_SMELT_WOOD_COST;
+    config.epoch_output_cap = INITIAL_SMELT_EPOCH_OUTPUT_CAP;
+    config.epoch_output = 0;
+    config.epoch_start_at = 0;
+    drop(config_data);
+
+    // Initialize mint.
+    create_pda(
+        mint_info,
+        &spl_token::id(),
+        Mint::LEN,
+        &[INGOT_MINT, MINT_NOISE.as_slice(), &[args.mint_bump]],
+        system_program,
+        signer,
+    )?;
+    solana_program::program::invoke_signed(
+        &spl_token::instruction::initialize_mint(
+            &spl_token::id(),
+            mint_info.key,
+            treasury_info.key,
+            None,
+            TOKEN_DECIMALS,
+        )?,
+        &[
+            token_program.clone(),
+            mint_info.clone(),
+            treasury_info.clone(),
+            rent_sysvar.clone(),
+        ],
+        &[&[INGOT_MINT, MINT_NOISE.as_slice(), &[args.mint_bump]]],
+    )?;
+
+    // Initialize mint metadata.
+    mpl_token_metadata::instructions::CreateMetadataAccountV3Cpi {
+        __program: metadata_program,
+        metadata: metadata_info,
+        mint: mint_info,
+        mint_authority: treasury_info,
+        payer: signer,
+        update_authority: (signer, true),
+        system_program,
+        rent: Some(rent_sysvar),
+        __args: mpl_token_metadata::instructions::CreateMetadataAccountV3InstructionArgs {
+            data: mpl_token_metadata::types::DataV2 {
+                name: INGOT_METADATA_NAME.to_string(),
+                symbol: INGOT_METADATA_SYMBOL.to_string(),
+                uri: INGOT_METADATA_URI.to_string(),
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            is_mutable: true,
+            collection_details: None,
+        },
+    }
+    .invoke_signed(&[&[TREASURY, &[args.treasury_bump]]])?;
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    loaders::*,
+    state::{Config, Leaderboard, WoodConfig},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey, system_program,
+};
+
+use crate::utils::{create_pda, AccountDeserialize, AccountVersion, Discriminator};
+
+/// Init leaderboard creates the account which ranks the miners of a resource each epoch, for
+/// resources initialized before leaderboards existed.
+pub fn process_init_leaderboard(
+    accounts: &[AccountInfo<'_>],
+    _data: &[u8],
+) -> ProgramResult {
+    // Load accounts.
+    let [signer, config_info, leaderboard_info, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_program(system_program, system_program::id())?;
+
+    // Check signer.
+    if signer.key.ne(&INITIALIZER_ADDRESS) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Lookup the leaderboard seed and resource.
+    let (seed, resource, last_reset_at) = if config_info.data.borrow()[0].eq(&Config::discriminator()) {
+        load_coal_config(config_info, false)?;
+        let config_data = config_info.data.borrow();
+        (COAL_LEADERBOARD, COAL_MINT_ADDRESS, Config::try_from_bytes(&config_data)?.last_reset_at)
+    } else if config_info.data.borrow()[0].eq(&WoodConfig::discriminator()) {
+        load_wood_config(config_info, false)?;
+        let config_data = config_info.data.borrow();
+        (WOOD_LEADERBOARD, WOOD_MINT_ADDRESS, WoodConfig::try_from_bytes(&config_data)?.last_reset_at)
+    } else {
+        return Err(ProgramError::InvalidAccountData);
+    };
+
+    // Initialize the leaderboard with the standings of the current epoch.
+    create_leaderboard(signer, leaderboard_info, system_program, seed, resource, last_reset_at)
+}
+
+/// Creates the leaderboard of a resource, with its current standings starting at the given epoch.
+pub(crate) fn create_leaderboard<'info>(
+    signer: &AccountInfo<'info>,
+    leaderboard_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    seed: &[u8],
+    resource: Pubkey,
+    epoch_reset_at: i64,
+) -> ProgramResult {
+    let (address, bump) = Pubkey::find_program_address(&[seed], &coal_api::id());
+    if leaderboard_info.key.ne(&address) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    load_system_account(leaderboard_info, true)?;
+    create_pda(
+        leaderboard_info,
+        &coal_api::id(),
+        8 + size_of::<Leaderboard>(),
+        &[seed, &[bump]],
+        system_program,
+        signer,
+    )?;
+    let mut leaderboard_data = leaderboard_info.try_borrow_mut_data()?;
+    leaderboard_data[0] = Leaderboard::discriminator();
+    leaderboard_data[1] = Leaderboard::version();
+    let leaderboard = Leaderboard::try_from_bytes_mut(&mut leaderboard_data)?;
+    leaderboard.resource = resource;
+    leaderboard.bump = bump as u64;
+    leaderboard.current.epoch_reset_at = epoch_reset_at;
+
+    Ok(())
+}
@@ -1,28 +1,72 @@
+mod add_busses;
+mod add_miner;
+mod audit_treasury;
 mod claim;
 mod close;
+mod close_and_claim;
+mod configure_epoch;
+mod configure_jackpot;
+mod configure_reaping;
+mod configure_referrals;
+mod configure_smelting;
+mod configure_vesting;
 mod init_coal;
+mod init_forge;
+mod init_ingot;
+mod init_leaderboard;
 mod init_wood;
 mod mine;
 mod open_coal;
 mod open_wood;
+mod reap_proof;
+mod rebalance;
+mod release_vested;
+mod remove_miner;
 mod reset;
+mod smelt;
 mod stake;
+mod stake_for;
+mod transfer_authority;
 mod update;
 mod equip;
+mod forge_tool;
 mod unequip;
+mod upgrade_account;
 
+use add_busses::*;
+use add_miner::*;
+use audit_treasury::*;
 use claim::*;
 use close::*;
+use close_and_claim::*;
+use configure_epoch::*;
+use configure_jackpot::*;
+use configure_reaping::*;
+use configure_referrals::*;
+use configure_smelting::*;
+use configure_vesting::*;
 use init_coal::*;
+use init_forge::*;
+use init_ingot::*;
+use init_leaderboard::*;
 use init_wood::*;
 use mine::*;
 use open_coal::*;
 use open_wood::*;
+use reap_proof::*;
+use rebalance::*;
+use release_vested::*;
+use remove_miner::*;
 use reset::*;
+use smelt::*;
 use stake::*;
+use stake_for::*;
+use transfer_authority::*;
 use update::*;
 use equip::*;
+use forge_tool::*;
 use unequip::*;
+use upgrade_account::*;
 use coal_api::instruction::*;
 use solana_program::{
     self, account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
@@ -59,8 +103,32 @@ pub fn process_instruction(
         CoalInstruction::Update => process_update(accounts, data)?,
         CoalInstruction::InitCoal => process_init_coal(accounts, data)?,
         CoalInstruction::InitWood => process_init_wood(accounts, data)?,
+        CoalInstruction::ConfigureReferrals => process_configure_referrals(accounts, data)?,
         CoalInstruction::Equip => process_equip_tool(accounts, data)?,
         CoalInstruction::Unequip => process_unequip_tool(accounts, data)?,
+        CoalInstruction::UpgradeAccount => process_upgrade_account(accounts, data)?,
+        CoalInstruction::ProposeProofAuthority => process_propose_proof_authority(accounts, data)?,
+        CoalInstruction::AcceptProofAuthority => process_accept_proof_authority(accounts, data)?,
+        CoalInstruction::AddMiner => process_add_miner(accounts, data)?,
+        CoalInstruction::RemoveMiner => process_remove_miner(accounts, data)?,
+        CoalInstruction::CloseAndClaim => process_close_and_claim(accounts, data)?,
+        CoalInstruction::Smelt => process_smelt(accounts, data)?,
+        CoalInstruction::InitIngot => process_init_ingot(accounts, data)?,
+        CoalInstruction::ConfigureSmelting => process_configure_smelting(accounts, data)?,
+        CoalInstruction::ForgeTool => process_forge_tool(accounts, data)?,
+        CoalInstruction::AuditTreasury => process_audit_treasury(accounts, data)?,
+        CoalInstruction::StakeFor => process_stake_for(accounts, data)?,
+        CoalInstruction::ConfigureVesting => process_configure_vesting(accounts, data)?,
+        CoalInstruction::ReleaseVested => process_release_vested(accounts, data)?,
+        CoalInstruction::MineRelayed => process_mine_relayed(accounts, data)?,
+        CoalInstruction::ReapProof => process_reap_proof(accounts, data)?,
+        CoalInstruction::Rebalance => process_rebalance(accounts, data)?,
+        CoalInstruction::InitForge => process_init_forge(accounts, data)?,
+        CoalInstruction::AddBusses => process_add_busses(accounts, data)?,
+        CoalInstruction::ConfigureReaping => process_configure_reaping(accounts, data)?,
+        CoalInstruction::InitLeaderboard => process_init_leaderboard(accounts, data)?,
+        CoalInstruction::ConfigureJackpot => process_configure_jackpot(accounts, data)?,
+        CoalInstruction::ConfigureEpoch => process_configure_epoch(accounts, data)?,
     }
 
     Ok(())
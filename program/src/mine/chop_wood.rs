@@ -2,12 +2,12 @@ use std::mem::size_of;
 
 use drillx::Solution;
 use coal_api::{
+    auth::authenticate,
     consts::*,
     error::CoalError,
     event::MineEvent,
-    instruction::MineArgs,
     loaders::*,
-    state::{Bus, ProofV2, WoodConfig},
+    state::{Bus, Leaderboard, ProofV2, WoodConfig},
 };
 use solana_program::msg;
 #[allow(deprecated)]
@@ -19,58 +19,118 @@ use solana_program::{
     program::set_return_data,
     program_error::ProgramError,
     pubkey::Pubkey,
-    sanitize::SanitizeError,
-    serialize_utils::{read_pubkey, read_u16},
     slot_hashes::SlotHash,
     sysvar::{self, Sysvar},
 };
 
-use crate::utils::AccountDeserialize;
+use crate::{authorize_miner, calculate_jackpot_reward, utils::AccountDeserialize, Relay};
 
-pub fn process_chop_wood(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+/// Chop verifies a hash and increments the miner's claimable WOOD balance.
+pub(crate) fn chop_wood<'a, 'info>(
+    required_accounts: &'a [AccountInfo<'info>],
+    optional_accounts: &'a [AccountInfo<'info>],
+    solution: Solution,
+    relay: Option<Relay<'a, 'info>>,
+) -> ProgramResult {
     msg!("Processing chop wood");
-    // Parse args.
-    let args = MineArgs::try_from_bytes(data)?;
-
     // Load accounts.
     let [signer, bus_info, config_info, proof_info, instructions_sysvar, slot_hashes_sysvar] =
-        accounts
+        required_accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     load_signer(signer)?;
+
+    // Upgrade accounts which predate the latest layouts.
+    //
+    // The signer covers any additional rent, so miners need no separate upgrade after a layout change.
+    let system_program = optional_accounts.get(3);
+    crate::upgrade_in_place(bus_info, signer, system_program, &[])?;
+    crate::upgrade_in_place(proof_info, signer, system_program, std::slice::from_ref(config_info))?;
+    if let Some(relay) = &relay {
+        crate::upgrade_in_place(
+            relay.relayer_proof_info,
+            signer,
+            system_program,
+            std::slice::from_ref(config_info),
+        )?;
+    }
+
     load_any_wood_bus(bus_info, true)?;
     load_wood_config(config_info, false)?;
-    load_proof_v2_with_miner(proof_info, signer.key, &WOOD_MINT_ADDRESS, true)?;
+    load_any_proof_v2(proof_info, true)?;
     load_sysvar(instructions_sysvar, sysvar::instructions::id())?;
     load_sysvar(slot_hashes_sysvar, sysvar::slot_hashes::id())?;
+    if let Some(relay) = &relay {
+        load_any_proof_v2(relay.relayer_proof_info, true)?;
+        if relay.relayer_proof_info.key.eq(proof_info.key) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+    }
+    let miner_keys_info = optional_accounts.first();
+    let referrer_info = optional_accounts
+        .get(1)
+        .filter(|info| info.key.ne(&coal_api::id()));
+    let leaderboard_info = optional_accounts
+        .get(2)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if leaderboard_info.key.ne(&WOOD_LEADERBOARD_ADDRESS) {
+        return Err(ProgramError::InvalidSeeds);
+    }
 
     // Authenticate the proof account.
     //
     // Only one proof account can be used for any given transaction. All `mine` instructions
     // in the transaction must use the same proof account.
-    authenticate_wood_proof(&instructions_sysvar.data.borrow(), proof_info.key)?;
+    authenticate(&instructions_sysvar.data.borrow(), proof_info.key, &WOOD_MINT_ADDRESS)?;
 
     // Validate epoch is active.
-    let mut config_data = config_info.data.borrow_mut();
-    let config = WoodConfig::try_from_bytes_mut(&mut config_data)?;
+    let config_data = config_info.data.borrow();
+    let config = WoodConfig::try_from_bytes(&config_data)?;
     let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
     if config
         .last_reset_at
-        .saturating_add(WOOD_EPOCH_DURATION)
+        .saturating_add(config.epoch_duration)
         .le(&clock.unix_timestamp)
     {
         println!("Needs reset");
         return Err(CoalError::NeedsReset.into());
     }
 
+    // Authorize the miner.
+    //
+    // The miner must be the proof's miner, or one of the proof's unexpired miner keys. The miner signs
+    // the transaction, unless a relayer submits the solution with the miner's ed25519 signature.
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = ProofV2::try_from_bytes_mut(&mut proof_data)?;
+    if proof.resource.ne(&WOOD_MINT_ADDRESS) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    let miner = match &relay {
+        Some(relay) => {
+            relay.verify(
+                &instructions_sysvar.data.borrow(),
+                proof_info.key,
+                proof.challenge,
+                solution,
+            )?;
+            relay.miner
+        }
+        None => *signer.key,
+    };
+    authorize_miner(
+        miner_keys_info,
+        proof_info.key,
+        &proof.miner,
+        &miner,
+        config.last_reset_at,
+        clock.unix_timestamp,
+    )?;
+
     // Validate the hash digest.
     //
     // Here we use drillx_2 to validate the provided solution is a valid hash of the challenge.
     // If invalid, we return an error.
-    let mut proof_data = proof_info.data.borrow_mut();
-    let proof = ProofV2::try_from_bytes_mut(&mut proof_data)?;
-    let solution = Solution::new(args.digest, args.nonce);
     if !solution.is_valid(&proof.challenge) {
         return Err(CoalError::HashInvalid.into());
     }
@@ -175,6 +235,78 @@ pub fn process_chop_wood(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
     bus.rewards = bus.rewards.checked_sub(reward_actual).unwrap();
     proof.balance = proof.balance.checked_add(reward_actual).unwrap();
 
+    // Track the liability owed to the miner.
+    //
+    // Liabilities are held on the bus until the next reset, so the config is never written while mining.
+    bus.liabilities = bus.liabilities.checked_add(reward_actual).unwrap();
+
+    // Pay the relayer fee.
+    //
+    // The fee the miner signed is taken out of its reward and credited to the relayer's proof.
+    if let Some(relay) = &relay {
+        let mut relayer_proof_data = relay.relayer_proof_info.data.borrow_mut();
+        let relayer_proof = ProofV2::try_from_bytes_mut(&mut relayer_proof_data)?;
+        if relayer_proof.resource.ne(&WOOD_MINT_ADDRESS) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let fee = relay.fee(reward_actual);
+        proof.balance = proof.balance.checked_sub(fee).unwrap();
+        relayer_proof.balance = relayer_proof.balance.checked_add(fee).unwrap();
+    }
+
+    // Pay the jackpot.
+    //
+    // Rewards are limited by the bus, so a hash far above the minimum difficulty also wins a share of
+    // the jackpot. Once won, the tokens are owed to the miner like any other reward.
+    // The jackpot is split across the busses at reset, so the share is taken from this bus's portion.
+    let jackpot_reward = calculate_jackpot_reward(
+        bus.jackpot,
+        difficulty as u64,
+        config.min_difficulty,
+        config.jackpot_difficulty,
+    );
+    bus.jackpot = bus.jackpot.checked_sub(jackpot_reward).unwrap();
+    proof.balance = proof.balance.checked_add(jackpot_reward).unwrap();
+    bus.liabilities = bus.liabilities.checked_add(jackpot_reward).unwrap();
+
+    // Pay the referral bonus.
+    //
+    // For a limited number of epochs after a proof is opened with a referrer, the referrer earns a bonus
+    // proportional to the rewards paid to the referred miner. The bonus is paid out of the same bus.
+    if proof.referrer.ne(&Pubkey::default()) && t.lt(&proof.referral_expires_at) {
+        let Some(referrer_info) = referrer_info else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if referrer_info.key.ne(&proof.referrer) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // The referrer may have since closed or transferred its proof.
+        if !referrer_info.data_is_empty() {
+            crate::upgrade_in_place(
+                referrer_info,
+                signer,
+                system_program,
+                std::slice::from_ref(config_info),
+            )?;
+            load_any_proof_v2(referrer_info, true)?;
+            let mut referrer_data = referrer_info.data.borrow_mut();
+            let referrer = ProofV2::try_from_bytes_mut(&mut referrer_data)?;
+            let referral_reward = ((reward_actual as u128)
+                .checked_mul(config.referral_bonus_bps as u128)
+                .unwrap()
+                .checked_div(BASIS_POINTS as u128)
+                .unwrap() as u64)
+                .min(bus.rewards);
+            bus.rewards = bus.rewards.checked_sub(referral_reward).unwrap();
+            referrer.balance = referrer.balance.checked_add(referral_reward).unwrap();
+            bus.liabilities = bus.liabilities.checked_add(referral_reward).unwrap();
+            referrer.total_referral_rewards = referrer
+                .total_referral_rewards
+                .saturating_add(referral_reward);
+        }
+    }
+
     // Hash a recent slot hash into the next challenge to prevent pre-mining attacks.
     //
     // The slot hashes are unpredictable values. By seeding the next challenge with the most recent slot hash,
@@ -194,6 +326,27 @@ pub fn process_chop_wood(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
     proof.total_hashes = proof.total_hashes.saturating_add(1);
     proof.total_rewards = proof.total_rewards.saturating_add(reward);
 
+    // Update epoch stats.
+    if proof.epoch_reset_at.ne(&config.last_reset_at) {
+        proof.epoch_reset_at = config.last_reset_at;
+        proof.epoch_rewards = 0;
+    }
+    proof.epoch_rewards = proof
+        .epoch_rewards
+        .saturating_add(reward_actual)
+        .saturating_add(jackpot_reward);
+
+    // Update the leaderboard.
+    //
+    // Reset moves the standings of the ended epoch into the previous epoch. Resources initialized
+    // before leaderboards existed have none until InitLeaderboard runs, so mining goes on without it.
+    if !leaderboard_info.data_is_empty() {
+        load_wood_leaderboard(leaderboard_info, true)?;
+        let mut leaderboard_data = leaderboard_info.data.borrow_mut();
+        let leaderboard = Leaderboard::try_from_bytes_mut(&mut leaderboard_data)?;
+        leaderboard.record(*proof_info.key, proof.epoch_rewards, difficulty as u64);
+    }
+
     // Log the mined rewards.
     //
     // This data can be used by off-chain indexers to display mining stats.
@@ -202,54 +355,10 @@ pub fn process_chop_wood(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
             difficulty: difficulty as u64,
             reward: reward_actual,
             timing: t.saturating_sub(t_liveness),
+            jackpot: jackpot_reward,
         }
         .to_bytes(),
     );
 
     Ok(())
 }
-
-fn authenticate_wood_proof(data: &[u8], proof_address: &Pubkey) -> ProgramResult {
-    if let Ok(Some(auth_address)) = parse_wood_auth_address(data) {
-        if proof_address.ne(&auth_address) {
-            return Err(CoalError::AuthFailed.into());
-        }
-    } else {
-        return Err(CoalError::AuthFailed.into());
-    }
-    Ok(())
-}
-
-
-/// Use transaction introspection to parse the authenticated pubkey.
-fn parse_wood_auth_address(data: &[u8]) -> Result<Option<Pubkey>, SanitizeError> {
-    // Start the current byte index at 0
-    let mut curr = 0;
-    let num_instructions = read_u16(&mut curr, data)?;
-    let pc = curr;
-
-    // Iterate through the transaction instructions
-    for i in 0..num_instructions as usize {
-        // Shift pointer to correct positition
-        curr = pc + i * 2;
-        curr = read_u16(&mut curr, data)? as usize;
-
-        // Skip accounts
-        let num_accounts = read_u16(&mut curr, data)? as usize;
-        curr += num_accounts * 33;
-
-        // Read the instruction program id
-        let program_id = read_pubkey(&mut curr, data)?;
-
-        // Introspect on the first noop instruction
-        if program_id.eq(&NOOP_PROGRAM_ID) {
-            // Retrun address read from instruction data
-            curr += 2;
-            let address = read_pubkey(&mut curr, data)?;
-            return Ok(Some(address));
-        }
-    }
-
-    // Default return none
-    Ok(None)
-}
\ No newline at end of file
@@ -2,12 +2,13 @@ use std::mem::size_of;
 
 use drillx::Solution;
 use coal_api::{
+    auth::authenticate,
     consts::*,
     error::CoalError,
     event::MineEvent,
-    instruction::MineArgs,
     loaders::*,
-    state::{Config, Proof, Bus, Tool},
+    ore::{ore_boost, OreProof},
+    state::{Config, Leaderboard, Proof, Bus, Tool},
 };
 #[allow(deprecated)]
 use solana_program::{
@@ -18,36 +19,72 @@ use solana_program::{
     program::set_return_data,
     program_error::ProgramError,
     pubkey::Pubkey,
-    sanitize::SanitizeError,
-    serialize_utils::{read_pubkey, read_u16},
     slot_hashes::SlotHash,
     sysvar::{self, Sysvar},
 };
 
-use crate::utils::AccountDeserialize;
-
-pub fn process_mine_coal(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    // Parse args.
-    let args = MineArgs::try_from_bytes(data)?;
+use crate::{authorize_miner, calculate_jackpot_reward, utils::AccountDeserialize, Relay};
 
+/// Mine verifies a hash and increments the miner's claimable COAL balance.
+pub(crate) fn mine_coal<'a, 'info>(
+    required_accounts: &'a [AccountInfo<'info>],
+    optional_accounts: &'a [AccountInfo<'info>],
+    solution: Solution,
+    relay: Option<Relay<'a, 'info>>,
+) -> ProgramResult {
     // Load accounts.
-    let (required_accounts, optional_accounts) = accounts.split_at(6);
     let [signer, bus_info, config_info, proof_info, instructions_sysvar, slot_hashes_sysvar] = required_accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     load_signer(signer)?;
+
+    // Upgrade accounts which predate the latest layouts.
+    //
+    // The signer covers any additional rent, so miners need no separate upgrade after a layout change.
+    let system_program = optional_accounts.get(5);
+    crate::upgrade_in_place(bus_info, signer, system_program, &[])?;
+    crate::upgrade_in_place(proof_info, signer, system_program, std::slice::from_ref(config_info))?;
+    if let Some(relay) = &relay {
+        crate::upgrade_in_place(
+            relay.relayer_proof_info,
+            signer,
+            system_program,
+            std::slice::from_ref(config_info),
+        )?;
+    }
+
     load_any_coal_bus(bus_info, true)?;
     load_coal_config(config_info, false)?;
-    load_coal_proof_with_miner(proof_info, signer.key, true)?;
+    load_any_coal_proof(proof_info, true)?;
     load_sysvar(instructions_sysvar, sysvar::instructions::id())?;
     load_sysvar(slot_hashes_sysvar, sysvar::slot_hashes::id())?;
+    if let Some(relay) = &relay {
+        load_any_coal_proof(relay.relayer_proof_info, true)?;
+        if relay.relayer_proof_info.key.eq(proof_info.key) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+    }
+    let tool_info = optional_accounts.first();
+    let miner_keys_info = optional_accounts.get(1);
+    let referrer_info = optional_accounts
+        .get(2)
+        .filter(|info| info.key.ne(&coal_api::id()));
+    let ore_proof_info = optional_accounts
+        .get(3)
+        .filter(|info| info.key.ne(&coal_api::id()));
+    let leaderboard_info = optional_accounts
+        .get(4)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if leaderboard_info.key.ne(&COAL_LEADERBOARD_ADDRESS) {
+        return Err(ProgramError::InvalidSeeds);
+    }
 
     // Authenticate the proof account.
     //
     // Only one proof account can be used for any given transaction. All `mine` instructions
     // in the transaction must use the same proof account.
-    authenticate_coal_proof(&instructions_sysvar.data.borrow(), proof_info.key)?;
+    authenticate(&instructions_sysvar.data.borrow(), proof_info.key, &COAL_MINT_ADDRESS)?;
 
     // Validate epoch is active.
     let config_data = config_info.data.borrow();
@@ -55,20 +92,44 @@ pub fn process_mine_coal(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
     let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
     if config
         .last_reset_at
-        .saturating_add(COAL_EPOCH_DURATION)
+        .saturating_add(config.epoch_duration)
         .le(&clock.unix_timestamp)
     {
         println!("Needs reset");
         return Err(CoalError::NeedsReset.into());
     }
 
+    // Authorize the miner.
+    //
+    // The miner must be the proof's miner, or one of the proof's unexpired miner keys. The miner signs
+    // the transaction, unless a relayer submits the solution with the miner's ed25519 signature.
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+    let miner = match &relay {
+        Some(relay) => {
+            relay.verify(
+                &instructions_sysvar.data.borrow(),
+                proof_info.key,
+                proof.challenge,
+                solution,
+            )?;
+            relay.miner
+        }
+        None => *signer.key,
+    };
+    authorize_miner(
+        miner_keys_info,
+        proof_info.key,
+        &proof.miner,
+        &miner,
+        config.last_reset_at,
+        clock.unix_timestamp,
+    )?;
+
     // Validate the hash digest.
     //
     // Here we use drillx_2 to validate the provided solution is a valid hash of the challenge.
     // If invalid, we return an error.
-    let mut proof_data = proof_info.data.borrow_mut();
-    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
-    let solution = Solution::new(args.digest, args.nonce);
     if !solution.is_valid(&proof.challenge) {
         return Err(CoalError::HashInvalid.into());
     }
@@ -110,11 +171,10 @@ pub fn process_mine_coal(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
     // Apply tool multiplier.
     //
     // Durability is decremented for the amount added.
-    if optional_accounts.len().eq(&1) {
-        let tool_info = &optional_accounts[0];
-
+    if let Some(tool_info) = tool_info {
         if !tool_info.data_is_empty() {
-            load_tool(&tool_info, signer.key, true)?;
+            crate::upgrade_in_place(tool_info, signer, system_program, &[])?;
+            load_tool(tool_info, &proof.miner, true)?;
     
             let mut tool_data = tool_info.data.borrow_mut();
             let tool = Tool::try_from_bytes_mut(&mut tool_data)?;
@@ -134,6 +194,19 @@ pub fn process_mine_coal(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
         }
     }
 
+    // Apply ORE boost.
+    //
+    // Miners who stake ORE with the same authority earn a bounded bonus proportional to their ORE stake.
+    // As with COAL stake, the ORE stake is only counted if it was deposited more than one minute ago.
+    if let Some(ore_proof_info) = ore_proof_info {
+        load_ore_proof(ore_proof_info, &proof.authority)?;
+        let ore_proof_data = ore_proof_info.data.borrow();
+        let ore_proof = OreProof::try_from_bytes(&ore_proof_data).unwrap();
+        if ore_proof.last_stake_at.saturating_add(ONE_MINUTE).lt(&t) {
+            reward = reward.checked_add(ore_boost(reward, ore_proof.balance)).unwrap();
+        }
+    }
+
     // Apply staking multiplier.
     //
     // If user has greater than or equal to the max stake on the network, they receive 2x multiplier.
@@ -201,6 +274,75 @@ pub fn process_mine_coal(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
     bus.rewards = bus.rewards.checked_sub(reward_actual).unwrap();
     proof.balance = proof.balance.checked_add(reward_actual).unwrap();
 
+    // Track the liability owed to the miner.
+    //
+    // Liabilities are held on the bus until the next reset, so the config is never written while mining.
+    bus.liabilities = bus.liabilities.checked_add(reward_actual).unwrap();
+
+    // Pay the relayer fee.
+    //
+    // The fee the miner signed is taken out of its reward and credited to the relayer's proof.
+    if let Some(relay) = &relay {
+        let mut relayer_proof_data = relay.relayer_proof_info.data.borrow_mut();
+        let relayer_proof = Proof::try_from_bytes_mut(&mut relayer_proof_data)?;
+        let fee = relay.fee(reward_actual);
+        proof.balance = proof.balance.checked_sub(fee).unwrap();
+        relayer_proof.balance = relayer_proof.balance.checked_add(fee).unwrap();
+    }
+
+    // Pay the jackpot.
+    //
+    // Rewards are limited by the bus, so a hash far above the minimum difficulty also wins a share of
+    // the jackpot. Once won, the tokens are owed to the miner like any other reward.
+    // The jackpot is split across the busses at reset, so the share is taken from this bus's portion.
+    let jackpot_reward = calculate_jackpot_reward(
+        bus.jackpot,
+        difficulty as u64,
+        config.min_difficulty,
+        config.jackpot_difficulty,
+    );
+    bus.jackpot = bus.jackpot.checked_sub(jackpot_reward).unwrap();
+    proof.balance = proof.balance.checked_add(jackpot_reward).unwrap();
+    bus.liabilities = bus.liabilities.checked_add(jackpot_reward).unwrap();
+
+    // Pay the referral bonus.
+    //
+    // For a limited number of epochs after a proof is opened with a referrer, the referrer earns a bonus
+    // proportional to the rewards paid to the referred miner. The bonus is paid out of the same bus.
+    if proof.referrer.ne(&Pubkey::default()) && t.lt(&proof.referral_expires_at) {
+        let Some(referrer_info) = referrer_info else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if referrer_info.key.ne(&proof.referrer) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // The referrer may have since closed or transferred its proof.
+        if !referrer_info.data_is_empty() {
+            crate::upgrade_in_place(
+                referrer_info,
+                signer,
+                system_program,
+                std::slice::from_ref(config_info),
+            )?;
+            load_any_coal_proof(referrer_info, true)?;
+            let mut referrer_data = referrer_info.data.borrow_mut();
+            let referrer = Proof::try_from_bytes_mut(&mut referrer_data)?;
+            let referral_reward = ((reward_actual as u128)
+                .checked_mul(config.referral_bonus_bps as u128)
+                .unwrap()
+                .checked_div(BASIS_POINTS as u128)
+                .unwrap() as u64)
+                .min(bus.rewards);
+            bus.rewards = bus.rewards.checked_sub(referral_reward).unwrap();
+            referrer.balance = referrer.balance.checked_add(referral_reward).unwrap();
+            bus.liabilities = bus.liabilities.checked_add(referral_reward).unwrap();
+            referrer.total_referral_rewards = referrer
+                .total_referral_rewards
+                .saturating_add(referral_reward);
+        }
+    }
+
     // Hash a recent slot hash into the next challenge to prevent pre-mining attacks.
     //
     // The slot hashes are unpredictable values. By seeding the next challenge with the most recent slot hash,
@@ -219,6 +361,27 @@ pub fn process_mine_coal(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
     proof.total_hashes = proof.total_hashes.saturating_add(1);
     proof.total_rewards = proof.total_rewards.saturating_add(reward);
 
+    // Update epoch stats.
+    if proof.epoch_reset_at.ne(&config.last_reset_at) {
+        proof.epoch_reset_at = config.last_reset_at;
+        proof.epoch_rewards = 0;
+    }
+    proof.epoch_rewards = proof
+        .epoch_rewards
+        .saturating_add(reward_actual)
+        .saturating_add(jackpot_reward);
+
+    // Update the leaderboard.
+    //
+    // Reset moves the standings of the ended epoch into the previous epoch. Resources initialized
+    // before leaderboards existed have none until InitLeaderboard runs, so mining goes on without it.
+    if !leaderboard_info.data_is_empty() {
+        load_coal_leaderboard(leaderboard_info, true)?;
+        let mut leaderboard_data = leaderboard_info.data.borrow_mut();
+        let leaderboard = Leaderboard::try_from_bytes_mut(&mut leaderboard_data)?;
+        leaderboard.record(*proof_info.key, proof.epoch_rewards, difficulty as u64);
+    }
+
     // Log the mined rewards.
     //
     // This data can be used by off-chain indexers to display mining stats.
@@ -227,61 +390,10 @@ pub fn process_mine_coal(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
             difficulty: difficulty as u64,
             reward: reward_actual,
             timing: t.saturating_sub(t_liveness),
+            jackpot: jackpot_reward,
         }
         .to_bytes(),
     );
 
     Ok(())
 }
-
-/// Authenticate the proof account.
-///
-/// This process is necessary to prevent sybil attacks. If a user can pack multiple hashes into a single
-/// transaction, then there is a financial incentive to mine across multiple keypairs and submit as many hashes
-/// as possible in the same transaction to minimize fee / hash.
-///
-/// This is prevented by forcing every transaction to declare upfront the proof account that will be used for mining.
-/// The authentication process includes passing the 32 byte pubkey address as instruction data to a CU-optimized noop
-/// program. We parse this address through transaction introspection and use it to ensure the same proof account is
-/// used for every `mine` instruction in a given transaction.
-fn authenticate_coal_proof(data: &[u8], proof_address: &Pubkey) -> ProgramResult {
-    if let Ok(Some(auth_address)) = parse_coal_auth_address(data) {
-        if proof_address.ne(&auth_address) {
-            return Err(CoalError::AuthFailed.into());
-        }
-    } else {
-        return Err(CoalError::AuthFailed.into());
-    }
-    Ok(())
-}
-
-/// Use transaction introspection to parse the authenticated pubkey.
-fn parse_coal_auth_address(data: &[u8]) -> Result<Option<Pubkey>, SanitizeError> {
-    let mut curr = 0;
-    let num_instructions = read_u16(&mut curr, data)?;
-    let pc = curr;
-
-    let mut noop_count = 0;
-
-    for i in 0..num_instructions as usize {
-        curr = pc + i * 2;
-        curr = read_u16(&mut curr, data)? as usize;
-
-        let num_accounts = read_u16(&mut curr, data)? as usize;
-        curr += num_accounts * 33;
-
-        let program_id = read_pubkey(&mut curr, data)?;
-
-        if program_id.eq(&NOOP_PROGRAM_ID) {
-            noop_count += 1;
-            
-            if noop_count == 2 {
-                curr += 2;
-                let address = read_pubkey(&mut curr, data)?;
-                return Ok(Some(address));
-            }
-        }
-    }
-
-    Ok(None)
-}
\ No newline at end of file
@@ -1,27 +1,207 @@
-use coal_api::state::{Config, WoodConfig};
+use coal_api::{
+    consts::*,
+    error::CoalError,
+    instruction::{MineArgs, MineRelayedArgs},
+    loaders::load_miner_keys,
+    relay::{verify_relay_signature, RelayMessage},
+    state::{Config, MinerKeys, WoodConfig},
+};
+use drillx::Solution;
 use solana_program::{
     account_info::AccountInfo, 
     entrypoint::ProgramResult,
     program_error::ProgramError,
+    pubkey::Pubkey,
 };
 
-use crate::utils::Discriminator;
+use crate::utils::{AccountDeserialize, Discriminator};
 
 use chop_wood::*;
 use mine_coal::*;
 mod chop_wood;
 mod mine_coal;
 
-pub fn process_mine<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
-    let config_info = &accounts[2];
+pub fn process_mine(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = MineArgs::try_from_bytes(data)?;
+    let solution = Solution::new(args.digest, args.nonce);
+
+    // Split the optional accounts.
+    let (required_accounts, optional_accounts) = accounts.split_at(accounts.len().min(6));
+    dispatch_mine(required_accounts, optional_accounts, solution, None)
+}
+
+/// Mine relayed verifies a hash submitted by a relayer on behalf of a miner.
+///
+/// The miner authorizes the relayer with an ed25519 signature over the solution, verified by the
+/// native Ed25519 program in the same transaction. The relayer is paid the fee the miner signed.
+pub fn process_mine_relayed(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = MineRelayedArgs::try_from_bytes(data)?;
+    let solution = Solution::new(args.digest, args.nonce);
+    let fee_bps = u64::from_le_bytes(args.fee_bps);
+    if fee_bps.gt(&BASIS_POINTS) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    // Split the relayer proof and optional accounts.
+    let (required_accounts, optional_accounts) = accounts.split_at(accounts.len().min(7));
+    let Some((relayer_proof_info, required_accounts)) = required_accounts.split_last() else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let relay = Relay {
+        miner: args.miner,
+        fee_bps,
+        relayer_proof_info,
+    };
+    dispatch_mine(required_accounts, optional_accounts, solution, Some(relay))
+}
+
+fn dispatch_mine<'a, 'info>(
+    required_accounts: &'a [AccountInfo<'info>],
+    optional_accounts: &'a [AccountInfo<'info>],
+    solution: Solution,
+    relay: Option<Relay<'a, 'info>>,
+) -> ProgramResult {
+    let Some(config_info) = required_accounts.get(2) else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if config_info.data.borrow()[0].eq(&Config::discriminator()) {
+        return mine_coal(required_accounts, optional_accounts, solution, relay)
+    }
+
+    if config_info.data.borrow()[0].eq(&WoodConfig::discriminator()) {
+        return chop_wood(required_accounts, optional_accounts, solution, relay)
+    }
+
+    Err(ProgramError::InvalidAccountData)
+}
+
+/// A solution submitted by a relayer on behalf of a miner.
+pub(crate) struct Relay<'a, 'info> {
+    /// The miner key which signed the solution.
+    pub miner: Pubkey,
+
+    /// The share of the reward paid to the relayer, in basis points.
+    pub fee_bps: u64,
+
+    /// The proof account credited with the relayer fee.
+    pub relayer_proof_info: &'a AccountInfo<'info>,
+}
+
+impl Relay<'_, '_> {
+    /// Errors if the transaction does not verify the miner's signature over the solution and the
+    /// relayer's proof.
+    pub fn verify(
+        &self,
+        instructions_data: &[u8],
+        proof: &Pubkey,
+        challenge: [u8; 32],
+        solution: Solution,
+    ) -> ProgramResult {
+        let message = RelayMessage::new(
+            *proof,
+            *self.relayer_proof_info.key,
+            challenge,
+            solution,
+            self.fee_bps,
+        );
+        verify_relay_signature(instructions_data, &self.miner, &message)
+    }
+
+    /// Returns the relayer fee owed on the reward.
+    pub fn fee(&self, reward: u64) -> u64 {
+        (reward as u128)
+            .checked_mul(self.fee_bps as u128)
+            .unwrap()
+            .checked_div(BASIS_POINTS as u128)
+            .unwrap() as u64
+    }
+}
+
+/// Authorize the signer to submit hashes for a proof.
+///
+/// The proof's miner is always authorized. Any other signer must hold an unexpired key in the
+/// proof's miner keys account, and be within the key's hash quota for the current epoch.
+pub(crate) fn authorize_miner(
+    miner_keys_info: Option<&AccountInfo>,
+    proof_address: &Pubkey,
+    proof_miner: &Pubkey,
+    signer: &Pubkey,
+    epoch_reset_at: i64,
+    now: i64,
+) -> ProgramResult {
+    if proof_miner.eq(signer) {
+        return Ok(());
+    }
+
+    let Some(miner_keys_info) = miner_keys_info else {
+        return Err(ProgramError::InvalidAccountData);
+    };
+    load_miner_keys(miner_keys_info, proof_address, true)?;
+    let mut miner_keys_data = miner_keys_info.data.borrow_mut();
+    let miner_keys = MinerKeys::try_from_bytes_mut(&mut miner_keys_data)?;
+    let key = miner_keys
+        .keys
+        .iter_mut()
+        .find(|key| key.miner.eq(signer))
+        .ok_or(ProgramError::InvalidAccountData)?;
 
-    if config_info.data.borrow()[0].eq(&(Config::discriminator() as u8)) {
-        return process_mine_coal(accounts, data)
+    // Reject expired keys.
+    if key.expires_at.le(&now) {
+        return Err(CoalError::MinerKeyExpired.into());
     }
 
-    if config_info.data.borrow()[0].eq(&(WoodConfig::discriminator() as u8)) {
-        return process_chop_wood(accounts, data)
+    // Enforce the per-epoch hash quota.
+    if key.epoch_reset_at.ne(&epoch_reset_at) {
+        key.epoch_reset_at = epoch_reset_at;
+        key.epoch_hashes = 0;
     }
+    if key.hash_quota.gt(&0) && key.epoch_hashes.ge(&key.hash_quota) {
+        return Err(CoalError::HashQuotaExceeded.into());
+    }
+    key.epoch_hashes = key.epoch_hashes.saturating_add(1);
+
+    Ok(())
+}
 
-    return Err(ProgramError::InvalidAccountData);    
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use coal_api::{relay::relay_signature, test_utils::sysvar_data};
+
+    use super::*;
+
+    /// Runs Relay::verify for a relayer proof against a signature over the signed relayer proof.
+    fn run_verify(signed_relayer_proof: Pubkey, relayer_proof: Pubkey) -> ProgramResult {
+        let miner = Pubkey::new_unique();
+        let proof = Pubkey::new_unique();
+        let solution = Solution::new([1; 16], [2; 8]);
+        let message = RelayMessage::new(proof, signed_relayer_proof, [3; 32], solution, 500);
+        let data = sysvar_data(&[relay_signature(miner, [4; 64], &message)]);
+        let owner = coal_api::id();
+        let mut lamports = 0;
+        let relayer_proof_info =
+            AccountInfo::new(&relayer_proof, false, true, &mut lamports, &mut [], &owner, false, 0);
+        let relay = Relay {
+            miner,
+            fee_bps: 500,
+            relayer_proof_info: &relayer_proof_info,
+        };
+        relay.verify(&data, &proof, [3; 32], solution)
+    }
+
+    #[test]
+    fn test_relay_verify() {
+        let relayer_proof = Pubkey::new_unique();
+        assert!(run_verify(relayer_proof, relayer_proof).is_ok());
+    }
+
+    #[test]
+    fn test_relay_verify_rejects_substituted_relayer() {
+        assert_eq!(
+            run_verify(Pubkey::new_unique(), Pubkey::new_unique()),
+            Err(CoalError::RelaySignatureInvalid.into())
+        );
+    }
+}
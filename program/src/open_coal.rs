@@ -1,19 +1,23 @@
 use std::mem::size_of;
 
-use coal_api::{consts::*, instruction::OpenArgs, loaders::*, state::Proof};
+use coal_api::{consts::*, instruction::OpenArgs, loaders::*, state::{Config, Proof}};
 use solana_program::{
     account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, keccak::hashv, program_error::ProgramError, slot_hashes::SlotHash, system_program, sysvar::{self, Sysvar}
 };
 
-use crate::utils::{create_pda, AccountDeserialize, Discriminator};
+use crate::utils::{create_pda, AccountDeserialize, AccountVersion, Discriminator};
 
 /// Open creates a new proof account to track a miner's state.
-pub fn process_open_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+///
+/// A referrer must be followed by the config of the resource, which sets how long the referral lasts.
+pub fn process_open_coal(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse args.
     let args = OpenArgs::try_from_bytes(data)?;
 
     // Load accounts.
-    let [signer, miner_info, payer_info, proof_info, system_program, slot_hashes_info] = accounts
+    let (required_accounts, optional_accounts) = accounts.split_at(accounts.len().min(6));
+    let [signer, miner_info, payer_info, proof_info, system_program, slot_hashes_info] =
+        required_accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
@@ -28,6 +32,17 @@ pub fn process_open_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[
     )?;
     load_program(system_program, system_program::id())?;
     load_sysvar(slot_hashes_info, sysvar::slot_hashes::id())?;
+    let referrer_info = optional_accounts.first();
+    if let Some(referrer_info) = referrer_info {
+        load_any_coal_proof(referrer_info, false)?;
+    }
+    let config_info = optional_accounts.get(1);
+    if referrer_info.is_some() {
+        let Some(config_info) = config_info else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        load_coal_config(config_info, false)?;
+    }
 
     // Initialize proof.
     create_pda(
@@ -40,9 +55,11 @@ pub fn process_open_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[
     )?;
     let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
     let mut proof_data = proof_info.data.borrow_mut();
-    proof_data[0] = Proof::discriminator() as u8;
+    proof_data[0] = Proof::discriminator();
+    proof_data[1] = Proof::version();
     let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
     proof.authority = *signer.key;
+    proof.bump = args.bump as u64;
     proof.balance = 0;
     proof.challenge = hashv(&[
         signer.key.as_ref(),
@@ -56,5 +73,46 @@ pub fn process_open_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[
     proof.total_hashes = 0;
     proof.total_rewards = 0;
 
+    // Record the referrer, if any.
+    //
+    // The referral lasts for the configured number of epochs at the current epoch length.
+    if let (Some(referrer_info), Some(config_info)) = (referrer_info, config_info) {
+        let config_data = config_info.data.borrow();
+        let config = Config::try_from_bytes(&config_data)?;
+        proof.referrer = *referrer_info.key;
+        proof.referred_at = clock.unix_timestamp;
+        proof.referral_expires_at = crate::calculate_referral_expires_at(
+            proof.referred_at,
+            config.referral_epochs,
+            config.epoch_duration,
+        );
+    }
+    proof.total_referral_rewards = 0;
+
     Ok(())
 }
+
+/// Returns the time a referral made at the given time stops earning the referrer a bonus.
+pub(crate) fn calculate_referral_expires_at(
+    referred_at: i64,
+    referral_epochs: u64,
+    epoch_duration: i64,
+) -> i64 {
+    referred_at.saturating_add(
+        i64::try_from(referral_epochs)
+            .unwrap_or(i64::MAX)
+            .saturating_mul(epoch_duration),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_referral_expires_at() {
+        assert_eq!(calculate_referral_expires_at(1_000, 3, 60), 1_180);
+        assert_eq!(calculate_referral_expires_at(1_000, 0, 60), 1_000);
+        assert_eq!(calculate_referral_expires_at(1_000, u64::MAX, 60), i64::MAX);
+    }
+}
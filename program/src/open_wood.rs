@@ -1,19 +1,23 @@
 use std::mem::size_of;
 
-use coal_api::{consts::*, instruction::OpenArgs, loaders::*, state::ProofV2};
+use coal_api::{consts::*, instruction::OpenArgs, loaders::*, state::{ProofV2, WoodConfig}};
 use solana_program::{
     account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, keccak::hashv, program_error::ProgramError, slot_hashes::SlotHash, system_program, sysvar::{self, Sysvar}
 };
 
-use crate::utils::{create_pda, AccountDeserialize, Discriminator};
+use crate::utils::{create_pda, AccountDeserialize, AccountVersion, Discriminator};
 
 /// Open creates a new proof account to track a miner's state.
-pub fn process_open_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+///
+/// A referrer must be followed by the config of the resource, which sets how long the referral lasts.
+pub fn process_open_wood(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse args.
     let args = OpenArgs::try_from_bytes(data)?;
 
     // Load accounts.
-    let [signer, miner_info, payer_info, proof_info, system_program, slot_hashes_info] = accounts
+    let (required_accounts, optional_accounts) = accounts.split_at(accounts.len().min(6));
+    let [signer, miner_info, payer_info, proof_info, system_program, slot_hashes_info] =
+        required_accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
@@ -28,6 +32,22 @@ pub fn process_open_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[
     )?;
     load_program(system_program, system_program::id())?;
     load_sysvar(slot_hashes_info, sysvar::slot_hashes::id())?;
+    let referrer_info = optional_accounts.first();
+    if let Some(referrer_info) = referrer_info {
+        load_any_proof_v2(referrer_info, false)?;
+        let referrer_data = referrer_info.data.borrow();
+        let referrer = ProofV2::read_from_bytes(&referrer_data)?;
+        if referrer.resource.ne(&WOOD_MINT_ADDRESS) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+    }
+    let config_info = optional_accounts.get(1);
+    if referrer_info.is_some() {
+        let Some(config_info) = config_info else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        load_wood_config(config_info, false)?;
+    }
 
     // Initialize proof.
     create_pda(
@@ -40,10 +60,12 @@ pub fn process_open_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[
     )?;
     let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
     let mut proof_data = proof_info.data.borrow_mut();
-    proof_data[0] = ProofV2::discriminator() as u8;
+    proof_data[0] = ProofV2::discriminator();
+    proof_data[1] = ProofV2::version();
     let proof = ProofV2::try_from_bytes_mut(&mut proof_data)?;
     proof.resource = WOOD_MINT_ADDRESS;
     proof.authority = *signer.key;
+    proof.bump = args.bump as u64;
     proof.balance = 0;
     proof.challenge = hashv(&[
         b"wood",
@@ -58,5 +80,21 @@ pub fn process_open_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[
     proof.total_hashes = 0;
     proof.total_rewards = 0;
 
+    // Record the referrer, if any.
+    //
+    // The referral lasts for the configured number of epochs at the current epoch length.
+    if let (Some(referrer_info), Some(config_info)) = (referrer_info, config_info) {
+        let config_data = config_info.data.borrow();
+        let config = WoodConfig::try_from_bytes(&config_data)?;
+        proof.referrer = *referrer_info.key;
+        proof.referred_at = clock.unix_timestamp;
+        proof.referral_expires_at = crate::calculate_referral_expires_at(
+            proof.referred_at,
+            config.referral_epochs,
+            config.epoch_duration,
+        );
+    }
+    proof.total_referral_rewards = 0;
+
     Ok(())
 }
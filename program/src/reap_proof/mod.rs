@@ -0,0 +1,142 @@
+use coal_api::{
+    consts::{BASIS_POINTS, COAL_MAIN_HAND_TOOL},
+    error::CoalError,
+    event::ReapEvent,
+    state::{Proof, ProofV2},
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, program::set_return_data,
+    program_error::ProgramError, pubkey::Pubkey, sysvar::Sysvar,
+};
+
+use crate::utils::Discriminator;
+
+use reap_proof_coal::*;
+use reap_proof_wood::*;
+mod reap_proof_coal;
+mod reap_proof_wood;
+
+/// Reap proof lets anyone close an empty proof which has not hashed for the configured period.
+/// The rent is returned to the proof authority, less a share paid to the reaper.
+pub fn process_reap_proof(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let Some(proof_info) = accounts.get(2) else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if proof_info.data.borrow()[0].eq(&Proof::discriminator()) {
+        return process_reap_proof_coal(accounts, data)
+    }
+
+    if proof_info.data.borrow()[0].eq(&ProofV2::discriminator()) {
+        return process_reap_proof_wood(accounts, data)
+    }
+
+    Err(ProgramError::InvalidAccountData)
+}
+
+/// Errors if reaping is disabled, or the proof holds a balance or has hashed within the reap period.
+pub(crate) fn check_reapable(balance: u64, last_hash_at: i64, reap_after: i64) -> ProgramResult {
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    if reap_after.le(&0)
+        || balance.gt(&0)
+        || last_hash_at.saturating_add(reap_after).gt(&clock.unix_timestamp)
+    {
+        return Err(CoalError::ProofNotReapable.into());
+    }
+    Ok(())
+}
+
+/// Errors if the account is not the tool PDA of the authority, or a tool is equipped in it.
+///
+/// Only the authority can unequip a tool, so a proof is not reapable until its tool is returned.
+pub(crate) fn check_unequipped(tool_info: &AccountInfo, authority: &Pubkey) -> ProgramResult {
+    let tool_address =
+        Pubkey::find_program_address(&[COAL_MAIN_HAND_TOOL, authority.as_ref()], &coal_api::id()).0;
+    if tool_info.key.ne(&tool_address) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if !tool_info.data_is_empty() {
+        return Err(CoalError::ToolEquipped.into());
+    }
+    Ok(())
+}
+
+/// Closes the proof account and splits its rent between the authority and the reaper.
+pub(crate) fn reap<'info>(
+    reaper_info: &AccountInfo<'info>,
+    authority_info: &AccountInfo<'info>,
+    proof_info: &AccountInfo<'info>,
+    reaper_bonus_bps: u64,
+) -> ProgramResult {
+    // Realloc data to zero.
+    proof_info.realloc(0, true)?;
+
+    // Split the remaining lamports.
+    let lamports = proof_info.lamports();
+    let (authority_lamports, reaper_lamports) = split_rent(lamports, reaper_bonus_bps);
+    **authority_info.lamports.borrow_mut() += authority_lamports;
+    **reaper_info.lamports.borrow_mut() += reaper_lamports;
+    **proof_info.lamports.borrow_mut() = 0;
+
+    // Log the reap.
+    set_return_data(
+        ReapEvent {
+            proof: *proof_info.key,
+            authority_lamports,
+            reaper_lamports,
+        }
+        .to_bytes(),
+    );
+
+    Ok(())
+}
+
+/// Returns the lamports owed to the authority and to the reaper.
+fn split_rent(lamports: u64, reaper_bonus_bps: u64) -> (u64, u64) {
+    let reaper_lamports = (lamports as u128)
+        .saturating_mul(reaper_bonus_bps.min(BASIS_POINTS) as u128)
+        .saturating_div(BASIS_POINTS as u128) as u64;
+    (lamports.saturating_sub(reaper_lamports), reaper_lamports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_rent() {
+        assert_eq!(split_rent(1_000_000, 1_000), (900_000, 100_000));
+        assert_eq!(split_rent(1_000_000, 0), (1_000_000, 0));
+        assert_eq!(split_rent(999, 1_000), (900, 99));
+    }
+
+    #[test]
+    fn test_check_unequipped() {
+        let authority = Pubkey::new_unique();
+        let address =
+            Pubkey::find_program_address(&[COAL_MAIN_HAND_TOOL, authority.as_ref()], &coal_api::id()).0;
+        let owner = coal_api::id();
+        let mut lamports = 0;
+        let mut data = [];
+        let info = AccountInfo::new(&address, false, false, &mut lamports, &mut data, &owner, false, 0);
+        assert_eq!(check_unequipped(&info, &authority), Ok(()));
+        assert_eq!(
+            check_unequipped(&info, &Pubkey::new_unique()),
+            Err(ProgramError::InvalidSeeds)
+        );
+
+        let mut lamports = 0;
+        let mut data = [0; 8];
+        let info = AccountInfo::new(&address, false, false, &mut lamports, &mut data, &owner, false, 0);
+        assert_eq!(
+            check_unequipped(&info, &authority),
+            Err(CoalError::ToolEquipped.into())
+        );
+    }
+
+    #[test]
+    fn test_split_rent_caps_bonus() {
+        assert_eq!(split_rent(1_000_000, u64::MAX), (0, 1_000_000));
+        assert_eq!(split_rent(u64::MAX, BASIS_POINTS), (0, u64::MAX));
+    }
+}
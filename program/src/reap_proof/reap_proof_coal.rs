@@ -0,0 +1,37 @@
+use coal_api::{
+    loaders::*,
+    state::{Config, Proof},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::utils::AccountDeserialize;
+
+pub fn process_reap_proof_coal(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer, authority_info, proof_info, config_info, miner_keys_info, tool_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_coal_proof(proof_info, authority_info.key, true)?;
+    load_coal_config(config_info, false)?;
+    if !authority_info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Validate the proof is abandoned.
+    let config_data = config_info.data.borrow();
+    let config = Config::try_from_bytes(&config_data)?;
+    let proof_data = proof_info.data.borrow();
+    let proof = Proof::read_from_bytes(&proof_data)?;
+    crate::check_reapable(proof.balance, proof.last_hash_at, config.reap_after)?;
+    crate::check_unequipped(tool_info, authority_info.key)?;
+    drop(proof_data);
+
+    // Close the miner keys, if any, and return the rent to the authority.
+    crate::close_miner_keys(miner_keys_info, proof_info, authority_info)?;
+
+    // Close the proof.
+    crate::reap(signer, authority_info, proof_info, config.reaper_bonus_bps)
+}
@@ -0,0 +1,42 @@
+use coal_api::{
+    consts::*,
+    error::CoalError,
+    loaders::*,
+    state::{ProofV2, WoodConfig},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::utils::AccountDeserialize;
+
+pub fn process_reap_proof_wood(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer, authority_info, proof_info, config_info, miner_keys_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_proof_v2(proof_info, authority_info.key, &WOOD_MINT_ADDRESS, true)?;
+    load_wood_config(config_info, false)?;
+    if !authority_info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Validate the proof is abandoned.
+    let config_data = config_info.data.borrow();
+    let config = WoodConfig::try_from_bytes(&config_data)?;
+    let proof_data = proof_info.data.borrow();
+    let proof = ProofV2::read_from_bytes(&proof_data)?;
+    crate::check_reapable(proof.balance, proof.last_hash_at, config.reap_after)?;
+    if proof.equipped_tool.ne(&Pubkey::default()) {
+        return Err(CoalError::ToolEquipped.into());
+    }
+    drop(proof_data);
+
+    // Close the miner keys, if any, and return the rent to the authority.
+    crate::close_miner_keys(miner_keys_info, proof_info, authority_info)?;
+
+    // Close the proof.
+    crate::reap(signer, authority_info, proof_info, config.reaper_bonus_bps)
+}
@@ -0,0 +1,187 @@
+use coal_api::{
+    consts::*,
+    error::CoalError,
+    loaders::*,
+    state::{Bus, Config, WoodConfig},
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, sysvar::Sysvar,
+};
+
+use crate::utils::{AccountDeserialize, Discriminator};
+
+/// Rebalance spreads the rewards remaining in the busses of a resource evenly across them, so miners
+/// on a depleted bus are not paid nothing until the next reset. It is permissionless, but can only be
+/// called once per epoch, and only once a bus is depleted, so an early call cannot use up the
+/// epoch's rebalance before it is needed.
+///
+/// Only the remaining rewards move between busses. The theoretical rewards are left as they are, so the
+/// reward rate still adjusts to the hashpower on each bus at reset.
+pub fn process_rebalance(
+    accounts: &[AccountInfo<'_>],
+    _data: &[u8],
+) -> ProgramResult {
+    // Load accounts.
+    let (required_accounts, busses) = accounts.split_at(accounts.len().min(2));
+    let [signer, config_info] = required_accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+
+    // Validate the busses, rate limit, and depletion.
+    //
+    // Every bus of the resource must be provided, ordered by id.
+    if config_info.data.borrow()[0].eq(&Config::discriminator()) {
+        load_coal_config(config_info, true)?;
+        let mut config_data = config_info.data.borrow_mut();
+        let config = Config::try_from_bytes_mut(&mut config_data)?;
+        if (busses.len() as u64).ne(&config.bus_count) {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        for (i, bus_info) in busses.iter().enumerate() {
+            load_coal_bus(bus_info, i as u64, true)?;
+        }
+        validate_rebalance(
+            config.last_reset_at,
+            config.last_rebalance_at,
+            config.epoch_duration,
+            clock.unix_timestamp,
+            &bus_rewards(busses)?,
+        )?;
+        config.last_rebalance_at = clock.unix_timestamp;
+    } else if config_info.data.borrow()[0].eq(&WoodConfig::discriminator()) {
+        load_wood_config(config_info, true)?;
+        let mut config_data = config_info.data.borrow_mut();
+        let config = WoodConfig::try_from_bytes_mut(&mut config_data)?;
+        if (busses.len() as u64).ne(&config.bus_count) {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        for (i, bus_info) in busses.iter().enumerate() {
+            load_wood_bus(bus_info, i as u64, true)?;
+        }
+        validate_rebalance(
+            config.last_reset_at,
+            config.last_rebalance_at,
+            config.epoch_duration,
+            clock.unix_timestamp,
+            &bus_rewards(busses)?,
+        )?;
+        config.last_rebalance_at = clock.unix_timestamp;
+    } else {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Spread the remaining rewards across the busses.
+    for (bus_info, rewards) in busses.iter().zip(rebalanced_rewards(&bus_rewards(busses)?)) {
+        let mut bus_data = bus_info.data.borrow_mut();
+        Bus::try_from_bytes_mut(&mut bus_data)?.rewards = rewards;
+    }
+
+    Ok(())
+}
+
+/// Returns the remaining rewards of each bus.
+fn bus_rewards(busses: &[AccountInfo<'_>]) -> Result<Vec<u64>, ProgramError> {
+    let mut rewards = Vec::with_capacity(busses.len());
+    for bus_info in busses {
+        let bus_data = bus_info.data.borrow();
+        rewards.push(Bus::try_from_bytes(&bus_data)?.rewards);
+    }
+    Ok(rewards)
+}
+
+/// Errors if the epoch has ended, the busses have already been rebalanced this epoch, or no bus is
+/// depleted.
+fn validate_rebalance(
+    last_reset_at: i64,
+    last_rebalance_at: i64,
+    epoch_duration: i64,
+    now: i64,
+    rewards: &[u64],
+) -> ProgramResult {
+    if last_reset_at.saturating_add(epoch_duration).le(&now) {
+        return Err(CoalError::NeedsReset.into());
+    }
+    if last_rebalance_at.ge(&last_reset_at) {
+        return Err(CoalError::RebalanceTooSoon.into());
+    }
+    if !is_depleted(rewards) {
+        return Err(CoalError::RebalanceNotNeeded.into());
+    }
+    Ok(())
+}
+
+/// Returns true if any bus holds less than REBALANCE_THRESHOLD_BPS of an even split of the remaining
+/// rewards. Busses with nothing left to spread are never depleted, since rebalancing would not move
+/// anything.
+fn is_depleted(rewards: &[u64]) -> bool {
+    let total = rewards
+        .iter()
+        .fold(0u128, |total, rewards| total.saturating_add(*rewards as u128));
+    let threshold = total
+        .saturating_mul(REBALANCE_THRESHOLD_BPS as u128)
+        .checked_div((rewards.len() as u128).saturating_mul(BASIS_POINTS as u128))
+        .unwrap_or(0);
+    total.gt(&0) && rewards.iter().any(|rewards| (*rewards as u128).lt(&threshold))
+}
+
+/// Returns the rewards of each bus after splitting their total evenly. Any remainder goes to the
+/// busses with the lowest ids.
+fn rebalanced_rewards(rewards: &[u64]) -> Vec<u64> {
+    let bus_count = rewards.len() as u64;
+    let total = rewards
+        .iter()
+        .fold(0u128, |total, rewards| total.saturating_add(*rewards as u128));
+    let share = total.checked_div(bus_count as u128).unwrap_or(0);
+    let remainder = total.checked_rem(bus_count as u128).unwrap_or(0);
+    (0..bus_count)
+        .map(|i| share.saturating_add((i as u128).lt(&remainder) as u128) as u64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebalanced_rewards() {
+        assert_eq!(rebalanced_rewards(&[0, 100, 200, 100]), vec![100, 100, 100, 100]);
+        assert_eq!(rebalanced_rewards(&[0, 0, 0, 7]), vec![2, 2, 2, 1]);
+        assert_eq!(rebalanced_rewards(&[]), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_rebalanced_rewards_preserves_total() {
+        let rewards = [u64::MAX, u64::MAX, 0];
+        let rebalanced = rebalanced_rewards(&rewards);
+        let total: u128 = rebalanced.iter().map(|rewards| *rewards as u128).sum();
+        assert_eq!(total, 2 * u64::MAX as u128);
+        assert!(rebalanced.iter().all(|rewards| rewards.ge(&(u64::MAX / 3 * 2))));
+    }
+
+    #[test]
+    fn test_validate_rebalance() {
+        let depleted = [0, 100, 200, 100];
+        assert!(validate_rebalance(100, 0, 60, 130, &depleted).is_ok());
+        assert!(validate_rebalance(100, 100, 60, 130, &depleted).is_err());
+        assert!(validate_rebalance(100, 120, 60, 130, &depleted).is_err());
+        assert!(validate_rebalance(100, 0, 60, 160, &depleted).is_err());
+        assert_eq!(
+            validate_rebalance(100, 0, 60, 130, &[100, 100, 200, 100]),
+            Err(CoalError::RebalanceNotNeeded.into())
+        );
+    }
+
+    #[test]
+    fn test_is_depleted() {
+        // The even split is 100, so busses below 10 are depleted.
+        assert!(is_depleted(&[0, 100, 200, 100]));
+        assert!(is_depleted(&[9, 130, 131, 130]));
+        assert!(!is_depleted(&[10, 130, 130, 130]));
+        assert!(!is_depleted(&[0, 0, 0, 0]));
+        assert!(!is_depleted(&[]));
+        assert!(is_depleted(&[u64::MAX, u64::MAX, 0]));
+    }
+}
@@ -0,0 +1,66 @@
+use coal_api::{
+    consts::*,
+    loaders::*,
+    state::{Config, Vesting, WoodConfig},
+};
+use coal_utils::spl::transfer_signed;
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, sysvar::Sysvar,
+};
+
+use crate::utils::AccountDeserialize;
+
+/// Release vested pays out the unlocked portion of a vesting account from the treasury.
+pub fn process_release_vested(
+    accounts: &[AccountInfo<'_>],
+    _data: &[u8],
+) -> ProgramResult {
+    // Load accounts.
+    let [signer, beneficiary_info, vesting_info, config_info, treasury_info, treasury_tokens_info, token_program, mint_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_vesting(vesting_info, signer.key, true)?;
+    load_treasury(treasury_info, false)?;
+
+    // Release the unlocked tokens.
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    let mut vesting_data = vesting_info.data.borrow_mut();
+    let vesting = Vesting::try_from_bytes_mut(&mut vesting_data)?;
+    let amount = vesting.unlocked(clock.unix_timestamp);
+    vesting.released = vesting.released.checked_add(amount).unwrap();
+
+    // Release the liability owed to the miner.
+    if vesting.mint.eq(&COAL_MINT_ADDRESS) {
+        load_coal_config(config_info, true)?;
+        load_coal_treasury_tokens(treasury_tokens_info, true)?;
+        let mut config_data = config_info.data.borrow_mut();
+        let config = Config::try_from_bytes_mut(&mut config_data)?;
+        config.outstanding_liabilities = crate::sub_liabilities(config.outstanding_liabilities, amount)?;
+    } else {
+        load_wood_config(config_info, true)?;
+        load_wood_treasury_tokens(treasury_tokens_info, true)?;
+        let mut config_data = config_info.data.borrow_mut();
+        let config = WoodConfig::try_from_bytes_mut(&mut config_data)?;
+        config.outstanding_liabilities = crate::sub_liabilities(config.outstanding_liabilities, amount)?;
+    }
+    load_mint(mint_info, vesting.mint, false)?;
+    load_token_program(token_program, mint_info)?;
+    load_token_account(beneficiary_info, None, &vesting.mint, true)?;
+
+    // Transfer tokens from treasury to beneficiary.
+    transfer_signed(
+        treasury_info,
+        treasury_tokens_info,
+        beneficiary_info,
+        mint_info,
+        token_program,
+        amount,
+        &[&[TREASURY, &[TREASURY_BUMP]]],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use coal_api::{consts::MINER_KEYS, loaders::*, state::{MinerKey, MinerKeys}};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::utils::AccountDeserialize;
+
+/// Remove miner revokes a keypair's permission to submit hashes for a proof account.
+pub fn process_remove_miner(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
+    // Load accounts.
+    let [signer, proof_info, miner_keys_info, miner_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any_proof_with_authority(proof_info, signer.key, false)?;
+    load_miner_keys(miner_keys_info, proof_info.key, true)?;
+    load_any(miner_info, false)?;
+
+    // Clear the key's slot.
+    let mut miner_keys_data = miner_keys_info.data.borrow_mut();
+    let miner_keys = MinerKeys::try_from_bytes_mut(&mut miner_keys_data)?;
+    let slot = miner_keys
+        .keys
+        .iter()
+        .position(|key| key.miner.eq(miner_info.key))
+        .ok_or(ProgramError::InvalidArgument)?;
+    miner_keys.keys[slot] = MinerKey {
+        miner: Pubkey::default(),
+        expires_at: 0,
+        hash_quota: 0,
+        epoch_hashes: 0,
+        epoch_reset_at: 0,
+    };
+
+    Ok(())
+}
+
+/// Closes the miner keys account of a proof, if it was ever created, and returns the rent to the
+/// recipient.
+///
+/// Miner keys are seeded by the proof address. Any proof instruction which closes or moves the proof
+/// must also close its keys, or they would authorize the same miners again once the proof is reopened.
+pub(crate) fn close_miner_keys<'info>(
+    miner_keys_info: &AccountInfo<'info>,
+    proof_info: &AccountInfo<'info>,
+    recipient_info: &AccountInfo<'info>,
+) -> ProgramResult {
+    if !has_miner_keys(miner_keys_info, proof_info.key)? {
+        return Ok(());
+    }
+
+    // Realloc data to zero.
+    miner_keys_info.realloc(0, true)?;
+
+    // Send remaining lamports to the recipient.
+    **recipient_info.lamports.borrow_mut() += miner_keys_info.lamports();
+    **miner_keys_info.lamports.borrow_mut() = 0;
+
+    Ok(())
+}
+
+/// Returns whether the miner keys account of a proof was ever created.
+///
+/// Errors if the account is not the miner keys PDA of the proof, or holds the keys of another proof.
+fn has_miner_keys(miner_keys_info: &AccountInfo, proof: &Pubkey) -> Result<bool, ProgramError> {
+    let miner_keys_address =
+        Pubkey::find_program_address(&[MINER_KEYS, proof.as_ref()], &coal_api::id()).0;
+    if miner_keys_info.key.ne(&miner_keys_address) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if miner_keys_info.data_is_empty() {
+        return Ok(false);
+    }
+    load_miner_keys(miner_keys_info, proof, true)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+
+    use super::*;
+    use crate::utils::Discriminator;
+
+    /// Serializes a miner keys account of a proof.
+    fn miner_keys_data(proof: Pubkey) -> Vec<u8> {
+        let mut data = vec![0; 8];
+        data[0] = MinerKeys::discriminator();
+        data.extend_from_slice(bytemuck::bytes_of(&MinerKeys {
+            proof,
+            ..MinerKeys::zeroed()
+        }));
+        data
+    }
+
+    /// Runs has_miner_keys on the account data at the given address.
+    fn run_has_miner_keys(address: Pubkey, proof: Pubkey, data: &mut [u8]) -> Result<bool, ProgramError> {
+        let owner = coal_api::id();
+        let mut lamports = 500;
+        let info = AccountInfo::new(&address, false, true, &mut lamports, data, &owner, false, 0);
+        has_miner_keys(&info, &proof)
+    }
+
+    fn miner_keys_address(proof: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[MINER_KEYS, proof.as_ref()], &coal_api::id()).0
+    }
+
+    #[test]
+    fn test_has_miner_keys() {
+        let proof = Pubkey::new_unique();
+        let mut data = miner_keys_data(proof);
+        assert_eq!(run_has_miner_keys(miner_keys_address(&proof), proof, &mut data), Ok(true));
+    }
+
+    #[test]
+    fn test_has_miner_keys_skips_uncreated_account() {
+        let proof = Pubkey::new_unique();
+        assert_eq!(run_has_miner_keys(miner_keys_address(&proof), proof, &mut []), Ok(false));
+    }
+
+    #[test]
+    fn test_has_miner_keys_rejects_other_address() {
+        let proof = Pubkey::new_unique();
+        let mut data = miner_keys_data(proof);
+        assert_eq!(
+            run_has_miner_keys(Pubkey::new_unique(), proof, &mut data),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+
+    #[test]
+    fn test_has_miner_keys_rejects_keys_of_other_proof() {
+        let proof = Pubkey::new_unique();
+        let mut data = miner_keys_data(Pubkey::new_unique());
+        assert_eq!(
+            run_has_miner_keys(miner_keys_address(&proof), proof, &mut data),
+            Err(ProgramError::InvalidAccountData)
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use coal_api::state::{Config, WoodConfig};
+use coal_api::{
+    consts::{BASIS_POINTS, JACKPOT_PAYOUT_BPS, LATE_RESETS_BEFORE_ADAPT, MAX_EPOCH_DURATION, ONE_MINUTE},
+    state::{Config, WoodConfig},
+};
 use solana_program::{
     account_info::AccountInfo, 
     entrypoint::ProgramResult,
@@ -13,18 +16,20 @@ use reset_wood::*;
 mod reset_coal;
 mod reset_wood;
 
-pub fn process_reset<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
-    let config_info = &accounts[9];
+pub fn process_reset(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let Some(config_info) = accounts.get(1) else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
 
-    if config_info.data.borrow()[0].eq(&(Config::discriminator() as u8)) {
+    if config_info.data.borrow()[0].eq(&Config::discriminator()) {
         return process_reset_coal(accounts, data)
     }
 
-    if config_info.data.borrow()[0].eq(&(WoodConfig::discriminator() as u8)) {
+    if config_info.data.borrow()[0].eq(&WoodConfig::discriminator()) {
         return process_reset_wood(accounts, data)
     }
 
-    return Err(ProgramError::InvalidAccountData);    
+    Err(ProgramError::InvalidAccountData)
 }
 
 /// This function calculates what the new reward rate should be based on how many total rewards
@@ -60,4 +65,86 @@ pub(crate) fn calculate_new_reward_rate(
     let new_rate_smoothed = new_rate.min(new_rate_max).max(new_rate_min);
     // Prevent reward rate from dropping below 1 or exceeding target_rewards and return.
     new_rate_smoothed.max(1).min(bus_rewards)
-}
\ No newline at end of file
+}
+
+/// This function calculates the quantity of tokens added to the jackpot at reset.
+///
+/// The jackpot takes its share of the epoch's emissions out of the rewards the busses did not pay out,
+/// so the tokens emitted in an epoch never exceed the epoch's emissions.
+pub(crate) fn calculate_jackpot_contribution(
+    epoch_rewards: u64,
+    remaining_rewards: u64,
+    jackpot_bps: u64,
+) -> u64 {
+    (epoch_rewards as u128)
+        .saturating_mul(jackpot_bps as u128)
+        .saturating_div(BASIS_POINTS as u128)
+        .min(remaining_rewards as u128) as u64
+}
+
+/// This function calculates the share of the jackpot held by each bus for the next epoch.
+///
+/// The jackpot is split evenly across the busses. The remainder of the division is left unsplit.
+pub(crate) fn calculate_bus_jackpot(jackpot: u64, bus_count: u64) -> u64 {
+    jackpot.checked_div(bus_count).unwrap_or(0)
+}
+
+/// This function calculates the share of the jackpot won by a hash of the given difficulty.
+///
+/// A hash which reaches the jackpot difficulty above the minimum difficulty wins a fixed share of the
+/// jackpot. The share doubles for every bit of difficulty beyond it, up to the entire jackpot.
+pub(crate) fn calculate_jackpot_reward(
+    jackpot: u64,
+    difficulty: u64,
+    min_difficulty: u64,
+    jackpot_difficulty: u64,
+) -> u64 {
+    // A jackpot difficulty of zero disables the jackpot.
+    if jackpot_difficulty.eq(&0) {
+        return 0;
+    }
+
+    // Calculate the share of the jackpot won.
+    let Some(extra_difficulty) =
+        difficulty.checked_sub(min_difficulty.saturating_add(jackpot_difficulty))
+    else {
+        return 0;
+    };
+    let payout_bps = JACKPOT_PAYOUT_BPS
+        .saturating_mul(2u64.saturating_pow(extra_difficulty.min(u32::MAX as u64) as u32))
+        .min(BASIS_POINTS);
+    (jackpot as u128)
+        .saturating_mul(payout_bps as u128)
+        .saturating_div(BASIS_POINTS as u128) as u64
+}
+
+/// This function calculates the epoch duration and the count of consecutive late resets after a reset
+/// which happened the given number of seconds after the epoch ended.
+///
+/// A reset is late if it happens more than half an epoch after the epoch ended. If resets are
+/// consistently late, the epoch is lengthened by a minute, up to the maximum epoch duration.
+///
+/// The epoch is only ever lengthened here. Resets which are early or on time leave it unchanged, so
+/// shortening it again is left to the admin through ConfigureEpoch.
+pub(crate) fn calculate_epoch_duration(
+    epoch_duration: i64,
+    late_resets: u64,
+    lateness: i64,
+) -> (i64, u64) {
+    // The count restarts with any reset on time.
+    if lateness.le(&epoch_duration.saturating_div(2)) {
+        return (epoch_duration, 0);
+    }
+
+    // Lengthen the epoch once enough consecutive resets are late.
+    let late_resets = late_resets.saturating_add(1);
+    if late_resets.lt(&LATE_RESETS_BEFORE_ADAPT) {
+        return (epoch_duration, late_resets);
+    }
+    (
+        epoch_duration
+            .saturating_add(ONE_MINUTE)
+            .min(MAX_EPOCH_DURATION.max(epoch_duration)),
+        0,
+    )
+}
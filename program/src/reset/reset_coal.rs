@@ -1,8 +1,9 @@
 use coal_api::{
     consts::*,
+    emission,
     error::CoalError,
     loaders::*,
-    state::{Config, Bus},
+    state::{Bus, Config, Leaderboard},
 };
 use coal_utils::AccountDeserialize;
 use solana_program::{
@@ -10,50 +11,81 @@ use solana_program::{
 };
 use spl_token::state::Mint;
 
-use crate::calculate_new_reward_rate;
+use crate::{
+    calculate_bus_jackpot, calculate_epoch_duration, calculate_jackpot_contribution,
+    calculate_new_reward_rate,
+};
 
 /// Reset tops up the bus balances, updates the base reward rate, and sets up the ORE program for the next epoch.
-pub fn process_reset_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data: &[u8]) -> ProgramResult {
+pub fn process_reset_coal(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     // Load accounts.
-    let [signer, bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info, bus_7_info, config_info, mint_info, treasury_info, treasury_tokens_info, token_program] =
-        accounts
+    let (required_accounts, optional_accounts) = accounts.split_at(accounts.len().min(6));
+    let [signer, config_info, mint_info, treasury_info, treasury_tokens_info, token_program] =
+        required_accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     load_signer(signer)?;
-    load_coal_bus(bus_0_info, 0, true)?;
-    load_coal_bus(bus_1_info, 1, true)?;
-    load_coal_bus(bus_2_info, 2, true)?;
-    load_coal_bus(bus_3_info, 3, true)?;
-    load_coal_bus(bus_4_info, 4, true)?;
-    load_coal_bus(bus_5_info, 5, true)?;
-    load_coal_bus(bus_6_info, 6, true)?;
-    load_coal_bus(bus_7_info, 7, true)?;
     load_coal_config(config_info, true)?;
     load_mint(mint_info, COAL_MINT_ADDRESS, true)?;
     load_treasury(treasury_info, true)?;
     load_coal_treasury_tokens(treasury_tokens_info, true)?;
     load_program(token_program, spl_token::id())?;
-    let busses: [&AccountInfo; BUS_COUNT] = [
-        bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info,
-        bus_7_info,
-    ];
 
-    // Validate enough time has passed since the last reset.
+    // Validate the bus accounts.
+    //
+    // Every bus of the resource must be provided, ordered by id, followed by the leaderboard.
     let mut config_data = config_info.data.borrow_mut();
     let config = Config::try_from_bytes_mut(&mut config_data)?;
+    let bus_count = config.bus_count;
+    let (busses, optional_accounts) =
+        optional_accounts.split_at(optional_accounts.len().min(bus_count as usize));
+    if (busses.len() as u64).ne(&bus_count) {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    for (i, bus_info) in busses.iter().enumerate() {
+        load_coal_bus(bus_info, i as u64, true)?;
+    }
+    let leaderboard_info = optional_accounts
+        .first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if leaderboard_info.key.ne(&COAL_LEADERBOARD_ADDRESS) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Validate enough time has passed since the last reset.
     let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
-    if config
-        .last_reset_at
-        .saturating_add(COAL_EPOCH_DURATION)
-        .gt(&clock.unix_timestamp)
-    {
+    let epoch_ended_at = config.last_reset_at.saturating_add(config.epoch_duration);
+    if epoch_ended_at.gt(&clock.unix_timestamp) {
         return Ok(());
     }
 
+    // Lengthen the epoch if resets are consistently late.
+    //
+    // The ended epoch keeps the duration it started with. The first reset is skipped, since there
+    // was no epoch to end.
+    let last_epoch_duration = config.epoch_duration;
+    if config.last_reset_at.gt(&0) {
+        (config.epoch_duration, config.late_resets) = calculate_epoch_duration(
+            config.epoch_duration,
+            config.late_resets,
+            clock.unix_timestamp.saturating_sub(epoch_ended_at),
+        );
+    }
+
     // Update timestamp.
     config.last_reset_at = clock.unix_timestamp;
 
+    // Move the standings of the ended epoch into the previous epoch.
+    //
+    // Resources initialized before leaderboards existed have none until InitLeaderboard runs.
+    if !leaderboard_info.data_is_empty() {
+        load_coal_leaderboard(leaderboard_info, true)?;
+        let mut leaderboard_data = leaderboard_info.data.borrow_mut();
+        let leaderboard = Leaderboard::try_from_bytes_mut(&mut leaderboard_data)?;
+        leaderboard.roll(config.last_reset_at);
+    }
+
     // Max supply check.
     let mint = Mint::unpack(&mint_info.data.borrow()).expect("Failed to parse mint");
     if mint.supply.ge(&MAX_COAL_SUPPLY) {
@@ -63,19 +95,20 @@ pub fn process_reset_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
     // For each 5% of total supply, reduce the BUS_EPOCH_REWARDS and MAX_EPOCH_REWARDS by 50%
     // The halving is done to incentivize the accumulation of the token.
     // Halving should only occur at 5% intervals.
-    let supply_percentage = (mint.supply as f64 / MAX_COAL_SUPPLY as f64) * 100.0;
-    let halving_factor = 2u64.pow((supply_percentage / 5.0) as u32);
-    let adjusted_target_rewards = TARGET_COAL_EPOCH_REWARDS / halving_factor;
-    let adjusted_bus_epoch_rewards = BUS_COAL_EPOCH_REWARDS / halving_factor;
-    let adjusted_max_epoch_rewards = MAX_COAL_EPOCH_REWARDS / halving_factor;   
+    //
+    // Epoch rewards scale with the epoch duration. The target is set for the ended epoch, which the
+    // theoretical rewards were mined in, and the busses are funded for the epoch starting now.
+    let adjusted_target_rewards = emission::target_epoch_rewards(mint.supply, last_epoch_duration);
+    let adjusted_bus_epoch_rewards =
+        emission::bus_epoch_rewards(mint.supply, bus_count, config.epoch_duration);
 
     // Reset bus accounts and calculate actual rewards mined since last reset.
     let mut total_remaining_rewards = 0u64;
     let mut total_theoretical_rewards = 0u64;
     let mut top_balance = 0u64;
-    for i in 0..BUS_COUNT {
+    for bus_info in busses {
         // Parse bus account.
-        let mut bus_data = busses[i].data.borrow_mut();
+        let mut bus_data = bus_info.data.borrow_mut();
         let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
 
         // Track top balance.
@@ -88,12 +121,30 @@ pub fn process_reset_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
         total_theoretical_rewards =
             total_theoretical_rewards.saturating_add(bus.theoretical_rewards);
 
+        // Move the liabilities and jackpot share held on the bus onto the config.
+        config.outstanding_liabilities =
+            crate::add_liabilities(config.outstanding_liabilities, bus.liabilities)?;
+        config.jackpot = config.jackpot.saturating_add(bus.jackpot);
+        bus.liabilities = 0;
+        bus.jackpot = 0;
+
         // Reset bus account for new epoch.
         bus.rewards = adjusted_bus_epoch_rewards;
         bus.theoretical_rewards = 0;
         bus.top_balance = 0;
     }
-    let total_epoch_rewards = adjusted_max_epoch_rewards.saturating_sub(total_remaining_rewards);
+
+    // Calculate the rewards paid out since last reset.
+    //
+    // Busses funded before the funded rewards were tracked are assumed to have been funded with the max
+    // epoch rewards.
+    let funded_epoch_rewards = if config.total_epoch_rewards.gt(&0) {
+        config.total_epoch_rewards
+    } else {
+        emission::max_epoch_rewards(mint.supply, last_epoch_duration)
+    };
+    let total_epoch_rewards = funded_epoch_rewards.saturating_sub(total_remaining_rewards);
+    config.total_epoch_rewards = adjusted_bus_epoch_rewards.saturating_mul(bus_count);
 
     // Update global top balance.
     config.top_balance = top_balance;
@@ -108,8 +159,8 @@ pub fn process_reset_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
         SMOOTHING_FACTOR
     );
 
-    let adjusted_base_reward_threshold = BASE_COAL_REWARD_RATE_MIN_THRESHOLD / halving_factor;
-    let adjusted_base_reward_max_threshold = BASE_COAL_REWARD_RATE_MAX_THRESHOLD / halving_factor;
+    let (adjusted_base_reward_threshold, adjusted_base_reward_max_threshold) =
+        emission::base_reward_rate_thresholds(mint.supply);
    
     // If base reward rate is too low, increment min difficulty by 1 and double base reward rate.
     if config.base_reward_rate.le(&adjusted_base_reward_threshold) {
@@ -123,11 +174,23 @@ pub fn process_reset_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
         config.base_reward_rate = config.base_reward_rate.checked_div(2).unwrap();
     }
 
-    
+    // Add a share of the epoch's emissions to the jackpot.
+    //
+    // The jackpot is funded out of the rewards the busses did not pay out, and is minted into the
+    // treasury along with the rewards paid out.
+    let jackpot_contribution = calculate_jackpot_contribution(
+        funded_epoch_rewards,
+        total_remaining_rewards,
+        config.jackpot_bps,
+    );
+
     // Fund the treasury token account.
     let amount = MAX_COAL_SUPPLY
         .saturating_sub(mint.supply)
-        .min(total_epoch_rewards);
+        .min(total_epoch_rewards.saturating_add(jackpot_contribution));
+    config.jackpot = config
+        .jackpot
+        .saturating_add(amount.saturating_sub(total_epoch_rewards));
     solana_program::program::invoke_signed(
         &spl_token::instruction::mint_to(
             &spl_token::id(),
@@ -146,15 +209,34 @@ pub fn process_reset_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
         &[&[TREASURY, &[TREASURY_BUMP]]],
     )?;
 
+    // Split the jackpot evenly across the busses.
+    //
+    // Mining never writes the config, so each bus pays out of its own share. The remainder is held
+    // on the config until the next reset.
+    let bus_jackpot = calculate_bus_jackpot(config.jackpot, bus_count);
+    for bus_info in busses {
+        let mut bus_data = bus_info.data.borrow_mut();
+        let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
+        bus.jackpot = bus_jackpot;
+    }
+    config.jackpot = config
+        .jackpot
+        .saturating_sub(bus_jackpot.saturating_mul(bus_count));
+
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use rand::{distributions::Uniform, Rng};
-    use crate::calculate_new_reward_rate;
+    use crate::{
+        calculate_bus_jackpot, calculate_epoch_duration, calculate_jackpot_contribution,
+        calculate_jackpot_reward, calculate_new_reward_rate,
+    };
     use coal_api::consts::{
-        BASE_COAL_REWARD_RATE_MIN_THRESHOLD, BUS_COAL_EPOCH_REWARDS, MAX_COAL_EPOCH_REWARDS, SMOOTHING_FACTOR,
+        BASE_COAL_REWARD_RATE_MIN_THRESHOLD, BUS_COAL_EPOCH_REWARDS, COAL_EPOCH_DURATION,
+        INITIAL_JACKPOT_BPS, INITIAL_JACKPOT_DIFFICULTY, LATE_RESETS_BEFORE_ADAPT,
+        MAX_COAL_EPOCH_REWARDS, MAX_EPOCH_DURATION, MAX_JACKPOT_BPS, ONE_MINUTE, SMOOTHING_FACTOR,
         TARGET_COAL_EPOCH_REWARDS,
     };
 
@@ -320,4 +402,140 @@ mod tests {
         );
         assert!(new_rate.eq(&1u64.saturating_mul(SMOOTHING_FACTOR)));
     }
+
+    #[test]
+    fn test_calculate_jackpot_contribution() {
+        let contribution = calculate_jackpot_contribution(MAX_COAL_EPOCH_REWARDS, MAX_COAL_EPOCH_REWARDS, 200);
+        assert!(contribution.eq(&(MAX_COAL_EPOCH_REWARDS / 50)));
+    }
+
+    #[test]
+    fn test_calculate_jackpot_contribution_limited_by_remaining() {
+        let contribution = calculate_jackpot_contribution(MAX_COAL_EPOCH_REWARDS, 1_000, MAX_JACKPOT_BPS);
+        assert!(contribution.eq(&1_000));
+        let contribution = calculate_jackpot_contribution(MAX_COAL_EPOCH_REWARDS, 0, MAX_JACKPOT_BPS);
+        assert!(contribution.eq(&0));
+    }
+
+    #[test]
+    fn test_calculate_jackpot_contribution_max_inputs() {
+        let contribution = calculate_jackpot_contribution(u64::MAX, u64::MAX, u64::MAX);
+        assert!(contribution.eq(&u64::MAX));
+    }
+
+    #[test]
+    fn test_calculate_jackpot_reward_disabled() {
+        let reward = calculate_jackpot_reward(1_000_000, 64, 1, 0);
+        assert!(reward.eq(&0));
+    }
+
+    #[test]
+    fn test_calculate_jackpot_reward_below_difficulty() {
+        let reward = calculate_jackpot_reward(1_000_000, 12, 1, 12);
+        assert!(reward.eq(&0));
+    }
+
+    #[test]
+    fn test_calculate_jackpot_reward_doubles() {
+        assert!(calculate_jackpot_reward(1_000_000, 13, 1, 12).eq(&250_000));
+        assert!(calculate_jackpot_reward(1_000_000, 14, 1, 12).eq(&500_000));
+        assert!(calculate_jackpot_reward(1_000_000, 15, 1, 12).eq(&1_000_000));
+        assert!(calculate_jackpot_reward(1_000_000, 64, 1, 12).eq(&1_000_000));
+        assert!(calculate_jackpot_reward(u64::MAX, u64::MAX, 0, 1).eq(&u64::MAX));
+    }
+
+    #[test]
+    fn test_calculate_bus_jackpot() {
+        assert!(calculate_bus_jackpot(1_000, 8).eq(&125));
+        assert!(calculate_bus_jackpot(1_003, 8).eq(&125));
+        assert!(calculate_bus_jackpot(7, 8).eq(&0));
+        assert!(calculate_bus_jackpot(1_000, 0).eq(&0));
+    }
+
+    #[test]
+    fn test_jackpot_simulation() {
+        // Simulate epochs of random bus payouts and hash difficulties, checking that the jackpot never
+        // pays out more than it holds and that emissions never exceed the epoch's emissions.
+        let mut rng = rand::thread_rng();
+        let min_difficulty = 10u64;
+        let mut jackpot = 0u64;
+        let mut total_contributed = 0u64;
+        let mut total_won = 0u64;
+        for _ in 0..FUZZ_SIZE {
+            // Reset.
+            let remaining_rewards: u64 = rng.sample(Uniform::new_inclusive(0, MAX_COAL_EPOCH_REWARDS));
+            let paid_rewards = MAX_COAL_EPOCH_REWARDS - remaining_rewards;
+            let contribution =
+                calculate_jackpot_contribution(MAX_COAL_EPOCH_REWARDS, remaining_rewards, INITIAL_JACKPOT_BPS);
+            assert!(paid_rewards.saturating_add(contribution).le(&MAX_COAL_EPOCH_REWARDS));
+            jackpot += contribution;
+            total_contributed += contribution;
+
+            // Mine.
+            for _ in 0..rng.sample(Uniform::new(0, 8)) {
+                let difficulty: u64 = rng.sample(Uniform::new(min_difficulty, min_difficulty + 20));
+                let reward = calculate_jackpot_reward(jackpot, difficulty, min_difficulty, INITIAL_JACKPOT_DIFFICULTY);
+                assert!(reward.le(&jackpot));
+                if difficulty.lt(&(min_difficulty + INITIAL_JACKPOT_DIFFICULTY)) {
+                    assert!(reward.eq(&0));
+                }
+                jackpot -= reward;
+                total_won += reward;
+            }
+        }
+        assert!(total_contributed.eq(&(total_won + jackpot)));
+    }
+
+    #[test]
+    fn test_calculate_epoch_duration_on_time() {
+        let (epoch_duration, late_resets) = calculate_epoch_duration(COAL_EPOCH_DURATION, 5, 0);
+        assert!(epoch_duration.eq(&COAL_EPOCH_DURATION));
+        assert!(late_resets.eq(&0));
+        let (_, late_resets) = calculate_epoch_duration(COAL_EPOCH_DURATION, 5, COAL_EPOCH_DURATION / 2);
+        assert!(late_resets.eq(&0));
+    }
+
+    #[test]
+    fn test_calculate_epoch_duration_late() {
+        let (epoch_duration, late_resets) =
+            calculate_epoch_duration(COAL_EPOCH_DURATION, 0, COAL_EPOCH_DURATION / 2 + 1);
+        assert!(epoch_duration.eq(&COAL_EPOCH_DURATION));
+        assert!(late_resets.eq(&1));
+    }
+
+    #[test]
+    fn test_calculate_epoch_duration_adapts() {
+        // Simulate consistently late resets until the epoch reaches its maximum duration.
+        let mut epoch_duration = COAL_EPOCH_DURATION;
+        let mut late_resets = 0;
+        for i in 1..=1_000u64 {
+            let previous_duration = epoch_duration;
+            (epoch_duration, late_resets) = calculate_epoch_duration(epoch_duration, late_resets, ONE_MINUTE * 60);
+            if i % LATE_RESETS_BEFORE_ADAPT == 0 {
+                assert!(late_resets.eq(&0));
+                assert!(epoch_duration.eq(&(previous_duration + ONE_MINUTE).min(MAX_EPOCH_DURATION)));
+            } else {
+                assert!(epoch_duration.eq(&previous_duration));
+            }
+        }
+        assert!(epoch_duration.eq(&MAX_EPOCH_DURATION));
+    }
+
+    #[test]
+    fn test_calculate_new_reward_rate_scaled_epoch() {
+        // Hashes are paid per minute, so an epoch twice as long at the same hashrate mines twice the
+        // rewards, which meets the target scaled to the epoch duration.
+        let current_rate = 1000;
+        let epoch_duration = COAL_EPOCH_DURATION * 2;
+        let target_rewards = coal_api::emission::target_epoch_rewards(0, epoch_duration);
+        let new_rate = calculate_new_reward_rate(
+            current_rate,
+            TARGET_COAL_EPOCH_REWARDS * 2,
+            target_rewards,
+            coal_api::emission::bus_epoch_rewards(0, 8, epoch_duration),
+            SMOOTHING_FACTOR,
+            SMOOTHING_FACTOR
+        );
+        assert!(new_rate.eq(&current_rate));
+    }
 }
@@ -1,66 +1,116 @@
 use coal_api::{
     consts::*,
+    emission,
+    error::CoalError,
     loaders::*,
-    state::{Bus, WoodConfig},
+    state::{Bus, Leaderboard, WoodConfig},
 };
 use coal_utils::AccountDeserialize;
 use solana_program::{
-    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg, program_error::ProgramError, sysvar::Sysvar
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg, program_error::ProgramError, program_pack::Pack, sysvar::Sysvar
 };
+use spl_token::state::Mint;
 
-use crate::calculate_new_reward_rate;
+use crate::{
+    calculate_bus_jackpot, calculate_epoch_duration, calculate_jackpot_contribution,
+    calculate_new_reward_rate,
+};
 
-pub fn process_reset_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data: &[u8]) -> ProgramResult {
+pub fn process_reset_wood(accounts: &[AccountInfo<'_>], _data: &[u8]) -> ProgramResult {
     msg!("Processing reset for WOOD");
     // Load accounts.
-    let [signer, bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info, bus_7_info, config_info, mint_info, treasury_info, treasury_tokens_info, token_program] =
-        accounts
+    let (required_accounts, optional_accounts) = accounts.split_at(accounts.len().min(6));
+    let [signer, config_info, mint_info, treasury_info, treasury_tokens_info, token_program] =
+        required_accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     load_signer(signer)?;
-    load_wood_bus(bus_0_info, 0, true)?;
-    load_wood_bus(bus_1_info, 1, true)?;
-    load_wood_bus(bus_2_info, 2, true)?;
-    load_wood_bus(bus_3_info, 3, true)?;
-    load_wood_bus(bus_4_info, 4, true)?;
-    load_wood_bus(bus_5_info, 5, true)?;
-    load_wood_bus(bus_6_info, 6, true)?;
-    load_wood_bus(bus_7_info, 7, true)?;
     load_wood_config(config_info, true)?;
     load_mint(mint_info, WOOD_MINT_ADDRESS, true)?;
     load_treasury(treasury_info, true)?;
     load_wood_treasury_tokens(treasury_tokens_info, true)?;
     load_program(token_program, spl_token::id())?;
 
-    let busses: [&AccountInfo; BUS_COUNT] = [
-        bus_0_info, bus_1_info, bus_2_info, bus_3_info, bus_4_info, bus_5_info, bus_6_info,
-        bus_7_info,
-    ];
-
-    // Validate enough time has passed since the last reset.
+    // Validate the bus accounts.
+    //
+    // Every bus of the resource must be provided, ordered by id, followed by the leaderboard.
     let mut config_data = config_info.data.borrow_mut();
     let config = WoodConfig::try_from_bytes_mut(&mut config_data)?;
+    let bus_count = config.bus_count;
+    let (busses, optional_accounts) =
+        optional_accounts.split_at(optional_accounts.len().min(bus_count as usize));
+    if (busses.len() as u64).ne(&bus_count) {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    for (i, bus_info) in busses.iter().enumerate() {
+        load_wood_bus(bus_info, i as u64, true)?;
+    }
+    let leaderboard_info = optional_accounts
+        .first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if leaderboard_info.key.ne(&WOOD_LEADERBOARD_ADDRESS) {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    // Validate enough time has passed since the last reset.
     let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
-    if config
-        .last_reset_at
-        .saturating_add(WOOD_EPOCH_DURATION)
-        .gt(&clock.unix_timestamp)
-    {
+    let epoch_ended_at = config.last_reset_at.saturating_add(config.epoch_duration);
+    if epoch_ended_at.gt(&clock.unix_timestamp) {
         return Ok(());
     }
 
+    // Lengthen the epoch if resets are consistently late.
+    //
+    // The first reset is skipped, since there was no epoch to end.
+    if config.last_reset_at.gt(&0) {
+        (config.epoch_duration, config.late_resets) = calculate_epoch_duration(
+            config.epoch_duration,
+            config.late_resets,
+            clock.unix_timestamp.saturating_sub(epoch_ended_at),
+        );
+    }
+
     // Update timestamp.
     config.last_reset_at = clock.unix_timestamp;
 
+    // Move the standings of the ended epoch into the previous epoch.
+    //
+    // Resources initialized before leaderboards existed have none until InitLeaderboard runs.
+    if !leaderboard_info.data_is_empty() {
+        load_wood_leaderboard(leaderboard_info, true)?;
+        let mut leaderboard_data = leaderboard_info.data.borrow_mut();
+        let leaderboard = Leaderboard::try_from_bytes_mut(&mut leaderboard_data)?;
+        leaderboard.roll(config.last_reset_at);
+    }
+
+    // Max supply check.
+    let mint = Mint::unpack(&mint_info.data.borrow()).expect("Failed to parse mint");
+    if mint.supply.ge(&MAX_WOOD_SUPPLY) {
+        return Err(CoalError::MaxSupply.into());
+    }
+
+    // For each 10% of max supply in circulation, halve the bus reward limits and the base reward rate
+    // thresholds. The decay keeps WOOD emissions converging towards the max supply.
+    let decay_factor = wood_decay_factor(mint.supply);
+    let (min_reward_rate_threshold, max_reward_rate_threshold) =
+        wood_reward_rate_thresholds(decay_factor);
+    // The bus reward limits also scale with the duration of the epoch starting now.
+    let adjusted_min_epoch_rewards =
+        emission::scale_to_epoch(MIN_WOOD_EPOCH_REWARDS, config.epoch_duration, WOOD_EPOCH_DURATION)
+            / decay_factor;
+    let adjusted_max_epoch_rewards =
+        emission::scale_to_epoch(MAX_WOOD_EPOCH_REWARDS, config.epoch_duration, WOOD_EPOCH_DURATION)
+            / decay_factor;
+
     // Reset bus accounts and calculate reward rates for next epoch.
     let mut top_balance: u64 = 0u64;
     let mut total_remaining_rewards = 0u64;
     let mut total_theoretical_rewards = 0u64;
     
-    for i in 0..BUS_COUNT {
+    for bus_info in busses {
         // Parse bus account.
-        let mut bus_data = busses[i].data.borrow_mut();
+        let mut bus_data = bus_info.data.borrow_mut();
         let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
 
         // Track top balance.
@@ -71,6 +121,13 @@ pub fn process_reset_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
         total_remaining_rewards = total_remaining_rewards.saturating_add(bus.rewards);
         total_theoretical_rewards = total_theoretical_rewards.saturating_add(bus.theoretical_rewards);
 
+        // Move the liabilities and jackpot share held on the bus onto the config.
+        config.outstanding_liabilities =
+            crate::add_liabilities(config.outstanding_liabilities, bus.liabilities)?;
+        config.jackpot = config.jackpot.saturating_add(bus.jackpot);
+        bus.liabilities = 0;
+        bus.jackpot = 0;
+
         // Reset bus account for new epoch.
         bus.theoretical_rewards = 0;
         bus.top_balance = 0;
@@ -78,6 +135,17 @@ pub fn process_reset_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
 
     let total_epoch_rewards = config.total_epoch_rewards.saturating_sub(total_remaining_rewards).max(0);
 
+    // Add a share of the epoch's emissions to the jackpot.
+    //
+    // The jackpot is funded out of the rewards the busses did not pay out, instead of carrying them
+    // over to the next epoch.
+    let jackpot_contribution = calculate_jackpot_contribution(
+        config.total_epoch_rewards,
+        total_remaining_rewards,
+        config.jackpot_bps,
+    );
+    let total_remaining_rewards = total_remaining_rewards.saturating_sub(jackpot_contribution);
+
     // Calculate propogation for next epoch.
     let mut next_epoch_rewards = 0u64;
     // If total_epoch_rewards is 0, double propogation rate (i.e. 10%).
@@ -87,14 +155,14 @@ pub fn process_reset_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
         WOOD_PROPOGATION_RATE
     };
 
-    for i in 0..BUS_COUNT {
-        let mut bus_data = busses[i].data.borrow_mut();
+    for bus_info in busses {
+        let mut bus_data = bus_info.data.borrow_mut();
         let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
         
         // Distribute remaining rewards to each bus with 5% growth rate.
-        bus.rewards = total_remaining_rewards.saturating_div(BUS_COUNT as u64).saturating_add(
+        bus.rewards = total_remaining_rewards.saturating_div(bus_count).saturating_add(
             bus.rewards.saturating_div(propogation_rate)
-        ).max(MIN_WOOD_EPOCH_REWARDS).min(MAX_WOOD_EPOCH_REWARDS);
+        ).max(adjusted_min_epoch_rewards).min(adjusted_max_epoch_rewards);
         next_epoch_rewards = next_epoch_rewards.saturating_add(bus.rewards);
     }
     
@@ -105,7 +173,7 @@ pub fn process_reset_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
     config.total_epoch_rewards = next_epoch_rewards;
 
     // Update base reward rate for next epoch.
-    let target_rewards = next_epoch_rewards.saturating_div(BUS_COUNT as u64);
+    let target_rewards = next_epoch_rewards.saturating_div(bus_count);
     // Rewards remain unchanged if total_epoch_rewards is 0.
     if total_epoch_rewards.gt(&0) {
         config.base_reward_rate = calculate_new_reward_rate(
@@ -119,19 +187,27 @@ pub fn process_reset_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
     }
 
     // If base reward rate is too low, increment min difficulty by 1 and double base reward rate.
-    if config.base_reward_rate.le(&BASE_WOOD_REWARD_RATE_MIN_THRESHOLD) {
+    if config.base_reward_rate.le(&min_reward_rate_threshold) {
         config.min_difficulty = config.min_difficulty.checked_add(1).unwrap();
         config.base_reward_rate = config.base_reward_rate.checked_mul(2).unwrap();
     }
 
     // If base reward rate is too high, decrement min difficulty by 1 and halve base reward rate.
-    if config.base_reward_rate.ge(&BASE_WOOD_REWARD_RATE_MAX_THRESHOLD) && config.min_difficulty.gt(&1) {
+    if config.base_reward_rate.ge(&max_reward_rate_threshold) && config.min_difficulty.gt(&1) {
         config.min_difficulty = config.min_difficulty.checked_sub(1).unwrap();
         config.base_reward_rate = config.base_reward_rate.checked_div(2).unwrap();
     }
 
     
     // Fund the treasury token account.
+    //
+    // The jackpot contribution is minted into the treasury along with the rewards paid out.
+    let amount = MAX_WOOD_SUPPLY
+        .saturating_sub(mint.supply)
+        .min(total_epoch_rewards.saturating_add(jackpot_contribution));
+    config.jackpot = config
+        .jackpot
+        .saturating_add(amount.saturating_sub(total_epoch_rewards));
     solana_program::program::invoke_signed(
         &spl_token::instruction::mint_to(
             &spl_token::id(),
@@ -139,7 +215,7 @@ pub fn process_reset_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
             treasury_tokens_info.key,
             treasury_info.key,
             &[treasury_info.key],
-            total_epoch_rewards,
+            amount,
         )?,
         &[
             token_program.clone(),
@@ -150,5 +226,59 @@ pub fn process_reset_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], _data:
         &[&[TREASURY, &[TREASURY_BUMP]]],
     )?;
 
+    // Split the jackpot evenly across the busses.
+    //
+    // Mining never writes the config, so each bus pays out of its own share. The remainder is held
+    // on the config until the next reset.
+    let bus_jackpot = calculate_bus_jackpot(config.jackpot, bus_count);
+    for bus_info in busses {
+        let mut bus_data = bus_info.data.borrow_mut();
+        let bus = Bus::try_from_bytes_mut(&mut bus_data)?;
+        bus.jackpot = bus_jackpot;
+    }
+    config.jackpot = config
+        .jackpot
+        .saturating_sub(bus_jackpot.saturating_mul(bus_count));
+
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Returns the factor the WOOD bus reward limits and reward rate thresholds are divided by at the
+/// given supply. It doubles for each 10% of max supply in circulation.
+fn wood_decay_factor(supply: u64) -> u64 {
+    2u64.saturating_pow((supply / WOOD_DECAY_INTERVAL) as u32)
+}
+
+/// Returns the min and max base reward rate thresholds after decay.
+fn wood_reward_rate_thresholds(decay_factor: u64) -> (u64, u64) {
+    (
+        BASE_WOOD_REWARD_RATE_MIN_THRESHOLD / decay_factor,
+        BASE_WOOD_REWARD_RATE_MAX_THRESHOLD / decay_factor,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wood_reward_rate_thresholds_decay() {
+        assert_eq!(
+            wood_reward_rate_thresholds(wood_decay_factor(0)),
+            (BASE_WOOD_REWARD_RATE_MIN_THRESHOLD, BASE_WOOD_REWARD_RATE_MAX_THRESHOLD)
+        );
+        for i in 1..10u64 {
+            let supply = WOOD_DECAY_INTERVAL * i;
+            let factor = 2u64.pow(i as u32);
+            assert_eq!(wood_decay_factor(supply), factor);
+            assert_eq!(wood_decay_factor(supply - 1), factor / 2);
+            assert_eq!(
+                wood_reward_rate_thresholds(wood_decay_factor(supply)),
+                (
+                    BASE_WOOD_REWARD_RATE_MIN_THRESHOLD / factor,
+                    BASE_WOOD_REWARD_RATE_MAX_THRESHOLD / factor
+                )
+            );
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use coal_api::{
+    consts::*,
+    error::CoalError,
+    instruction::SmeltArgs,
+    loaders::*,
+    state::IngotConfig,
+};
+use coal_utils::spl::{burn, mint_to_signed};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program_error::ProgramError, sysvar::Sysvar,
+};
+
+use crate::utils::AccountDeserialize;
+
+/// Smelt burns COAL and WOOD from the signer according to the smelting recipe and mints INGOT
+/// to the beneficiary.
+pub fn process_smelt(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = SmeltArgs::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+
+    // Load accounts.
+    let [signer, config_info, coal_mint_info, wood_mint_info, ingot_mint_info, coal_tokens_info, wood_tokens_info, beneficiary_info, treasury_info, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_ingot_config(config_info, true)?;
+    load_mint(coal_mint_info, COAL_MINT_ADDRESS, true)?;
+    load_mint(wood_mint_info, WOOD_MINT_ADDRESS, true)?;
+    load_mint(ingot_mint_info, INGOT_MINT_ADDRESS, true)?;
+    load_token_account(coal_tokens_info, Some(signer.key), &COAL_MINT_ADDRESS, true)?;
+    load_token_account(wood_tokens_info, Some(signer.key), &WOOD_MINT_ADDRESS, true)?;
+    load_token_account(beneficiary_info, None, &INGOT_MINT_ADDRESS, true)?;
+    load_treasury(treasury_info, false)?;
+    load_program(token_program, spl_token::id())?;
+
+    // Reset the output counter if a new smelting epoch has started.
+    let mut config_data = config_info.data.borrow_mut();
+    let config = IngotConfig::try_from_bytes_mut(&mut config_data)?;
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    if clock
+        .unix_timestamp
+        .saturating_sub(config.epoch_start_at)
+        .ge(&SMELT_EPOCH_DURATION)
+    {
+        config.epoch_output = 0;
+        config.epoch_start_at = clock.unix_timestamp;
+    }
+
+    // Enforce the per-epoch output cap.
+    let epoch_output = config
+        .epoch_output
+        .checked_add(amount)
+        .ok_or(CoalError::SmeltCapExceeded)?;
+    if epoch_output.gt(&config.epoch_output_cap) {
+        return Err(CoalError::SmeltCapExceeded.into());
+    }
+    config.epoch_output = epoch_output;
+
+    // Calculate the cost of the requested output.
+    let coal_cost = smelt_cost(amount, config.coal_cost)?;
+    let wood_cost = smelt_cost(amount, config.wood_cost)?;
+    drop(config_data);
+
+    // Burn the inputs.
+    burn(
+        coal_tokens_info,
+        coal_mint_info,
+        signer,
+        token_program,
+        coal_cost,
+    )?;
+    burn(
+        wood_tokens_info,
+        wood_mint_info,
+        signer,
+        token_program,
+        wood_cost,
+    )?;
+
+    // Mint the output to the beneficiary.
+    mint_to_signed(
+        ingot_mint_info,
+        beneficiary_info,
+        treasury_info,
+        token_program,
+        amount,
+        &[&[TREASURY, &[TREASURY_BUMP]]],
+    )?;
+
+    Ok(())
+}
+
+/// Returns the cost of smelting the given amount of INGOT at a cost per whole INGOT.
+///
+/// The cost is rounded up, so smelting a fraction of a grain of INGOT still burns at least one grain
+/// of each input.
+fn smelt_cost(amount: u64, cost_per_ingot: u64) -> Result<u64, ProgramError> {
+    let cost = (amount as u128)
+        .checked_mul(cost_per_ingot as u128)
+        .unwrap()
+        .div_ceil(ONE_INGOT as u128);
+    u64::try_from(cost).or(Err(ProgramError::ArithmeticOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_smelt_cost() {
+        assert_eq!(smelt_cost(ONE_INGOT, INITIAL_SMELT_COAL_COST), Ok(INITIAL_SMELT_COAL_COST));
+        assert_eq!(smelt_cost(ONE_INGOT / 2, INITIAL_SMELT_WOOD_COST), Ok(INITIAL_SMELT_WOOD_COST / 2));
+        assert_eq!(smelt_cost(0, INITIAL_SMELT_COAL_COST), Ok(0));
+    }
+
+    #[test]
+    fn test_smelt_cost_rounds_up() {
+        // One grain of INGOT at one grain of COAL per INGOT would round down to nothing.
+        assert_eq!(smelt_cost(1, 1), Ok(1));
+        assert_eq!(smelt_cost(ONE_INGOT + 1, 2), Ok(3));
+        assert_eq!(smelt_cost(ONE_INGOT - 1, ONE_INGOT), Ok(ONE_INGOT - 1));
+    }
+
+    #[test]
+    fn test_smelt_cost_overflow() {
+        assert_eq!(smelt_cost(u64::MAX, ONE_INGOT * 2), Err(ProgramError::ArithmeticOverflow));
+    }
+}
@@ -11,16 +11,16 @@ use stake_wood::*;
 mod stake_coal;
 mod stake_wood;
 
-pub fn process_stake<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+pub fn process_stake(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     let config_info = &accounts[1];
 
-    if config_info.data.borrow()[0].eq(&(Proof::discriminator() as u8)) {
+    if config_info.data.borrow()[0].eq(&Proof::discriminator()) {
         return process_stake_coal(accounts, data)
     }
 
-    if config_info.data.borrow()[0].eq(&(ProofV2::discriminator() as u8)) {
+    if config_info.data.borrow()[0].eq(&ProofV2::discriminator()) {
         return process_stake_wood(accounts, data)
     }
 
-    return Err(ProgramError::InvalidAccountData);    
+    Err(ProgramError::InvalidAccountData)
 }
\ No newline at end of file
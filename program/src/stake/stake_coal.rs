@@ -1,4 +1,4 @@
-use coal_api::{consts::*, instruction::StakeArgs, loaders::*, state::Proof};
+use coal_api::{consts::*, instruction::StakeArgs, loaders::*, state::{Config, Proof}};
 use coal_utils::spl::transfer;
 use solana_program::{
     account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
@@ -8,20 +8,22 @@ use solana_program::{
 use crate::utils::AccountDeserialize;
 
 /// Stake deposits ORE into a proof account to earn multiplier.
-pub fn process_stake_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+pub fn process_stake_coal(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse args.
     let args = StakeArgs::try_from_bytes(data)?;
     let amount = u64::from_le_bytes(args.amount);
 
     // Load accounts.
-    let [signer, proof_info, sender_info, treasury_tokens_info, token_program] = accounts else {
+    let [signer, proof_info, config_info, sender_info, treasury_tokens_info, token_program, mint_info] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     load_signer(signer)?;
     load_coal_proof(proof_info, signer.key, true)?;
+    load_coal_config(config_info, true)?;
     load_token_account(sender_info, Some(signer.key), &COAL_MINT_ADDRESS, true)?;
     load_coal_treasury_tokens(treasury_tokens_info, true)?;
-    load_program(token_program, spl_token::id())?;
+    load_mint(mint_info, COAL_MINT_ADDRESS, false)?;
+    load_token_program(token_program, mint_info)?;
 
     // Update the proof balance.
     let mut proof_data = proof_info.data.borrow_mut();
@@ -32,11 +34,17 @@ pub fn process_stake_coal<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &
     let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
     proof.last_stake_at = clock.unix_timestamp;
 
+    // Track the liability owed to the miner.
+    let mut config_data = config_info.data.borrow_mut();
+    let config = Config::try_from_bytes_mut(&mut config_data)?;
+    config.outstanding_liabilities = crate::add_liabilities(config.outstanding_liabilities, amount)?;
+
     // Transfer tokens from signer to treasury.
     transfer(
         signer,
         sender_info,
         treasury_tokens_info,
+        mint_info,
         token_program,
         amount,
     )?;
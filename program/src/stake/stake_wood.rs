@@ -1,4 +1,4 @@
-use coal_api::{consts::*, instruction::StakeArgs, loaders::*, state::ProofV2};
+use coal_api::{consts::*, instruction::StakeArgs, loaders::*, state::{WoodConfig, ProofV2}};
 use coal_utils::spl::transfer;
 use solana_program::{
     account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
@@ -7,20 +7,22 @@ use solana_program::{
 
 use crate::utils::AccountDeserialize;
 
-pub fn process_stake_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+pub fn process_stake_wood(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse args.
     let args = StakeArgs::try_from_bytes(data)?;
     let amount = u64::from_le_bytes(args.amount);
 
     // Load accounts.
-    let [signer, proof_info, sender_info, treasury_tokens_info, token_program] = accounts else {
+    let [signer, proof_info, config_info, sender_info, treasury_tokens_info, token_program, mint_info] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
     load_signer(signer)?;
     load_proof_v2(proof_info, signer.key, &WOOD_MINT_ADDRESS, true)?;
+    load_wood_config(config_info, true)?;
     load_token_account(sender_info, Some(signer.key), &WOOD_MINT_ADDRESS, true)?;
     load_wood_treasury_tokens(treasury_tokens_info, true)?;
-    load_program(token_program, spl_token::id())?;
+    load_mint(mint_info, WOOD_MINT_ADDRESS, false)?;
+    load_token_program(token_program, mint_info)?;
 
     // Update the proof balance.
     let mut proof_data = proof_info.data.borrow_mut();
@@ -31,11 +33,17 @@ pub fn process_stake_wood<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &
     let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
     proof.last_stake_at = clock.unix_timestamp;
 
+    // Track the liability owed to the miner.
+    let mut config_data = config_info.data.borrow_mut();
+    let config = WoodConfig::try_from_bytes_mut(&mut config_data)?;
+    config.outstanding_liabilities = crate::add_liabilities(config.outstanding_liabilities, amount)?;
+
     // Transfer tokens from signer to treasury.
     transfer(
         signer,
         sender_info,
         treasury_tokens_info,
+        mint_info,
         token_program,
         amount,
     )?;
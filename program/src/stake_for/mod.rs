@@ -0,0 +1,65 @@
+use coal_api::{
+    consts::MIN_STAKE_FOR_AMOUNT,
+    error::CoalError,
+    state::{Proof, ProofV2},
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult,
+    program_error::ProgramError
+};
+
+use crate::utils::Discriminator;
+
+use stake_for_coal::*;
+use stake_for_wood::*;
+mod stake_for_coal;
+mod stake_for_wood;
+
+/// Stake for lets any signer deposit tokens into the treasury on behalf of a target proof.
+pub fn process_stake_for(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    let Some(proof_info) = accounts.get(1) else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if proof_info.data.borrow()[0].eq(&Proof::discriminator()) {
+        return process_stake_for_coal(accounts, data)
+    }
+
+    if proof_info.data.borrow()[0].eq(&ProofV2::discriminator()) {
+        return process_stake_for_wood(accounts, data)
+    }
+
+    Err(ProgramError::InvalidAccountData)
+}
+
+/// Errors if the amount is below the minimum deposit on behalf of another miner.
+///
+/// Each deposit restarts the stake timer of the target proof, which delays its stake multiplier by a
+/// minute. The minimum keeps anyone from doing so repeatedly for free.
+pub(crate) fn check_stake_for_amount(amount: u64) -> Result<(), ProgramError> {
+    if amount.lt(&MIN_STAKE_FOR_AMOUNT) {
+        return Err(CoalError::StakeTooSmall.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_stake_for_amount() {
+        assert!(check_stake_for_amount(MIN_STAKE_FOR_AMOUNT).is_ok());
+        assert!(check_stake_for_amount(u64::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_check_stake_for_amount_rejects_dust() {
+        assert_eq!(check_stake_for_amount(0), Err(CoalError::StakeTooSmall.into()));
+        assert_eq!(
+            check_stake_for_amount(MIN_STAKE_FOR_AMOUNT - 1),
+            Err(CoalError::StakeTooSmall.into())
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use coal_api::{
+    consts::*,
+    event::StakeEvent,
+    instruction::StakeArgs,
+    loaders::*,
+    state::{Config, Proof},
+};
+use coal_utils::spl::transfer;
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult,
+    program::set_return_data, program_error::ProgramError, sysvar::Sysvar,
+};
+
+use crate::utils::AccountDeserialize;
+
+/// Stake for deposits COAL from the signer into another miner's proof account.
+pub fn process_stake_for_coal(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
+    // Parse args.
+    let args = StakeArgs::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
+    crate::check_stake_for_amount(amount)?;
+
+    // Load accounts.
+    let [signer, proof_info, config_info, sender_info, treasury_tokens_info, token_program, mint_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any_coal_proof(proof_info, true)?;
+    load_coal_config(config_info, true)?;
+    load_token_account(sender_info, Some(signer.key), &COAL_MINT_ADDRESS, true)?;
+    load_coal_treasury_tokens(treasury_tokens_info, true)?;
+    load_mint(mint_info, COAL_MINT_ADDRESS, false)?;
+    load_token_program(token_program, mint_info)?;
+
+    // Update the target proof balance.
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+    proof.balance = proof.balance.checked_add(amount).unwrap();
+
+    // Update deposit timestamp.
+    //
+    // The multiplier only applies to stake deposited more than one minute ago, so third-party
+    // deposits are subject to the same flash loan guard as the miner's own.
+    // The minimum deposit keeps this from being used to hold back the target's multiplier for free.
+    let clock = Clock::get().or(Err(ProgramError::InvalidAccountData))?;
+    proof.last_stake_at = clock.unix_timestamp;
+
+    // Track the liability owed to the miner.
+    let mut config_data = config_info.data.borrow_mut();
+    let config = Config::try_from_bytes_mut(&mut config_data)?;
+    config.outstanding_liabilities = crate::add_liabilities(config.outstanding_liabilities, amount)?;
+
+    // Transfer tokens from signer to treasury.
+    transfer(
+        signer,
+        sender_info,
+        treasury_tokens_info,
+        mint_info,
+        token_program,
+        amount,
+    )?;
+
+    // Log the deposit.
+    set_return_data(
+        StakeEvent {
+            staker: *signer.key,
+            proof: *proof_info.key,
+            amount,
+            balance: proof.balance,
+        }
+        .to_bytes(),
+    );
+
+    Ok(())
+}
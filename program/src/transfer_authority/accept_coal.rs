@@ -0,0 +1,152 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    instruction::AcceptProofAuthorityArgs,
+    loaders::*,
+    state::{Proof, Tool},
+};
+use mpl_core::instructions::TransferV1CpiBuilder;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey, system_program,
+};
+
+use crate::utils::{create_pda, AccountDeserialize, AccountVersion, Discriminator};
+
+/// Accept moves a coal proof account, and its equipped tool, to the PDAs of the proposed authority.
+pub fn process_accept_proof_authority_coal(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = AcceptProofAuthorityArgs::try_from_bytes(data)?;
+
+    // Load accounts.
+    let (required_accounts, optional_accounts) = accounts.split_at(accounts.len().min(8));
+    let [signer, old_authority_info, old_proof_info, proof_info, system_program, old_miner_keys_info, old_tool_info, old_vesting_info] =
+        required_accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any(old_authority_info, true)?;
+    load_coal_proof(old_proof_info, old_authority_info.key, true)?;
+    load_uninitialized_pda(
+        proof_info,
+        &[COAL_PROOF, signer.key.as_ref()],
+        args.proof_bump,
+        &coal_api::id(),
+    )?;
+    load_program(system_program, system_program::id())?;
+    load_pda(
+        old_tool_info,
+        &[COAL_MAIN_HAND_TOOL, old_authority_info.key.as_ref()],
+        args.old_tool_bump as u64,
+    )?;
+
+    // Validate the signer is the proposed authority.
+    let old_proof_data = old_proof_info.data.borrow();
+    let old_proof = *Proof::try_from_bytes(&old_proof_data)?;
+    drop(old_proof_data);
+    if old_proof.pending_authority.ne(signer.key) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Initialize the new proof with the state of the old one.
+    create_pda(
+        proof_info,
+        &coal_api::id(),
+        8 + size_of::<Proof>(),
+        &[COAL_PROOF, signer.key.as_ref(), &[args.proof_bump]],
+        system_program,
+        signer,
+    )?;
+    let mut proof_data = proof_info.data.borrow_mut();
+    proof_data[0] = Proof::discriminator();
+    proof_data[1] = Proof::version();
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+    *proof = old_proof;
+    proof.authority = *signer.key;
+    proof.miner = *signer.key;
+    proof.bump = args.proof_bump as u64;
+    proof.pending_authority = Pubkey::default();
+    drop(proof_data);
+
+    // Move the equipped tool, if any.
+    //
+    // An equipped tool must move with the proof, since it is bound to the old authority's PDA.
+    if !old_tool_info.data_is_empty() {
+        let [tool_info, asset_info, collection_info, mpl_core_program] = optional_accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        load_program(mpl_core_program, mpl_core::ID)?;
+        load_uninitialized_pda(
+            tool_info,
+            &[COAL_MAIN_HAND_TOOL, signer.key.as_ref()],
+            args.tool_bump,
+            &coal_api::id(),
+        )?;
+        let old_tool_data = old_tool_info.data.borrow();
+        let old_tool = *Tool::try_from_bytes(&old_tool_data)?;
+        drop(old_tool_data);
+        if old_tool_info.owner.ne(&coal_api::id())
+            || old_tool.authority.ne(old_authority_info.key)
+            || old_tool.asset.ne(asset_info.key)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Initialize the new tool.
+        create_pda(
+            tool_info,
+            &coal_api::id(),
+            8 + size_of::<Tool>(),
+            &[COAL_MAIN_HAND_TOOL, signer.key.as_ref(), &[args.tool_bump]],
+            system_program,
+            signer,
+        )?;
+        let mut tool_data = tool_info.data.borrow_mut();
+        tool_data[0] = Tool::discriminator();
+        tool_data[1] = Tool::version();
+        let tool = Tool::try_from_bytes_mut(&mut tool_data)?;
+        *tool = old_tool;
+        tool.authority = *signer.key;
+        tool.miner = *signer.key;
+        tool.bump = args.tool_bump as u64;
+        drop(tool_data);
+
+        // Transfer the asset to the new tool.
+        TransferV1CpiBuilder::new(mpl_core_program)
+            .asset(asset_info)
+            .collection(Some(collection_info))
+            .payer(signer)
+            .authority(Some(old_tool_info))
+            .new_owner(tool_info)
+            .system_program(Some(system_program))
+            .invoke_signed(&[&[
+                COAL_MAIN_HAND_TOOL,
+                old_authority_info.key.as_ref(),
+                &[args.old_tool_bump],
+            ]])?;
+
+        // Close the old tool and return the rent to the old authority.
+        old_tool_info.realloc(0, true)?;
+        **old_authority_info.lamports.borrow_mut() += old_tool_info.lamports();
+        **old_tool_info.lamports.borrow_mut() = 0;
+    }
+
+    // Close the old proof's miner keys, since keys are seeded by the proof address.
+    crate::close_miner_keys(old_miner_keys_info, old_proof_info, old_authority_info)?;
+
+    // Close the old proof's vesting account. Transfers are blocked until every vesting token has
+    // been released to the old authority.
+    crate::close_vesting(old_vesting_info, old_proof_info, old_authority_info)?;
+
+    // Close the old proof and return the rent to the old authority.
+    old_proof_info.realloc(0, true)?;
+    **old_authority_info.lamports.borrow_mut() += old_proof_info.lamports();
+    **old_proof_info.lamports.borrow_mut() = 0;
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    instruction::AcceptProofAuthorityArgs,
+    loaders::*,
+    state::ProofV2,
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey, system_program,
+};
+
+use crate::utils::{create_pda, AccountDeserialize, AccountVersion, Discriminator};
+
+/// Accept moves a wood proof account to the PDA of the proposed authority.
+pub fn process_accept_proof_authority_wood(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    // Parse args.
+    let args = AcceptProofAuthorityArgs::try_from_bytes(data)?;
+
+    // Load accounts.
+    let [signer, old_authority_info, old_proof_info, proof_info, system_program, old_miner_keys_info, old_vesting_info] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_any(old_authority_info, true)?;
+    load_proof_v2(old_proof_info, old_authority_info.key, &WOOD_MINT_ADDRESS, true)?;
+    load_uninitialized_pda(
+        proof_info,
+        &[WOOD_PROOF, signer.key.as_ref()],
+        args.proof_bump,
+        &coal_api::id(),
+    )?;
+    load_program(system_program, system_program::id())?;
+
+    // Validate the signer is the proposed authority.
+    let old_proof_data = old_proof_info.data.borrow();
+    let old_proof = *ProofV2::try_from_bytes(&old_proof_data)?;
+    drop(old_proof_data);
+    if old_proof.pending_authority.ne(signer.key) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Initialize the new proof with the state of the old one.
+    create_pda(
+        proof_info,
+        &coal_api::id(),
+        8 + size_of::<ProofV2>(),
+        &[WOOD_PROOF, signer.key.as_ref(), &[args.proof_bump]],
+        system_program,
+        signer,
+    )?;
+    let mut proof_data = proof_info.data.borrow_mut();
+    proof_data[0] = ProofV2::discriminator();
+    proof_data[1] = ProofV2::version();
+    let proof = ProofV2::try_from_bytes_mut(&mut proof_data)?;
+    *proof = old_proof;
+    proof.authority = *signer.key;
+    proof.miner = *signer.key;
+    proof.bump = args.proof_bump as u64;
+    proof.pending_authority = Pubkey::default();
+    drop(proof_data);
+
+    // Close the old proof's miner keys, since keys are seeded by the proof address.
+    crate::close_miner_keys(old_miner_keys_info, old_proof_info, old_authority_info)?;
+
+    // Close the old proof's vesting account. Transfers are blocked until every vesting token has
+    // been released to the old authority.
+    crate::close_vesting(old_vesting_info, old_proof_info, old_authority_info)?;
+
+    // Close the old proof and return the rent to the old authority.
+    old_proof_info.realloc(0, true)?;
+    **old_authority_info.lamports.borrow_mut() += old_proof_info.lamports();
+    **old_proof_info.lamports.borrow_mut() = 0;
+
+    Ok(())
+}
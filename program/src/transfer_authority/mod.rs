@@ -0,0 +1,56 @@
+use coal_api::state::{Proof, ProofV2};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::utils::Discriminator;
+
+use accept_coal::*;
+use accept_wood::*;
+use propose_coal::*;
+use propose_wood::*;
+mod accept_coal;
+mod accept_wood;
+mod propose_coal;
+mod propose_wood;
+
+/// Propose nominates a new authority to take ownership of a proof account.
+pub fn process_propose_proof_authority(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    let Some(proof_info) = accounts.get(1) else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if proof_info.data.borrow()[0].eq(&Proof::discriminator()) {
+        return process_propose_proof_authority_coal(accounts, data);
+    }
+
+    if proof_info.data.borrow()[0].eq(&ProofV2::discriminator()) {
+        return process_propose_proof_authority_wood(accounts, data);
+    }
+
+    Err(ProgramError::InvalidAccountData)
+}
+
+/// Accept moves a proof account to the PDA of its proposed authority. Proofs with vesting tokens
+/// which have not been released cannot be moved.
+pub fn process_accept_proof_authority(
+    accounts: &[AccountInfo<'_>],
+    data: &[u8],
+) -> ProgramResult {
+    let Some(proof_info) = accounts.get(2) else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if proof_info.data.borrow()[0].eq(&Proof::discriminator()) {
+        return process_accept_proof_authority_coal(accounts, data);
+    }
+
+    if proof_info.data.borrow()[0].eq(&ProofV2::discriminator()) {
+        return process_accept_proof_authority_wood(accounts, data);
+    }
+
+    Err(ProgramError::InvalidAccountData)
+}
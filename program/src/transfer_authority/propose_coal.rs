@@ -0,0 +1,27 @@
+use coal_api::{loaders::*, state::Proof};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+};
+
+use crate::utils::AccountDeserialize;
+
+/// Propose nominates a new authority to take ownership of a coal proof account.
+pub fn process_propose_proof_authority_coal(
+    accounts: &[AccountInfo<'_>],
+    _data: &[u8],
+) -> ProgramResult {
+    // Load accounts.
+    let [signer, proof_info, new_authority_info] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(signer)?;
+    load_coal_proof(proof_info, signer.key, true)?;
+    load_any(new_authority_info, false)?;
+
+    // Record the pending authority.
+    let mut proof_data = proof_info.data.borrow_mut();
+    let proof = Proof::try_from_bytes_mut(&mut proof_data)?;
+    proof.pending_authority = *new_authority_info.key;
+
+    Ok(())
+}
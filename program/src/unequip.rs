@@ -1,11 +1,11 @@
 use coal_api::{consts::*, instruction::UnequipArgs, loaders::*};
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError, system_program
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError, pubkey::Pubkey, system_program
 };
 use mpl_core::{instructions::{TransferV1CpiBuilder, UpdatePluginV1CpiBuilder}, types::{Attribute, Attributes, Plugin}, Asset};
 
 /// Closes the tool account and updates the durability attribute.
-pub fn process_unequip_tool<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+pub fn process_unequip_tool(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     // Parse args.
     let args = UnequipArgs::try_from_bytes(data)?;
 
@@ -18,10 +18,40 @@ pub fn process_unequip_tool<'a, 'info>(accounts: &'a [AccountInfo<'info>], data:
 	load_signer(signer)?;
 	load_program(mpl_core_program, mpl_core::ID)?;
     load_program(system_program, system_program::id())?;
-	
-	
+
+    unequip_tool(
+        signer,
+        miner_info.key,
+        payer_info,
+        asset_info,
+        collection_info,
+        tool_info,
+        plugin_update_authority,
+        mpl_core_program,
+        system_program,
+        args.bump,
+        args.plugin_authority_bump,
+    )
+}
+
+/// Writes the remaining durability back to the asset, closes the tool account, and returns
+/// the asset to the signer.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn unequip_tool<'info>(
+    signer: &AccountInfo<'info>,
+    miner: &Pubkey,
+    payer_info: &AccountInfo<'info>,
+    asset_info: &AccountInfo<'info>,
+    collection_info: &AccountInfo<'info>,
+    tool_info: &AccountInfo<'info>,
+    plugin_update_authority: &AccountInfo<'info>,
+    mpl_core_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    bump: u8,
+    plugin_authority_bump: u8,
+) -> ProgramResult {
 	// Update durability attribute
-    let (durability, _) = load_tool(tool_info, miner_info.key, true)?;
+    let (durability, _) = load_tool(tool_info, miner, true)?;
 	let mut updated_attributes = vec![
 		Attribute {
 			key: "durability".to_string(),
@@ -42,7 +72,7 @@ pub fn process_unequip_tool<'a, 'info>(accounts: &'a [AccountInfo<'info>], data:
 		}
 	});
 
-	let plugin_authority_seeds = &[b"update_authority".as_ref(), &[args.plugin_authority_bump]];
+	let plugin_authority_seeds = &[b"update_authority".as_ref(), &[plugin_authority_bump]];
 	// Update attributes CPI
 	UpdatePluginV1CpiBuilder::new(mpl_core_program)
 		.asset(asset_info)
@@ -65,7 +95,7 @@ pub fn process_unequip_tool<'a, 'info>(accounts: &'a [AccountInfo<'info>], data:
 
 
 	// Transfer tool to signer
-	let signer_seeds = &[COAL_MAIN_HAND_TOOL, signer.key.as_ref(), &[args.bump]];
+	let signer_seeds = &[COAL_MAIN_HAND_TOOL, signer.key.as_ref(), &[bump]];
 	
 	TransferV1CpiBuilder::new(mpl_core_program)
 	  .asset(asset_info)
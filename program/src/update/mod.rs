@@ -11,16 +11,16 @@ mod update_coal;
 mod update_wood;
 
 /// Update changes the miner authority on a proof account.
-pub fn process_update<'a, 'info>(accounts: &'a [AccountInfo<'info>], data: &[u8]) -> ProgramResult {
+pub fn process_update(accounts: &[AccountInfo<'_>], data: &[u8]) -> ProgramResult {
     let config_info = &accounts[9];
 
-    if config_info.data.borrow()[0].eq(&(Proof::discriminator() as u8)) {
+    if config_info.data.borrow()[0].eq(&Proof::discriminator()) {
         return process_update_coal(accounts, data)
     }
 
-    if config_info.data.borrow()[0].eq(&(ProofV2::discriminator() as u8)) {
+    if config_info.data.borrow()[0].eq(&ProofV2::discriminator()) {
         return process_update_wood(accounts, data)
     }
 
-    return Err(ProgramError::InvalidAccountData);    
+    Err(ProgramError::InvalidAccountData)
 }
@@ -6,8 +6,8 @@ use solana_program::{
 use crate::utils::AccountDeserialize;
 
 /// Update changes the miner authority on a proof account.
-pub fn process_update_coal<'a, 'info>(
-    accounts: &'a [AccountInfo<'info>],
+pub fn process_update_coal(
+    accounts: &[AccountInfo<'_>],
     _data: &[u8],
 ) -> ProgramResult {
     // Load accounts.
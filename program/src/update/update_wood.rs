@@ -5,8 +5,8 @@ use solana_program::{
 
 use crate::utils::AccountDeserialize;
 
-pub fn process_update_wood<'a, 'info>(
-    accounts: &'a [AccountInfo<'info>],
+pub fn process_update_wood(
+    accounts: &[AccountInfo<'_>],
     _data: &[u8],
 ) -> ProgramResult {
     // Load accounts.
@@ -0,0 +1,470 @@
+use std::mem::size_of;
+
+use coal_api::{
+    consts::*,
+    loaders::*,
+    state::{
+        AccountDiscriminator, Bus, Config, IngotConfig, Leaderboard, MinerKeys, Proof, ProofV2,
+        Tool, Treasury, Vesting, WoodConfig,
+    },
+};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey, rent::Rent, system_program, sysvar::Sysvar,
+};
+
+use spl_token_2022::{extension::StateWithExtensions, state::Account};
+
+use crate::utils::{AccountDeserialize, AccountVersion};
+
+/// Upgrade reallocs a program account to the latest layout of its type.
+///
+/// Layouts are append-only, so new fields are zero-initialized, except for PDA bumps, bus counts,
+/// epoch durations, outstanding liabilities and referral expiries which are backfilled. The payer
+/// covers any additional rent required by the larger account.
+///
+/// Configs which predate the outstanding liabilities must be followed by the treasury token account
+/// of their resource. Referred proofs which predate the referral expiry must be followed by the
+/// config of their resource.
+pub fn process_upgrade_account(
+    accounts: &[AccountInfo<'_>],
+    _data: &[u8],
+) -> ProgramResult {
+    // Load accounts.
+    let (required_accounts, optional_accounts) = accounts.split_at(accounts.len().min(3));
+    let [payer_info, account_info, system_program] = required_accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_signer(payer_info)?;
+    load_program(system_program, system_program::id())?;
+
+    upgrade_in_place(account_info, payer_info, Some(system_program), optional_accounts)
+}
+
+/// Reallocs an account on an older layout to the latest layout of its type, and backfills the
+/// fields added since. Accounts already on the latest layout are left alone.
+///
+/// Instructions which write to proofs, busses and tools call this before loading them, so accounts
+/// which predate a layout change keep working without a separate upgrade. The system program is
+/// only required if the payer has to top up rent.
+pub(crate) fn upgrade_in_place<'info>(
+    account_info: &AccountInfo<'info>,
+    payer_info: &AccountInfo<'info>,
+    system_program: Option<&AccountInfo<'info>>,
+    optional_accounts: &[AccountInfo<'info>],
+) -> ProgramResult {
+    if account_info.owner.ne(&coal_api::id()) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+    if account_info.data_is_empty() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    if !account_info.is_writable {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Lookup the latest layout for the account type.
+    let (discriminator, current_version) = {
+        let data = account_info.data.borrow();
+        (data[0], data[1])
+    };
+    let (version, size) = latest_layout(discriminator)?;
+
+    // Nothing to do if the account is already on the latest layout.
+    if current_version.eq(&version) {
+        return Ok(());
+    }
+    if current_version.gt(&version) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Top up rent for the new account size.
+    let space = 8 + size;
+    let rent = Rent::get()?;
+    let additional_rent = rent
+        .minimum_balance(space)
+        .saturating_sub(account_info.lamports());
+    if additional_rent.gt(&0) {
+        let system_program = system_program.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        load_program(system_program, system_program::id())?;
+        solana_program::program::invoke(
+            &solana_program::system_instruction::transfer(
+                payer_info.key,
+                account_info.key,
+                additional_rent,
+            ),
+            &[
+                payer_info.clone(),
+                account_info.clone(),
+                system_program.clone(),
+            ],
+        )?;
+    }
+
+    // Realloc to the new size and bump the version.
+    account_info.realloc(space, true)?;
+    account_info.data.borrow_mut()[1] = version;
+
+    // Backfill the bump of the PDA and any fields without a zero default.
+    backfill(account_info, current_version, optional_accounts)
+}
+
+/// Returns the latest version and size of the layout of an account type.
+fn latest_layout(discriminator: u8) -> Result<(u8, usize), ProgramError> {
+    Ok(match AccountDiscriminator::try_from(discriminator).or(Err(ProgramError::InvalidAccountData))? {
+        AccountDiscriminator::Bus => (Bus::version(), size_of::<Bus>()),
+        AccountDiscriminator::Config => (Config::version(), size_of::<Config>()),
+        AccountDiscriminator::Proof => (Proof::version(), size_of::<Proof>()),
+        AccountDiscriminator::Treasury => (Treasury::version(), size_of::<Treasury>()),
+        AccountDiscriminator::ProofV2 => (ProofV2::version(), size_of::<ProofV2>()),
+        AccountDiscriminator::WoodConfig => (WoodConfig::version(), size_of::<WoodConfig>()),
+        AccountDiscriminator::Tool => (Tool::version(), size_of::<Tool>()),
+        AccountDiscriminator::MinerKeys => (MinerKeys::version(), size_of::<MinerKeys>()),
+        AccountDiscriminator::IngotConfig => (IngotConfig::version(), size_of::<IngotConfig>()),
+        AccountDiscriminator::Vesting => (Vesting::version(), size_of::<Vesting>()),
+        AccountDiscriminator::Leaderboard => (Leaderboard::version(), size_of::<Leaderboard>()),
+    })
+}
+
+/// Writes the canonical bump of the account address into accounts which store it, and the
+/// initial bus count and epoch duration into configs which predate them.
+///
+/// Configs upgraded from a layout before version 2 have never tracked their liabilities, so the
+/// counter starts at the treasury balance of the resource, which is all owed to miners.
+///
+/// Referred proofs upgraded from a layout before version 6 computed their referral expiry from the
+/// config on every hash, so it is fixed at what the config gives at the time of the upgrade.
+fn backfill(
+    account_info: &AccountInfo,
+    previous_version: u8,
+    optional_accounts: &[AccountInfo],
+) -> ProgramResult {
+    let mut data = account_info.data.borrow_mut();
+    let find_bump = |seeds: &[&[u8]]| -> Result<u64, ProgramError> {
+        let (address, bump) = Pubkey::find_program_address(seeds, &coal_api::id());
+        if address.ne(account_info.key) {
+            return Err(ProgramError::InvalidSeeds);
+        }
+        Ok(bump as u64)
+    };
+    match AccountDiscriminator::try_from(data[0]).or(Err(ProgramError::InvalidAccountData))? {
+        AccountDiscriminator::Bus => {
+            let bus = Bus::try_from_bytes_mut(&mut data)?;
+            bus.bump = find_bump(&[COAL_BUS, &[bus.id as u8]])
+                .or_else(|_| find_bump(&[WOOD_BUS, &[bus.id as u8]]))?;
+        }
+        AccountDiscriminator::Config => {
+            let config = Config::try_from_bytes_mut(&mut data)?;
+            config.bump = find_bump(&[COAL_CONFIG])?;
+            if config.bus_count.eq(&0) {
+                config.bus_count = BUS_COUNT as u64;
+            }
+            if config.epoch_duration.eq(&0) {
+                config.epoch_duration = COAL_EPOCH_DURATION;
+            }
+            if previous_version.lt(&2) {
+                let treasury_tokens_info = optional_accounts
+                    .first()
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                load_coal_treasury_tokens(treasury_tokens_info, false)?;
+                config.outstanding_liabilities = treasury_balance(treasury_tokens_info)?;
+            }
+        }
+        AccountDiscriminator::WoodConfig => {
+            let config = WoodConfig::try_from_bytes_mut(&mut data)?;
+            config.bump = find_bump(&[WOOD_CONFIG])?;
+            if config.bus_count.eq(&0) {
+                config.bus_count = BUS_COUNT as u64;
+            }
+            if config.epoch_duration.eq(&0) {
+                config.epoch_duration = WOOD_EPOCH_DURATION;
+            }
+            if previous_version.lt(&2) {
+                let treasury_tokens_info = optional_accounts
+                    .first()
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                load_wood_treasury_tokens(treasury_tokens_info, false)?;
+                config.outstanding_liabilities = treasury_balance(treasury_tokens_info)?;
+            }
+        }
+        AccountDiscriminator::Proof => {
+            let proof = Proof::try_from_bytes_mut(&mut data)?;
+            proof.bump = find_bump(&[COAL_PROOF, proof.authority.as_ref()])?;
+            if previous_version.lt(&6) && proof.referrer.ne(&Pubkey::default()) {
+                let config_info = optional_accounts
+                    .first()
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                load_coal_config(config_info, false)?;
+                let config_data = config_info.data.borrow();
+                let config = Config::try_from_bytes(&config_data)?;
+                proof.referral_expires_at = crate::calculate_referral_expires_at(
+                    proof.referred_at,
+                    config.referral_epochs,
+                    config.epoch_duration,
+                );
+            }
+        }
+        AccountDiscriminator::ProofV2 => {
+            let proof = ProofV2::try_from_bytes_mut(&mut data)?;
+            proof.bump = find_bump(&[WOOD_PROOF, proof.authority.as_ref()])?;
+            if previous_version.lt(&6) && proof.referrer.ne(&Pubkey::default()) {
+                let config_info = optional_accounts
+                    .first()
+                    .ok_or(ProgramError::NotEnoughAccountKeys)?;
+                load_wood_config(config_info, false)?;
+                let config_data = config_info.data.borrow();
+                let config = WoodConfig::try_from_bytes(&config_data)?;
+                proof.referral_expires_at = crate::calculate_referral_expires_at(
+                    proof.referred_at,
+                    config.referral_epochs,
+                    config.epoch_duration,
+                );
+            }
+        }
+        AccountDiscriminator::Tool => {
+            let tool = Tool::try_from_bytes_mut(&mut data)?;
+            tool.bump = find_bump(&[COAL_MAIN_HAND_TOOL, tool.authority.as_ref()])?;
+        }
+        AccountDiscriminator::Leaderboard => {
+            let leaderboard = Leaderboard::try_from_bytes_mut(&mut data)?;
+            leaderboard.bump =
+                find_bump(&[COAL_LEADERBOARD]).or_else(|_| find_bump(&[WOOD_LEADERBOARD]))?;
+        }
+        AccountDiscriminator::Treasury
+        | AccountDiscriminator::MinerKeys
+        | AccountDiscriminator::IngotConfig
+        | AccountDiscriminator::Vesting => {}
+    }
+
+    Ok(())
+}
+
+/// Returns the token balance of a treasury token account as a liabilities counter.
+fn treasury_balance(treasury_tokens_info: &AccountInfo) -> Result<i64, ProgramError> {
+    let amount = StateWithExtensions::<Account>::unpack(&treasury_tokens_info.data.borrow())?
+        .base
+        .amount;
+    i64::try_from(amount).or(Err(ProgramError::ArithmeticOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+    use coal_api::consts::{
+        COAL_CONFIG_ADDRESS, COAL_MINT_ADDRESS, COAL_TREASURY_TOKENS_ADDRESS, TREASURY_ADDRESS,
+        WOOD_LEADERBOARD_ADDRESS,
+    };
+    use solana_program::program_pack::Pack;
+
+    use super::*;
+    use crate::utils::Discriminator;
+
+    /// Serializes an account on its latest layout, as it is after the realloc.
+    fn account_data<T: AccountVersion + Discriminator + bytemuck::Pod>(account: &T) -> Vec<u8> {
+        let mut data = vec![0; 8];
+        data[0] = T::discriminator();
+        data[1] = T::version();
+        data.extend_from_slice(bytemuck::bytes_of(account));
+        data
+    }
+
+    /// Runs the backfill on the account data at the given address, upgraded from the previous
+    /// version of its layout.
+    fn run_backfill(address: Pubkey, data: &mut [u8]) -> ProgramResult {
+        let mut lamports = 0;
+        let owner = coal_api::id();
+        let info = AccountInfo::new(&address, false, true, &mut lamports, data, &owner, false, 0);
+        let previous_version = info.data.borrow()[1].saturating_sub(1);
+        backfill(&info, previous_version, &[])
+    }
+
+    #[test]
+    fn test_latest_layout() {
+        assert_eq!(
+            latest_layout(AccountDiscriminator::Proof.into()).unwrap(),
+            (Proof::version(), size_of::<Proof>())
+        );
+        assert_eq!(
+            latest_layout(AccountDiscriminator::WoodConfig.into()).unwrap(),
+            (WoodConfig::version(), size_of::<WoodConfig>())
+        );
+        assert!(latest_layout(0).is_err());
+    }
+
+    #[test]
+    fn test_backfill_config_defaults() {
+        let mut data = account_data(&Config::zeroed());
+        run_backfill(COAL_CONFIG_ADDRESS, &mut data).unwrap();
+        let config = Config::try_from_bytes(&data).unwrap();
+        assert_eq!(config.bump, Pubkey::find_program_address(&[COAL_CONFIG], &coal_api::id()).1 as u64);
+        assert_eq!(config.bus_count, BUS_COUNT as u64);
+        assert_eq!(config.epoch_duration, COAL_EPOCH_DURATION);
+    }
+
+    #[test]
+    fn test_backfill_config_keeps_set_fields() {
+        let mut data = account_data(&Config {
+            bus_count: 12,
+            epoch_duration: ONE_MINUTE * 3,
+            ..Config::zeroed()
+        });
+        run_backfill(COAL_CONFIG_ADDRESS, &mut data).unwrap();
+        let config = Config::try_from_bytes(&data).unwrap();
+        assert_eq!(config.bus_count, 12);
+        assert_eq!(config.epoch_duration, ONE_MINUTE * 3);
+    }
+
+    #[test]
+    fn test_backfill_wood_bus() {
+        let (address, bump) = Pubkey::find_program_address(&[WOOD_BUS, &[3]], &coal_api::id());
+        let mut data = account_data(&Bus {
+            id: 3,
+            ..Bus::zeroed()
+        });
+        run_backfill(address, &mut data).unwrap();
+        assert_eq!(Bus::try_from_bytes(&data).unwrap().bump, bump as u64);
+    }
+
+    #[test]
+    fn test_backfill_proof() {
+        let authority = Pubkey::new_unique();
+        let (address, bump) =
+            Pubkey::find_program_address(&[COAL_PROOF, authority.as_ref()], &coal_api::id());
+        let mut data = account_data(&Proof {
+            authority,
+            ..Proof::zeroed()
+        });
+        run_backfill(address, &mut data).unwrap();
+        assert_eq!(Proof::try_from_bytes(&data).unwrap().bump, bump as u64);
+    }
+
+    #[test]
+    fn test_backfill_proof_referral_expiry() {
+        let mut config_data = account_data(&Config {
+            referral_epochs: 10,
+            epoch_duration: ONE_MINUTE,
+            bump: Pubkey::find_program_address(&[COAL_CONFIG], &coal_api::id()).1 as u64,
+            ..Config::zeroed()
+        });
+        let mut config_lamports = 0;
+        let owner = coal_api::id();
+        let config_info = AccountInfo::new(
+            &COAL_CONFIG_ADDRESS,
+            false,
+            false,
+            &mut config_lamports,
+            &mut config_data,
+            &owner,
+            false,
+            0,
+        );
+        let authority = Pubkey::new_unique();
+        let address = Pubkey::find_program_address(&[COAL_PROOF, authority.as_ref()], &owner).0;
+
+        // Referred proofs which predate the expiry need the config to compute it.
+        let mut data = account_data(&Proof {
+            authority,
+            referrer: Pubkey::new_unique(),
+            referred_at: 1_000,
+            ..Proof::zeroed()
+        });
+        let mut lamports = 0;
+        let info = AccountInfo::new(&address, false, true, &mut lamports, &mut data, &owner, false, 0);
+        assert_eq!(backfill(&info, 5, &[]), Err(ProgramError::NotEnoughAccountKeys));
+        backfill(&info, 5, &[config_info]).unwrap();
+        assert_eq!(
+            Proof::try_from_bytes(&info.data.borrow()).unwrap().referral_expires_at,
+            1_000 + 10 * ONE_MINUTE
+        );
+
+        // Proofs without a referrer have nothing to backfill.
+        let mut data = account_data(&Proof {
+            authority,
+            ..Proof::zeroed()
+        });
+        let info = AccountInfo::new(&address, false, true, &mut lamports, &mut data, &owner, false, 0);
+        backfill(&info, 5, &[]).unwrap();
+        assert_eq!(Proof::try_from_bytes(&info.data.borrow()).unwrap().referral_expires_at, 0);
+    }
+
+    #[test]
+    fn test_backfill_wood_leaderboard() {
+        let mut data = account_data(&Leaderboard::zeroed());
+        run_backfill(WOOD_LEADERBOARD_ADDRESS, &mut data).unwrap();
+        assert_eq!(
+            Leaderboard::try_from_bytes(&data).unwrap().bump,
+            Pubkey::find_program_address(&[WOOD_LEADERBOARD], &coal_api::id()).1 as u64
+        );
+    }
+
+    #[test]
+    fn test_backfill_rejects_wrong_address() {
+        let mut data = account_data(&ProofV2 {
+            authority: Pubkey::new_unique(),
+            ..ProofV2::zeroed()
+        });
+        assert_eq!(
+            run_backfill(Pubkey::new_unique(), &mut data),
+            Err(ProgramError::InvalidSeeds)
+        );
+    }
+
+    #[test]
+    fn test_backfill_config_liabilities() {
+        let mut token_data = vec![0; spl_token::state::Account::LEN];
+        spl_token::state::Account::pack(
+            spl_token::state::Account {
+                mint: COAL_MINT_ADDRESS,
+                owner: TREASURY_ADDRESS,
+                amount: 1_000,
+                state: spl_token::state::AccountState::Initialized,
+                ..Default::default()
+            },
+            &mut token_data,
+        )
+        .unwrap();
+        let mut token_lamports = 0;
+        let token_program = spl_token::id();
+        let treasury_tokens_info = AccountInfo::new(
+            &COAL_TREASURY_TOKENS_ADDRESS,
+            false,
+            false,
+            &mut token_lamports,
+            &mut token_data,
+            &token_program,
+            false,
+            0,
+        );
+
+        // Configs which predate the counter start at the treasury balance.
+        let mut data = account_data(&Config::zeroed());
+        let mut lamports = 0;
+        let owner = coal_api::id();
+        let info = AccountInfo::new(
+            &COAL_CONFIG_ADDRESS,
+            false,
+            true,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        assert_eq!(backfill(&info, 1, &[]), Err(ProgramError::NotEnoughAccountKeys));
+        backfill(&info, 1, &[treasury_tokens_info.clone()]).unwrap();
+        assert_eq!(
+            Config::try_from_bytes(&info.data.borrow()).unwrap().outstanding_liabilities,
+            1_000
+        );
+
+        // Configs which already track it are left alone.
+        Config::try_from_bytes_mut(&mut info.data.borrow_mut())
+            .unwrap()
+            .outstanding_liabilities = 5;
+        backfill(&info, 2, &[treasury_tokens_info]).unwrap();
+        assert_eq!(
+            Config::try_from_bytes(&info.data.borrow()).unwrap().outstanding_liabilities,
+            5
+        );
+    }
+}
@@ -78,9 +78,20 @@ pub trait Discriminator {
     fn discriminator() -> u8;
 }
 
+/// The layout version of an account, stored in the second byte of the account header.
+pub trait AccountVersion {
+    fn version() -> u8;
+}
+
 pub trait AccountDeserialize {
     fn try_from_bytes(data: &[u8]) -> Result<&Self, ProgramError>;
     fn try_from_bytes_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError>;
+
+    /// Reads a copy of an account on the current layout or any older one. Layouts are append-only,
+    /// so an older layout is a prefix of the current one and the fields it lacks are zeroed.
+    fn read_from_bytes(data: &[u8]) -> Result<Self, ProgramError>
+    where
+        Self: Sized;
 }
 
 #[macro_export]
@@ -97,13 +108,25 @@ macro_rules! impl_to_bytes {
 #[macro_export]
 macro_rules! impl_account_from_bytes {
     ($struct_name:ident) => {
-        impl crate::utils::AccountDeserialize for $struct_name {
+        $crate::impl_account_from_bytes!($struct_name, 0);
+    };
+    ($struct_name:ident, $version:expr) => {
+        impl $crate::AccountVersion for $struct_name {
+            fn version() -> u8 {
+                $version
+            }
+        }
+
+        impl $crate::AccountDeserialize for $struct_name {
             fn try_from_bytes(
                 data: &[u8],
             ) -> Result<&Self, solana_program::program_error::ProgramError> {
                 if Self::discriminator().ne(&data[0]) {
                     return Err(solana_program::program_error::ProgramError::InvalidAccountData);
                 }
+                if <Self as $crate::AccountVersion>::version().ne(&data[1]) {
+                    return Err(solana_program::program_error::ProgramError::InvalidAccountData);
+                }
                 bytemuck::try_from_bytes::<Self>(&data[8..]).or(Err(
                     solana_program::program_error::ProgramError::InvalidAccountData,
                 ))
@@ -114,10 +137,30 @@ macro_rules! impl_account_from_bytes {
                 if Self::discriminator().ne(&data[0]) {
                     return Err(solana_program::program_error::ProgramError::InvalidAccountData);
                 }
+                if <Self as $crate::AccountVersion>::version().ne(&data[1]) {
+                    return Err(solana_program::program_error::ProgramError::InvalidAccountData);
+                }
                 bytemuck::try_from_bytes_mut::<Self>(&mut data[8..]).or(Err(
                     solana_program::program_error::ProgramError::InvalidAccountData,
                 ))
             }
+            fn read_from_bytes(
+                data: &[u8],
+            ) -> Result<Self, solana_program::program_error::ProgramError> {
+                if data.len().lt(&8) || Self::discriminator().ne(&data[0]) {
+                    return Err(solana_program::program_error::ProgramError::InvalidAccountData);
+                }
+                if data[1].gt(&<Self as $crate::AccountVersion>::version()) {
+                    return Err(solana_program::program_error::ProgramError::InvalidAccountData);
+                }
+                let body = &data[8..];
+                if body.len().gt(&core::mem::size_of::<Self>()) {
+                    return Err(solana_program::program_error::ProgramError::InvalidAccountData);
+                }
+                let mut account = <Self as bytemuck::Zeroable>::zeroed();
+                bytemuck::bytes_of_mut(&mut account)[..body.len()].copy_from_slice(body);
+                Ok(account)
+            }
         }
     };
 }
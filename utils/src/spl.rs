@@ -1,4 +1,13 @@
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult};
+use spl_token_2022::{extension::StateWithExtensions, state::Mint};
+
+/// Returns the decimals of a mint owned by either token program.
+#[inline(always)]
+pub fn mint_decimals(mint_info: &AccountInfo) -> Result<u8, solana_program::program_error::ProgramError> {
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    Ok(mint.base.decimals)
+}
 
 #[inline(always)]
 pub fn create_ata<'info>(
@@ -15,7 +24,7 @@ pub fn create_ata<'info>(
             funder_info.key,
             owner_info.key,
             mint_info.key,
-            &spl_token::id(),
+            token_program.key,
         ),
         &[
             funder_info.clone(),
@@ -34,21 +43,25 @@ pub fn transfer<'info>(
     authority_info: &AccountInfo<'info>,
     from_info: &AccountInfo<'info>,
     to_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
     token_program: &AccountInfo<'info>,
     amount: u64,
 ) -> ProgramResult {
     solana_program::program::invoke(
-        &spl_token::instruction::transfer(
-            &spl_token::id(),
+        &spl_token_2022::instruction::transfer_checked(
+            token_program.key,
             from_info.key,
+            mint_info.key,
             to_info.key,
             authority_info.key,
             &[authority_info.key],
             amount,
+            mint_decimals(mint_info)?,
         )?,
         &[
             token_program.clone(),
             from_info.clone(),
+            mint_info.clone(),
             to_info.clone(),
             authority_info.clone(),
         ],
@@ -60,22 +73,26 @@ pub fn transfer_signed<'info>(
     authority_info: &AccountInfo<'info>,
     from_info: &AccountInfo<'info>,
     to_info: &AccountInfo<'info>,
+    mint_info: &AccountInfo<'info>,
     token_program: &AccountInfo<'info>,
     amount: u64,
     signer_seeds: &[&[&[u8]]],
 ) -> ProgramResult {
     solana_program::program::invoke_signed(
-        &spl_token::instruction::transfer(
-            &spl_token::id(),
+        &spl_token_2022::instruction::transfer_checked(
+            token_program.key,
             from_info.key,
+            mint_info.key,
             to_info.key,
             authority_info.key,
             &[authority_info.key],
             amount,
+            mint_decimals(mint_info)?,
         )?,
         &[
             token_program.clone(),
             from_info.clone(),
+            mint_info.clone(),
             to_info.clone(),
             authority_info.clone(),
         ],
@@ -93,8 +110,8 @@ pub fn mint_to_signed<'info>(
     signer_seeds: &[&[&[u8]]],
 ) -> ProgramResult {
     solana_program::program::invoke_signed(
-        &spl_token::instruction::mint_to(
-            &spl_token::id(),
+        &spl_token_2022::instruction::mint_to(
+            token_program.key,
             mint_info.key,
             to_info.key,
             authority_info.key,
@@ -120,8 +137,8 @@ pub fn burn<'info>(
     amount: u64,
 ) -> ProgramResult {
     solana_program::program::invoke(
-        &spl_token::instruction::burn(
-            &spl_token::id(),
+        &spl_token_2022::instruction::burn(
+            token_program.key,
             token_account_info.key,
             mint_info.key,
             authority_info.key,